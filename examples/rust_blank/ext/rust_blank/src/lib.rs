@@ -5,15 +5,9 @@ fn is_blank(s: &str) -> bool {
 }
 
 fn rb_is_blank(rb_self: RString) -> Result<bool, Error> {
-    // RString::as_str is unsafe as it's possible for Ruby to invalidate the
-    // str as we hold a reference to it, but here we're only ever using the
-    // &str before Ruby is invoked again, so it doesn't get a chance to mess
-    // with it and this is safe.
-    unsafe {
-        match rb_self.as_str() {
-            Ok(s) => Ok(is_blank(s)),
-            Err(_) => Ok(is_blank(rb_self.encode_utf8()?.as_str()?)),
-        }
+    match rb_self.with_str(is_blank) {
+        Ok(blank) => Ok(blank),
+        Err(_) => rb_self.encode_utf8()?.with_str(is_blank),
     }
 }
 
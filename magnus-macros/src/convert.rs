@@ -0,0 +1,331 @@
+use darling::{util::Flag, FromMeta};
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, ToTokens};
+use syn::{
+    ext::IdentExt, spanned::Spanned, Attribute, Data, DeriveInput, Error, Field, Fields, Ident,
+    Index,
+};
+
+#[derive(Default, FromMeta)]
+struct ContainerAttributes {
+    #[darling(default)]
+    tag: Option<String>,
+    #[darling(default)]
+    string_keys: Flag,
+}
+
+#[derive(Default, FromMeta)]
+struct FieldAttributes {
+    #[darling(default)]
+    rename: Option<String>,
+    #[darling(default)]
+    default: Flag,
+    #[darling(default)]
+    optional: Flag,
+}
+
+#[derive(Default, FromMeta)]
+struct VariantAttributes {
+    #[darling(default)]
+    rename: Option<String>,
+}
+
+struct NamedField {
+    ident: Ident,
+    key: String,
+    attrs: FieldAttributes,
+}
+
+pub fn expand_try_convert(input: DeriveInput) -> Result<TokenStream, Error> {
+    check_generics(&input, "TryConvert")?;
+    let container = attributes::<ContainerAttributes>(&input.attrs)?;
+    let ident = &input.ident;
+    let root = snake_case(&ident.unraw().to_string());
+
+    let body = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => {
+                let fields = named_fields(fields.named.iter())?;
+                let convert = convert_named_fields(&fields);
+                quote! {
+                    let hash = magnus::derive::hash(val)?;
+                    Ok(Self { #(#convert)* })
+                }
+            }
+            Fields::Unnamed(fields) => {
+                let len = fields.unnamed.len();
+                let convert = (0..len).map(|i| {
+                    quote! { magnus::derive::element(ary, #i)?, }
+                });
+                quote! {
+                    let ary = magnus::derive::array(val, #len)?;
+                    Ok(Self(#(#convert)*))
+                }
+            }
+            Fields::Unit => return Err(unit_struct_error(&input)),
+        },
+        Data::Enum(data) => {
+            let tag = container.tag.unwrap_or_else(|| "type".to_owned());
+            let mut names = Vec::new();
+            let mut arms = Vec::new();
+            let mut has_named = false;
+            for variant in data.variants.iter() {
+                let attrs = attributes::<VariantAttributes>(&variant.attrs)?;
+                let name = attrs
+                    .rename
+                    .unwrap_or_else(|| snake_case(&variant.ident.unraw().to_string()));
+                let variant_ident = &variant.ident;
+                let arm = match &variant.fields {
+                    Fields::Unit => quote! { #name => Ok(Self::#variant_ident), },
+                    Fields::Named(fields) => {
+                        has_named = true;
+                        let fields = named_fields(fields.named.iter())?;
+                        let convert = convert_named_fields(&fields);
+                        quote! {
+                            #name => {
+                                let hash = hash.unwrap_or_else(magnus::RHash::new);
+                                Ok(Self::#variant_ident { #(#convert)* })
+                            }
+                        }
+                    }
+                    Fields::Unnamed(fields) => return Err(tuple_variant_error(fields.span())),
+                };
+                names.push(name);
+                arms.push(arm);
+            }
+            let hash = if has_named {
+                quote! { hash }
+            } else {
+                quote! { _ }
+            };
+            quote! {
+                let (name, #hash) = magnus::derive::variant(val, #tag)?;
+                match name.as_str() {
+                    #(#arms)*
+                    _ => Err(magnus::derive::unknown_variant(&name, &[#(#names),*])),
+                }
+            }
+        }
+        Data::Union(data) => {
+            return Err(Error::new(
+                data.union_token.span(),
+                "TryConvert can't be derived for unions",
+            ))
+        }
+    };
+
+    Ok(quote! {
+        impl magnus::TryConvert for #ident {
+            fn try_convert(val: &magnus::Value) -> Result<Self, magnus::Error> {
+                magnus::derive::root(#root, || { #body })
+            }
+        }
+    })
+}
+
+pub fn expand_into_value(input: DeriveInput) -> Result<TokenStream, Error> {
+    check_generics(&input, "IntoValue")?;
+    let container = attributes::<ContainerAttributes>(&input.attrs)?;
+    let string_keys = container.string_keys.is_some();
+    let ident = &input.ident;
+
+    let body = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => {
+                let fields = named_fields(fields.named.iter())?;
+                let set = set_named_fields(&fields, string_keys, |f| quote! { val.#f });
+                quote! {
+                    let hash = magnus::RHash::new();
+                    #(#set)*
+                    hash.into()
+                }
+            }
+            Fields::Unnamed(fields) => {
+                let len = fields.unnamed.len();
+                let push = (0..len).map(|i| {
+                    let i = Index::from(i);
+                    quote! { let _ = ary.push(val.#i); }
+                });
+                quote! {
+                    let ary = magnus::RArray::with_capacity(#len);
+                    #(#push)*
+                    ary.into()
+                }
+            }
+            Fields::Unit => return Err(unit_struct_error(&input)),
+        },
+        Data::Enum(data) => {
+            let tag = container.tag.unwrap_or_else(|| "type".to_owned());
+            let mut arms = Vec::new();
+            for variant in data.variants.iter() {
+                let attrs = attributes::<VariantAttributes>(&variant.attrs)?;
+                let name = attrs
+                    .rename
+                    .unwrap_or_else(|| snake_case(&variant.ident.unraw().to_string()));
+                let variant_ident = &variant.ident;
+                let arm = match &variant.fields {
+                    Fields::Unit => quote! {
+                        #ident::#variant_ident => magnus::Symbol::new(#name).into(),
+                    },
+                    Fields::Named(fields) => {
+                        let fields = named_fields(fields.named.iter())?;
+                        let idents = fields.iter().map(|f| &f.ident);
+                        let set = set_named_fields(&fields, string_keys, |f| f.to_token_stream());
+                        quote! {
+                            #ident::#variant_ident { #(#idents),* } => {
+                                let hash = magnus::derive::tagged_hash(#tag, #name, #string_keys);
+                                #(#set)*
+                                hash.into()
+                            }
+                        }
+                    }
+                    Fields::Unnamed(fields) => return Err(tuple_variant_error(fields.span())),
+                };
+                arms.push(arm);
+            }
+            quote! {
+                match val {
+                    #(#arms)*
+                }
+            }
+        }
+        Data::Union(data) => {
+            return Err(Error::new(
+                data.union_token.span(),
+                "IntoValue can't be derived for unions",
+            ))
+        }
+    };
+
+    Ok(quote! {
+        impl From<#ident> for magnus::Value {
+            fn from(val: #ident) -> Self {
+                #body
+            }
+        }
+    })
+}
+
+pub fn check_generics(input: &DeriveInput, name: &str) -> Result<(), Error> {
+    if input.generics.to_token_stream().is_empty() {
+        Ok(())
+    } else {
+        Err(Error::new(
+            input.generics.span(),
+            format!("{} can't be derived for generic types", name),
+        ))
+    }
+}
+
+fn unit_struct_error(input: &DeriveInput) -> Error {
+    Error::new(input.span(), "unit structs are not supported")
+}
+
+fn tuple_variant_error(span: Span) -> Error {
+    Error::new(
+        span,
+        "tuple variants are not supported, use a variant with named fields",
+    )
+}
+
+fn named_fields<'a, I>(fields: I) -> Result<Vec<NamedField>, Error>
+where
+    I: Iterator<Item = &'a Field>,
+{
+    fields
+        .map(|field| {
+            let ident = field.ident.clone().unwrap();
+            let attrs = attributes::<FieldAttributes>(&field.attrs)?;
+            if attrs.default.is_some() && attrs.optional.is_some() {
+                return Err(Error::new(
+                    field.span(),
+                    "`default` and `optional` can't be used together",
+                ));
+            }
+            let key = attrs
+                .rename
+                .clone()
+                .unwrap_or_else(|| ident.unraw().to_string());
+            Ok(NamedField { ident, key, attrs })
+        })
+        .collect()
+}
+
+fn convert_named_fields(fields: &[NamedField]) -> Vec<TokenStream> {
+    fields
+        .iter()
+        .map(|f| {
+            let ident = &f.ident;
+            let key = &f.key;
+            let missing = if f.attrs.default.is_some() {
+                quote! { magnus::derive::Missing::Default(Default::default) }
+            } else if f.attrs.optional.is_some() {
+                quote! { magnus::derive::Missing::Optional }
+            } else {
+                quote! { magnus::derive::Missing::Required }
+            };
+            quote! { #ident: magnus::derive::field(hash, #key, #missing)?, }
+        })
+        .collect()
+}
+
+fn set_named_fields<F>(fields: &[NamedField], string_keys: bool, access: F) -> Vec<TokenStream>
+where
+    F: Fn(&Ident) -> TokenStream,
+{
+    fields
+        .iter()
+        .map(|f| {
+            let key = &f.key;
+            let val = access(&f.ident);
+            let optional = f.attrs.optional.is_some();
+            quote! { magnus::derive::set_field(hash, #key, #val, #string_keys, #optional); }
+        })
+        .collect()
+}
+
+pub fn attributes<T>(attrs: &[Attribute]) -> Result<T, Error>
+where
+    T: Default + FromMeta,
+{
+    let mut attrs = attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("magnus"))
+        .collect::<Vec<_>>();
+    if attrs.len() > 1 {
+        return Err(attrs
+            .into_iter()
+            .map(|a| Error::new(a.span(), "duplicate attribute"))
+            .reduce(|mut a, b| {
+                a.combine(b);
+                a
+            })
+            .unwrap());
+    }
+    match attrs.pop() {
+        Some(attr) => {
+            let meta = attr.parse_meta()?;
+            T::from_meta(&meta).map_err(|e| Error::new(attr.span(), e.to_string()))
+        }
+        None => Ok(T::default()),
+    }
+}
+
+// `DarkRed` -> `dark_red`
+fn snake_case(s: &str) -> String {
+    let mut res = String::with_capacity(s.len() + 4);
+    let mut prev_lower = false;
+    for c in s.chars() {
+        if c.is_uppercase() {
+            if prev_lower {
+                res.push('_');
+            }
+            res.extend(c.to_lowercase());
+            prev_lower = false;
+        } else {
+            res.push(c);
+            prev_lower = c.is_lowercase() || c.is_numeric();
+        }
+    }
+    res
+}
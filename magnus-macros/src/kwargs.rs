@@ -0,0 +1,120 @@
+use darling::{util::Flag, FromMeta};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{ext::IdentExt, spanned::Spanned, Data, DeriveInput, Error, Fields, Ident, Type};
+
+use crate::convert::{attributes, check_generics};
+
+#[derive(Default, FromMeta)]
+struct FieldAttributes {
+    #[darling(default)]
+    rename: Option<String>,
+    #[darling(default)]
+    rest: Flag,
+}
+
+struct Keyword {
+    ident: Ident,
+    key: String,
+}
+
+pub fn expand(input: DeriveInput) -> Result<TokenStream, Error> {
+    check_generics(&input, "KwArgs")?;
+    let ident = &input.ident;
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => fields,
+            _ => {
+                return Err(Error::new(
+                    input.span(),
+                    "KwArgs can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new(
+                input.span(),
+                "KwArgs can only be derived for structs with named fields",
+            ))
+        }
+    };
+
+    let mut required = Vec::new();
+    let mut optional = Vec::new();
+    let mut rest = None;
+    for field in fields.named.iter() {
+        let attrs = attributes::<FieldAttributes>(&field.attrs)?;
+        let field_ident = field.ident.clone().unwrap();
+        if attrs.rest.is_some() {
+            if attrs.rename.is_some() {
+                return Err(Error::new(
+                    field.span(),
+                    "`rest` and `rename` can't be used together",
+                ));
+            }
+            if rest.is_some() {
+                return Err(Error::new(field.span(), "only one field can be `rest`"));
+            }
+            rest = Some(field_ident);
+            continue;
+        }
+        let key = attrs
+            .rename
+            .unwrap_or_else(|| field_ident.unraw().to_string());
+        let keyword = Keyword {
+            ident: field_ident,
+            key,
+        };
+        if is_option(&field.ty) {
+            optional.push(keyword);
+        } else {
+            required.push(keyword);
+        }
+    }
+
+    let required_keys = required.iter().map(|k| &k.key);
+    let optional_keys = optional.iter().map(|k| &k.key);
+    let convert = required
+        .iter()
+        .chain(optional.iter())
+        .enumerate()
+        .map(|(i, k)| {
+            let ident = &k.ident;
+            let key = &k.key;
+            quote! { #ident: magnus::derive::kwarg(&kw, #i, #key)?, }
+        });
+    let has_rest = rest.is_some();
+    let rest = rest.map(|ident| quote! { #ident: kw.rest(), });
+
+    Ok(quote! {
+        impl magnus::scan_args::FromKwArgs for #ident {
+            fn from_kwargs(kw: magnus::RHash) -> Result<Self, magnus::Error> {
+                let kw = magnus::derive::kwargs(
+                    kw,
+                    &[#(#required_keys),*],
+                    &[#(#optional_keys),*],
+                    #has_rest,
+                )?;
+                Ok(Self {
+                    #(#convert)*
+                    #rest
+                })
+            }
+        }
+    })
+}
+
+// Checks if the type is spelled `Option<...>`, there's no way to resolve the
+// actual type from within a macro.
+fn is_option(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .map(|s| s.ident == "Option")
+            .unwrap_or(false),
+        _ => false,
+    }
+}
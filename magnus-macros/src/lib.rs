@@ -20,7 +20,13 @@ use darling::{util::Flag, FromMeta};
 use proc_macro::TokenStream;
 use proc_macro2::{Ident, Span};
 use quote::{quote, ToTokens};
-use syn::{parse_macro_input, spanned::Spanned, AttributeArgs, DeriveInput, Error, ItemFn};
+use syn::{
+    parse_macro_input, spanned::Spanned, AttributeArgs, DeriveInput, Error, ItemFn, ItemImpl,
+};
+
+mod convert;
+mod kwargs;
+mod methods;
 
 #[derive(FromMeta)]
 struct InitAttributes {
@@ -175,6 +181,94 @@ pub fn wrap(attrs: TokenStream, item: TokenStream) -> TokenStream {
     tokens.into()
 }
 
+/// Define Ruby methods for every `pub fn` in an `impl` block.
+///
+/// This implements `magnus::method::DefineMethods` for the type, defining
+/// the methods on the class set with the `class = "..."` attribute of
+/// [`macro@wrap`] (or [`TypedData`]) when `define_methods` is called.
+///
+/// Functions taking `&self` or `&mut self` are defined as instance methods,
+/// and associated functions without a `self` argument are defined as
+/// singleton (class) methods. The arity is inferred from the number of
+/// arguments, or `-1` for a function taking a single `&[Value]` argument.
+/// Functions that are not `pub` are ignored.
+///
+/// # Attributes
+///
+/// Individual functions can be annotated with `#[magnus(...)]`, with the
+/// following values.
+///
+/// * `name = "..."` - sets the Ruby method name. Defaults to the name of the
+///   Rust function.
+/// * `singleton` - define as a singleton method. This is the default for
+///   functions without a `self` argument.
+/// * `private` - define as a private instance method.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{define_class, method::DefineMethods, Error};
+///
+/// #[magnus::wrap(class = "Point")]
+/// struct Point {
+///     x: isize,
+///     y: isize,
+/// }
+///
+/// #[magnus::methods]
+/// impl Point {
+///     pub fn new(x: isize, y: isize) -> Self {
+///         Self { x, y }
+///     }
+///
+///     pub fn x(&self) -> isize {
+///         self.x
+///     }
+///
+///     #[magnus(name = "x=")]
+///     pub fn set_x(&mut self, val: isize) {
+///         self.x = val;
+///     }
+///
+///     pub fn y(&self) -> isize {
+///         self.y
+///     }
+///
+///     #[magnus(name = "y=")]
+///     pub fn set_y(&mut self, val: isize) {
+///         self.y = val;
+///     }
+///
+///     #[magnus(private)]
+///     pub fn distance_squared(&self) -> isize {
+///         self.x.pow(2) + self.y.pow(2)
+///     }
+/// }
+///
+/// #[magnus::init]
+/// fn init() -> Result<(), Error> {
+///     define_class("Point", Default::default())?;
+///     Point::define_methods();
+///     Ok(())
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn methods(attrs: TokenStream, item: TokenStream) -> TokenStream {
+    if !attrs.is_empty() {
+        return Error::new(
+            proc_macro2::TokenStream::from(attrs).span(),
+            "#[magnus::methods] does not take any arguments",
+        )
+        .into_compile_error()
+        .into();
+    }
+    let item = parse_macro_input!(item as ItemImpl);
+    match methods::expand(item) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.into_compile_error().into(),
+    }
+}
+
 /// Derives `DataTypeFunctions` with default implementations, for simple uses
 /// of [`TypedData`].
 ///
@@ -364,3 +458,199 @@ pub fn derive_typed_data(input: TokenStream) -> TokenStream {
     };
     tokens.into()
 }
+
+/// Derives `TryConvert`, allowing the type to be converted from a Ruby
+/// object.
+///
+/// * Structs with named fields are converted from a Ruby `Hash`, with each
+///   field taken from the key of the same name. Keys may be either Symbols or
+///   Strings.
+/// * Tuple structs are converted from a Ruby `Array` with the same number of
+///   elements as the struct has fields.
+/// * Enum variants without fields are converted from a Symbol (or String) of
+///   the variant name in snake case. Variants with named fields are converted
+///   from a Hash with the variant name at the key `type`, and the fields at
+///   the other keys.
+///
+/// Errors include the path to the value that failed to convert, e.g.
+/// `config[:retries]: no implicit conversion of String into Integer`.
+///
+/// # Attributes
+///
+/// The `#[magnus(...)]` attribute can be set on the type with the following
+/// values.
+///
+/// * `tag = "..."` - the key holding the variant name for enums with named
+///   fields. Defaults to `type`.
+///
+/// Fields can be annotated with `#[magnus(...)]`, with the following values.
+///
+/// * `rename = "..."` - the Hash key for the field. Defaults to the field
+///   name.
+/// * `default` - use [`Default::default`] when the key is missing.
+/// * `optional` - convert `nil` when the key is missing, e.g. for `Option`
+///   fields.
+///
+/// Enum variants can also be annotated with `#[magnus(rename = "...")]` to
+/// set the variant name.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{define_global_function, function, TryConvert};
+///
+/// #[derive(TryConvert)]
+/// enum Mode {
+///     Fast,
+///     Safe,
+/// }
+///
+/// #[derive(TryConvert)]
+/// struct Config {
+///     #[magnus(rename = "max_retries")]
+///     retries: u32,
+///     #[magnus(default)]
+///     timeout: f64,
+///     #[magnus(optional)]
+///     name: Option<String>,
+///     mode: Mode,
+/// }
+///
+/// fn connect(config: Config) -> String {
+///     format!("connecting with {} retries", config.retries)
+/// }
+///
+/// #[magnus::init]
+/// fn init() {
+///     // connect(max_retries: 3, mode: :fast)
+///     define_global_function("connect", function!(connect, 1));
+/// }
+/// ```
+#[proc_macro_derive(TryConvert, attributes(magnus))]
+pub fn derive_try_convert(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match convert::expand_try_convert(input) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.into_compile_error().into(),
+    }
+}
+
+/// Derives `From<T> for magnus::Value`, allowing the type to be converted to
+/// a Ruby object.
+///
+/// This is the reverse of [`derive@TryConvert`], structs with named fields
+/// are converted to a Hash with Symbol keys, tuple structs to an Array, and
+/// enums to a Symbol, or Hash for variants with named fields.
+///
+/// # Attributes
+///
+/// The `#[magnus(...)]` attribute can be set on the type with the following
+/// values.
+///
+/// * `tag = "..."` - the key holding the variant name for enums with named
+///   fields. Defaults to `type`.
+/// * `string_keys` - use String rather than Symbol keys.
+///
+/// Fields can be annotated with `#[magnus(...)]`, with the following values.
+///
+/// * `rename = "..."` - the Hash key for the field. Defaults to the field
+///   name.
+/// * `optional` - omit the key when the value is `nil`.
+///
+/// Enum variants can also be annotated with `#[magnus(rename = "...")]` to
+/// set the variant name.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{define_global_function, function, IntoValue};
+///
+/// #[derive(IntoValue)]
+/// enum Shape {
+///     Circle { radius: f64 },
+///     Rectangle { width: f64, height: f64 },
+/// }
+///
+/// #[derive(IntoValue)]
+/// struct Point(f64, f64);
+///
+/// fn unit_circle() -> Shape {
+///     // {type: :circle, radius: 1.0}
+///     Shape::Circle { radius: 1.0 }
+/// }
+///
+/// fn origin() -> Point {
+///     // [0.0, 0.0]
+///     Point(0.0, 0.0)
+/// }
+///
+/// #[magnus::init]
+/// fn init() {
+///     define_global_function("unit_circle", function!(unit_circle, 0));
+///     define_global_function("origin", function!(origin, 0));
+/// }
+/// ```
+#[proc_macro_derive(IntoValue, attributes(magnus))]
+pub fn derive_into_value(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match convert::expand_into_value(input) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.into_compile_error().into(),
+    }
+}
+
+/// Derives `magnus::scan_args::FromKwArgs`, allowing the type to be extracted
+/// from keyword arguments.
+///
+/// Each field is taken from the keyword of the same name. Fields with an
+/// `Option<T>` type are optional keywords, all other fields are required.
+///
+/// Missing required keywords, or unknown keywords, will result in an
+/// `ArgumentError` naming them, e.g. `unknown keyword: :retires`.
+///
+/// # Attributes
+///
+/// Fields can be annotated with `#[magnus(...)]`, with the following values.
+///
+/// * `rename = "..."` - the keyword for the field. Defaults to the field
+///   name.
+/// * `rest` - collect any other keywords in to this field, which must be a
+///   `magnus::RHash`. Unknown keywords are not an error when this is set.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{
+///     define_global_function, function, scan_args::scan_args, Error, KwArgs, RHash, Value,
+/// };
+///
+/// #[derive(KwArgs)]
+/// struct ConnectOptions {
+///     host: String,
+///     port: Option<u16>,
+///     #[magnus(rename = "timeout")]
+///     timeout_secs: Option<f64>,
+///     #[magnus(rest)]
+///     extra: RHash,
+/// }
+///
+/// fn connect(args: &[Value]) -> Result<String, Error> {
+///     let args = scan_args::<(), (), (), (), ConnectOptions, ()>(args)?;
+///     let opts = args.keywords;
+///     Ok(format!("{}:{}", opts.host, opts.port.unwrap_or(80)))
+/// }
+///
+/// #[magnus::init]
+/// fn init() {
+///     // connect(host: "localhost", port: 8080, timeout: 1.5)
+///     define_global_function("connect", function!(connect, -1));
+/// }
+/// ```
+#[proc_macro_derive(KwArgs, attributes(magnus))]
+pub fn derive_kw_args(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match kwargs::expand(input) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.into_compile_error().into(),
+    }
+}
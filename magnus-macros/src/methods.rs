@@ -0,0 +1,208 @@
+use darling::{util::Flag, FromMeta};
+use proc_macro2::{Literal, TokenStream};
+use quote::{quote, ToTokens};
+use syn::{
+    ext::IdentExt, spanned::Spanned, Attribute, Error, FnArg, ImplItem, ImplItemMethod, ItemImpl,
+    Type, Visibility,
+};
+
+#[derive(Default, FromMeta)]
+struct MethodAttributes {
+    #[darling(default)]
+    name: Option<String>,
+    #[darling(default)]
+    singleton: Flag,
+    #[darling(default)]
+    private: Flag,
+}
+
+enum Kind {
+    Method,
+    PrivateMethod,
+    Singleton,
+}
+
+struct Binding {
+    name: String,
+    kind: Kind,
+    arity: i8,
+    ident: syn::Ident,
+}
+
+pub fn expand(mut item: ItemImpl) -> Result<TokenStream, Error> {
+    if let Some((_, path, _)) = &item.trait_ {
+        return Err(Error::new(
+            path.span(),
+            "#[magnus::methods] can only be used on inherent impls",
+        ));
+    }
+    if !item.generics.params.is_empty() {
+        return Err(Error::new(
+            item.generics.span(),
+            "#[magnus::methods] can't be used with generic types",
+        ));
+    }
+
+    let mut bindings = Vec::new();
+    let mut errors: Option<Error> = None;
+    for impl_item in item.items.iter_mut() {
+        if let ImplItem::Method(method) = impl_item {
+            match binding(method) {
+                Ok(Some(b)) => bindings.push(b),
+                Ok(None) => (),
+                Err(e) => match errors.as_mut() {
+                    Some(errors) => errors.combine(e),
+                    None => errors = Some(e),
+                },
+            }
+        }
+    }
+    if let Some(e) = errors {
+        return Err(e);
+    }
+
+    let self_ty = &item.self_ty;
+    let defs = bindings.into_iter().map(|b| {
+        let name = b.name;
+        let ident = b.ident;
+        // method!/function! match on the literal tokens, so no suffix
+        let arity = if b.arity < 0 {
+            let n = Literal::i8_unsuffixed(-b.arity);
+            quote! { -#n }
+        } else {
+            Literal::i8_unsuffixed(b.arity).into_token_stream()
+        };
+        match b.kind {
+            Kind::Method => quote! {
+                class.define_method(#name, magnus::method!(<#self_ty>::#ident, #arity));
+            },
+            Kind::PrivateMethod => quote! {
+                class.define_private_method(#name, magnus::method!(<#self_ty>::#ident, #arity));
+            },
+            Kind::Singleton => quote! {
+                class.define_singleton_method(#name, magnus::function!(<#self_ty>::#ident, #arity));
+            },
+        }
+    });
+
+    Ok(quote! {
+        #item
+
+        impl magnus::method::DefineMethods for #self_ty {
+            fn define_methods() {
+                use magnus::{Module, Object};
+                let class = <Self as magnus::TypedData>::class();
+                #(#defs)*
+            }
+        }
+    })
+}
+
+fn binding(method: &mut ImplItemMethod) -> Result<Option<Binding>, Error> {
+    let attrs = take_attributes(&mut method.attrs)?;
+    if !matches!(method.vis, Visibility::Public(_)) {
+        if let Some(attr) = attrs.1 {
+            return Err(Error::new(
+                attr.span(),
+                "#[magnus] attribute on a method that is not `pub`",
+            ));
+        }
+        return Ok(None);
+    }
+    let attrs = attrs.0;
+    let sig = &method.sig;
+
+    let mut inputs = sig.inputs.iter().peekable();
+    let receiver = match inputs.peek() {
+        Some(FnArg::Receiver(r)) => {
+            if r.reference.is_none() {
+                return Err(Error::new(
+                    r.span(),
+                    "`self` by value is not supported, use `&self` or `&mut self`",
+                ));
+            }
+            inputs.next();
+            true
+        }
+        _ => false,
+    };
+    let args = inputs.collect::<Vec<_>>();
+
+    let kind = match (receiver, attrs.singleton.is_some(), attrs.private.is_some()) {
+        (true, true, _) => {
+            return Err(Error::new(
+                sig.span(),
+                "singleton methods can't take `self`",
+            ))
+        }
+        (false, _, true) => {
+            return Err(Error::new(
+                sig.span(),
+                "private singleton methods are not supported",
+            ))
+        }
+        (true, false, false) => Kind::Method,
+        (true, false, true) => Kind::PrivateMethod,
+        (false, _, false) => Kind::Singleton,
+    };
+
+    // `self` counts towards the limit of arguments supported by `method!`
+    let max_args = if receiver { 31 } else { 32 };
+    let arity = match args.as_slice() {
+        [FnArg::Typed(arg)] if is_slice(&arg.ty) => -1,
+        args if args.len() > max_args => {
+            return Err(Error::new(
+                sig.inputs.span(),
+                format!("too many arguments, methods can take at most {}", max_args),
+            ))
+        }
+        args => args.len() as i8,
+    };
+
+    let name = attrs.name.unwrap_or_else(|| sig.ident.unraw().to_string());
+
+    Ok(Some(Binding {
+        name,
+        kind,
+        arity,
+        ident: sig.ident.clone(),
+    }))
+}
+
+// Removes any `#[magnus(...)]` attributes, returning them parsed, along with
+// the original attribute for error reporting.
+fn take_attributes(
+    attrs: &mut Vec<Attribute>,
+) -> Result<(MethodAttributes, Option<Attribute>), Error> {
+    let (mut magnus_attrs, rest): (Vec<_>, Vec<_>) = attrs
+        .drain(..)
+        .partition(|attr| attr.path.is_ident("magnus"));
+    *attrs = rest;
+    if magnus_attrs.len() > 1 {
+        return Err(magnus_attrs
+            .into_iter()
+            .map(|a| Error::new(a.span(), "duplicate attribute"))
+            .reduce(|mut a, b| {
+                a.combine(b);
+                a
+            })
+            .unwrap());
+    }
+    match magnus_attrs.pop() {
+        Some(attr) => {
+            let meta = attr.parse_meta()?;
+            let parsed = MethodAttributes::from_meta(&meta)
+                .map_err(|e| Error::new(attr.span(), e.to_string()))?;
+            Ok((parsed, Some(attr)))
+        }
+        None => Ok((MethodAttributes::default(), None)),
+    }
+}
+
+// `&[Value]`, for methods taking a variable number of arguments.
+fn is_slice(ty: &Type) -> bool {
+    match ty {
+        Type::Reference(r) => matches!(&*r.elem, Type::Slice(_)),
+        _ => false,
+    }
+}
@@ -0,0 +1,66 @@
+//! Conversions between Ruby's `Integer` and [num-bigint]'s `BigInt` and
+//! `BigUint`.
+//!
+//! Only available when the `num-bigint` feature is enabled.
+//!
+//! [num-bigint]: https://docs.rs/num-bigint
+
+use num_bigint::{BigInt, BigUint, Sign};
+
+use crate::{
+    error::Error,
+    exception,
+    integer::{Integer, IntegerType},
+    r_bignum::{unpack_le, INTEGER_PACK_NEGATIVE},
+    try_convert::{TryConvert, TryConvertOwned},
+    value::Value,
+};
+
+impl From<BigInt> for Value {
+    fn from(val: BigInt) -> Self {
+        let (sign, bytes) = val.to_bytes_le();
+        let flags = if sign == Sign::Minus {
+            INTEGER_PACK_NEGATIVE
+        } else {
+            0
+        };
+        unpack_le(&bytes, flags).into()
+    }
+}
+
+impl From<BigUint> for Value {
+    fn from(val: BigUint) -> Self {
+        unpack_le(&val.to_bytes_le(), 0).into()
+    }
+}
+
+impl TryConvert for BigInt {
+    fn try_convert(val: &Value) -> Result<Self, Error> {
+        match Integer::try_convert(val)?.integer_type() {
+            IntegerType::Fixnum(fix) => Ok(BigInt::from(fix.to_i64())),
+            IntegerType::Bignum(big) => {
+                let sign = if big.is_negative() {
+                    Sign::Minus
+                } else {
+                    Sign::Plus
+                };
+                Ok(BigInt::from_bytes_le(sign, &big.to_bytes_le()))
+            }
+        }
+    }
+}
+impl TryConvertOwned for BigInt {}
+
+impl TryConvert for BigUint {
+    fn try_convert(val: &Value) -> Result<Self, Error> {
+        match Integer::try_convert(val)?.integer_type() {
+            IntegerType::Fixnum(fix) => fix.to_u64().map(BigUint::from),
+            IntegerType::Bignum(big) if big.is_negative() => Err(Error::new(
+                exception::range_error(),
+                "can't convert negative integer to unsigned",
+            )),
+            IntegerType::Bignum(big) => Ok(BigUint::from_bytes_le(&big.to_bytes_le())),
+        }
+    }
+}
+impl TryConvertOwned for BigUint {}
@@ -1,15 +1,27 @@
 //! Types and functions for working with Ruby blocks and Procs.
 
-use std::{fmt, mem::forget, ops::Deref, os::raw::c_int};
+use std::{
+    any::Any,
+    cell::RefCell,
+    fmt,
+    mem::{align_of, align_of_val, forget, size_of, size_of_val},
+    ops::Deref,
+    os::raw::c_int,
+    panic::{catch_unwind, AssertUnwindSafe},
+    ptr,
+    sync::Once,
+};
 
 use crate::{
     enumerator::Enumerator,
-    error::{ensure, protect, Error},
+    error::{ensure, protect, raise, Error},
     exception,
+    method::{Block, BlockReturn},
     r_array::RArray,
+    r_typed_data::{self, DataType, DataTypeFunctions},
     ruby_sys::{
-        rb_block_given_p, rb_block_proc, rb_obj_is_proc, rb_proc_call, rb_yield, rb_yield_splat,
-        rb_yield_values2, VALUE,
+        rb_block_given_p, rb_block_proc, rb_gc_mark_locations, rb_obj_is_proc, rb_proc_call,
+        rb_proc_new, rb_yield, rb_yield_splat, rb_yield_values2, VALUE,
     },
     try_convert::{ArgList, RArrayArgList, TryConvert},
     value::{NonZeroValue, Value},
@@ -34,6 +46,148 @@ impl Proc {
         }
     }
 
+    #[inline]
+    pub(crate) unsafe fn from_rb_value_unchecked(val: VALUE) -> Self {
+        Self(NonZeroValue::new_unchecked(Value::new(val)))
+    }
+
+    /// Create a new `Proc` from the function `block`.
+    ///
+    /// `block` will receive the arguments the proc is called with as a slice
+    /// of [`Value`]s, plus `Some(Proc)` if the proc itself was called with a
+    /// block, or `None` otherwise.
+    ///
+    /// `block` may return any `R` or `Result<R, Error>` where `R` implements
+    /// `Into<Value>`. Returning `Err(Error)` will raise the error as a Ruby
+    /// exception.
+    ///
+    /// See [`Proc::from_fn`] to create a `Proc` from a closure.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{block::Proc, eval};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let proc = Proc::new(|args, _block| {
+    ///     let acc = args[0].try_convert::<i64>()?;
+    ///     let i = args[1].try_convert::<i64>()?;
+    ///     Ok::<_, magnus::Error>(acc + i)
+    /// });
+    ///
+    /// let res: bool = eval!("[1, 2, 3, 4, 5].inject(&proc) == 15", proc).unwrap();
+    /// assert!(res);
+    /// ```
+    pub fn new<R>(block: fn(&[Value], Option<Proc>) -> R) -> Self
+    where
+        R: BlockReturn,
+    {
+        unsafe extern "C" fn call<R>(
+            _yielded_arg: VALUE,
+            callback_arg: VALUE,
+            argc: c_int,
+            argv: *const VALUE,
+            blockarg: VALUE,
+        ) -> VALUE
+        where
+            R: BlockReturn,
+        {
+            let func = std::mem::transmute::<VALUE, fn(&[Value], Option<Proc>) -> R>(callback_arg);
+            Block::new(func)
+                .call_handle_error(argc, argv as *const Value, Value::new(blockarg))
+                .as_rb_value()
+        }
+
+        let call_func =
+            call::<R> as unsafe extern "C" fn(VALUE, VALUE, c_int, *const VALUE, VALUE) -> VALUE;
+        #[cfg(ruby_lt_2_7)]
+        let call_func: unsafe extern "C" fn() -> VALUE = unsafe { std::mem::transmute(call_func) };
+
+        unsafe {
+            Self::from_rb_value_unchecked(rb_proc_new(Some(call_func), block as usize as VALUE))
+        }
+    }
+
+    /// Create a new `Proc` from the closure `block`.
+    ///
+    /// As [`Proc::new`], but `block` may capture variables from its
+    /// environment. `block` is kept alive as long as the proc is, and dropped
+    /// when the proc is garbage collected.
+    ///
+    /// Ruby values captured directly by `block` are kept alive along with it,
+    /// but values captured within a heap allocation (such as in a `Vec`) are
+    /// not tracked by the garbage collector, and must be kept alive by some
+    /// other means.
+    ///
+    /// If the proc is called again while `block` is running, such as by
+    /// `block` calling the proc, the inner call raises a `RuntimeError`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{block::Proc, eval};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let mut count = 0;
+    /// let proc = Proc::from_fn(move |args, _block| {
+    ///     count += args.len();
+    ///     count
+    /// });
+    ///
+    /// assert_eq!(proc.call::<_, usize>((1, 2)).unwrap(), 2);
+    /// assert_eq!(proc.call::<_, usize>((3,)).unwrap(), 3);
+    /// ```
+    pub fn from_fn<F, R>(block: F) -> Self
+    where
+        F: FnMut(&[Value], Option<Proc>) -> R + 'static,
+        R: BlockReturn,
+    {
+        unsafe extern "C" fn call<F, R>(
+            _yielded_arg: VALUE,
+            callback_arg: VALUE,
+            argc: c_int,
+            argv: *const VALUE,
+            blockarg: VALUE,
+        ) -> VALUE
+        where
+            F: FnMut(&[Value], Option<Proc>) -> R + 'static,
+            R: BlockReturn,
+        {
+            let cell = &*Captured::get::<RefCell<F>>(Value::new(callback_arg));
+            // the borrow must be released before raising, as raising skips
+            // destructors
+            let res = match cell.try_borrow_mut() {
+                Ok(mut func) => catch_unwind(AssertUnwindSafe(|| {
+                    Block::new(&mut *func).call_convert_value(
+                        argc,
+                        argv as *const Value,
+                        Value::new(blockarg),
+                    )
+                }))
+                .unwrap_or_else(|e| Err(Error::from_panic(e))),
+                Err(_) => Err(Error::new(
+                    exception::runtime_error(),
+                    "proc called recursively",
+                )),
+            };
+            match res {
+                Ok(v) => v.as_rb_value(),
+                Err(e) => raise(e),
+            }
+        }
+
+        let call_func =
+            call::<F, R> as unsafe extern "C" fn(VALUE, VALUE, c_int, *const VALUE, VALUE) -> VALUE;
+        #[cfg(ruby_lt_2_7)]
+        let call_func: unsafe extern "C" fn() -> VALUE = unsafe { std::mem::transmute(call_func) };
+
+        // the proc's block references `captured`, keeping it alive
+        let captured = Captured::wrap(RefCell::new(block));
+        unsafe {
+            Self::from_rb_value_unchecked(rb_proc_new(Some(call_func), captured.as_rb_value()))
+        }
+    }
+
     /// Call the proc with `args`.
     ///
     /// Returns `Ok(T)` if the proc runs without error and the return value
@@ -247,6 +401,69 @@ where
     );
 }
 
+// Owner of Rust data, such as a closure, that must live as long as some Ruby
+// object. The data is wrapped in a hidden Ruby object, which should be
+// referenced from the object the data is tied to.
+//
+// Ruby values held directly by the data are kept alive by conservatively
+// scanning it for anything that looks like a Ruby object, as Ruby does with
+// the C stack. Values behind a pointer (e.g. in a `Vec`) won't be found.
+pub(crate) struct Captured(Box<dyn Any>);
+
+impl Captured {
+    fn data_type() -> &'static DataType {
+        static INIT: Once = Once::new();
+        static mut DATA_TYPE: Option<DataType> = None;
+        unsafe {
+            INIT.call_once(|| {
+                let mut builder = DataType::builder::<Self>("magnus captured data");
+                builder.mark();
+                builder.free_immediatly();
+                DATA_TYPE = Some(builder.build());
+            });
+            (*ptr::addr_of!(DATA_TYPE)).as_ref().unwrap()
+        }
+    }
+
+    // Wrap `data` in a new hidden Ruby object.
+    pub(crate) fn wrap<T>(data: T) -> Value
+    where
+        T: 'static,
+    {
+        unsafe {
+            Value::new(r_typed_data::wrap(
+                0,
+                Self(Box::new(data)),
+                Self::data_type(),
+            ))
+        }
+    }
+
+    // Get a pointer to the data wrapped by `val`.
+    //
+    // `val` must have been returned from `Captured::wrap::<T>`. The pointer is
+    // only valid while `val` is alive.
+    pub(crate) unsafe fn get<T>(val: Value) -> *mut T
+    where
+        T: 'static,
+    {
+        let captured = &mut *r_typed_data::get_unchecked::<Self>(val);
+        captured.0.downcast_mut::<T>().unwrap()
+    }
+}
+
+impl DataTypeFunctions for Captured {
+    fn mark(&mut self) {
+        // anything aligned less than a VALUE can't contain a Ruby object
+        if align_of_val(&*self.0) < align_of::<VALUE>() {
+            return;
+        }
+        let start = &*self.0 as *const dyn Any as *const VALUE;
+        let len = size_of_val(&*self.0) / size_of::<VALUE>();
+        unsafe { rb_gc_mark_locations(start, start.add(len)) };
+    }
+}
+
 /// Helper type for functions that either yield a single value to a block or
 /// return an Enumerator.
 ///
@@ -1,23 +1,26 @@
 //! Types and functions for working with Ruby classes.
 
-use std::{fmt, ops::Deref, os::raw::c_int};
+use std::{fmt, ops::Deref, os::raw::c_int, panic::AssertUnwindSafe};
 
 use crate::{
     debug_assert_value,
-    error::{protect, Error},
+    error::{protect, raise, Error},
     exception,
     module::Module,
     object::Object,
+    r_typed_data::{self, RTypedData, TypedData},
     ruby_sys::{
         self, rb_cArray, rb_cBasicObject, rb_cBinding, rb_cClass, rb_cComplex, rb_cDir,
         rb_cEncoding, rb_cEnumerator, rb_cFalseClass, rb_cFile, rb_cFloat, rb_cHash, rb_cIO,
         rb_cInteger, rb_cMatch, rb_cMethod, rb_cModule, rb_cNameErrorMesg, rb_cNilClass,
         rb_cNumeric, rb_cObject, rb_cProc, rb_cRandom, rb_cRange, rb_cRational, rb_cRegexp,
         rb_cStat, rb_cString, rb_cStruct, rb_cSymbol, rb_cThread, rb_cTime, rb_cTrueClass,
-        rb_cUnboundMethod, rb_class_new, rb_class_new_instance, ruby_value_type, VALUE,
+        rb_cUnboundMethod, rb_class_new, rb_class_new_instance, rb_class_superclass,
+        rb_define_alloc_func, rb_obj_init_copy, rb_singleton_class, rb_undef_alloc_func,
+        ruby_fl_type, ruby_value_type, VALUE,
     },
     try_convert::{ArgList, TryConvert},
-    value::{NonZeroValue, Value},
+    value::{Id, NonZeroValue, Value},
 };
 
 #[cfg(ruby_gte_3_1)]
@@ -82,6 +85,134 @@ impl RClass {
         }
     }
 
+    /// Create a new class named `name` in the scope of `parent`, inheriting
+    /// from `superclass`.
+    ///
+    /// Unlike [`Module::define_class`], this always creates a new class,
+    /// replacing any existing constant `name`. The class is created
+    /// anonymous and then assigned to the constant, after which `superclass`'
+    /// `inherited` hook is called with the named class.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{class, define_module, eval, Module, RClass};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let outer = define_module("Outer").unwrap();
+    /// let class = RClass::new_named(outer, "Inner", class::object()).unwrap();
+    /// assert_eq!(class.name().unwrap(), "Outer::Inner");
+    /// assert!(eval::<bool>("Outer::Inner.superclass == Object").unwrap());
+    /// ```
+    pub fn new_named<T, U>(parent: T, name: U, superclass: RClass) -> Result<RClass, Error>
+    where
+        T: Module,
+        U: Into<Id>,
+    {
+        let class = Self::new(superclass)?;
+        parent.const_set(name, class)?;
+        superclass.funcall::<_, _, Value>("inherited", (class,))?;
+        Ok(class)
+    }
+
+    /// Return the singleton class of `val`, creating it if it does not
+    /// already exist.
+    ///
+    /// Errors if `val` can not have a singleton class, such as an `Integer`
+    /// or `Symbol`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RClass, Value};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let obj: Value = eval("Object.new").unwrap();
+    /// let singleton = RClass::singleton_class_of(obj).unwrap();
+    /// assert!(singleton.is_singleton());
+    /// assert!(RClass::singleton_class_of(eval("1").unwrap()).is_err());
+    /// ```
+    pub fn singleton_class_of<T>(val: T) -> Result<RClass, Error>
+    where
+        T: Into<Value>,
+    {
+        let val = val.into();
+        debug_assert_value!(val);
+        unsafe {
+            protect(|| Value::new(rb_singleton_class(val.as_rb_value())))
+                .map(|v| Self::from_rb_value_unchecked(v.as_rb_value()))
+        }
+    }
+
+    /// Return the superclass of `self`, or `None` if `self` is
+    /// `BasicObject`.
+    ///
+    /// Included modules and singleton classes are skipped. Errors if `self`
+    /// is an uninitialised class.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::class;
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let superclass = class::integer().superclass().unwrap().unwrap();
+    /// assert!(superclass.equal(class::numeric()).unwrap());
+    /// assert!(class::basic_object().superclass().unwrap().is_none());
+    /// ```
+    pub fn superclass(self) -> Result<Option<RClass>, Error> {
+        unsafe {
+            protect(|| Value::new(rb_class_superclass(self.as_rb_value())))
+                .map(|v| (!v.is_nil()).then(|| Self::from_rb_value_unchecked(v.as_rb_value())))
+        }
+    }
+
+    /// Returns whether `self` is a singleton class.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{class, eval, RClass};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// assert!(!class::string().is_singleton());
+    /// assert!(eval::<RClass>("String.singleton_class").unwrap().is_singleton());
+    /// ```
+    pub fn is_singleton(self) -> bool {
+        unsafe {
+            self.r_basic_unchecked().as_ref().flags & ruby_fl_type::RUBY_FL_SINGLETON as VALUE != 0
+        }
+    }
+
+    /// Return the object `self` is the singleton class of.
+    ///
+    /// Errors if `self` is not a singleton class.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{class, eval, RClass};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let singleton = eval::<RClass>("String.singleton_class").unwrap();
+    /// let attached: RClass = singleton.attached_object().unwrap().try_convert().unwrap();
+    /// assert!(attached.equal(class::string()).unwrap());
+    /// assert!(class::string().attached_object().is_err());
+    /// ```
+    pub fn attached_object(self) -> Result<Value, Error> {
+        if !self.is_singleton() {
+            return Err(Error::new(
+                exception::type_error(),
+                format!("`{}' is not a singleton class", self.inspect()),
+            ));
+        }
+        if cfg!(ruby_gte_3_2) {
+            self.funcall("attached_object", ())
+        } else {
+            self.ivar_get("__attached__")
+        }
+    }
+
     /// Create a new object, an instance of `self`, passing the arguments
     /// `args` to the initialiser.
     ///
@@ -111,6 +242,155 @@ impl RClass {
             })
         }
     }
+
+    /// Define an allocator function for `self`, allocating `T::default()`
+    /// wrapped in a new Ruby object.
+    ///
+    /// This allows the class `T` is wrapped as to be instantiated with
+    /// `Class#new` (and `Class#allocate`), along with any subclasses. Combined
+    /// with an `initialize` method the Rust value can be allocated by Ruby
+    /// and then filled in from the arguments to `new`.
+    ///
+    /// As `initialize` only gets a shared reference to the Rust value,
+    /// setting it's fields requires interior mutability, e.g. with
+    /// [`Cell`](std::cell::Cell) or [`RefCell`](std::cell::RefCell).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cell::Cell;
+    ///
+    /// use magnus::{define_class, eval, method, prelude::*};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// #[derive(Default)]
+    /// #[magnus::wrap(class = "Point")]
+    /// struct Point {
+    ///     x: Cell<isize>,
+    ///     y: Cell<isize>,
+    /// }
+    ///
+    /// impl Point {
+    ///     fn initialize(&self, x: isize, y: isize) {
+    ///         self.x.set(x);
+    ///         self.y.set(y);
+    ///     }
+    ///
+    ///     fn x(&self) -> isize {
+    ///         self.x.get()
+    ///     }
+    /// }
+    ///
+    /// let class = define_class("Point", Default::default()).unwrap();
+    /// class.define_alloc_func::<Point>();
+    /// class.define_method("initialize", method!(Point::initialize, 2));
+    /// class.define_method("x", method!(Point::x, 0));
+    ///
+    /// assert_eq!(eval::<isize>("Point.new(1, 2).x").unwrap(), 1);
+    /// assert_eq!(eval::<isize>("Class.new(Point).new(3, 4).x").unwrap(), 3);
+    /// assert_eq!(eval::<isize>("Point.allocate.x").unwrap(), 0);
+    /// ```
+    pub fn define_alloc_func<T>(self)
+    where
+        T: Default + TypedData,
+    {
+        unsafe extern "C" fn allocate<T: Default + TypedData>(class: VALUE) -> VALUE {
+            let data = match std::panic::catch_unwind(T::default) {
+                Ok(v) => v,
+                Err(e) => raise(Error::from_panic(e)),
+            };
+            r_typed_data::wrap(class, data, T::data_type())
+        }
+
+        unsafe { rb_define_alloc_func(self.as_rb_value(), Some(allocate::<T>)) }
+    }
+
+    /// Remove the allocator function of `self`, preventing instances of the
+    /// class being created with `Class#new` or `Class#allocate`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{class, eval, RClass};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let class = RClass::new(class::object()).unwrap();
+    /// class.undef_alloc_func();
+    /// assert!(class.new_instance(()).is_err());
+    /// ```
+    pub fn undef_alloc_func(self) {
+        unsafe { rb_undef_alloc_func(self.as_rb_value()) }
+    }
+
+    /// Define an `initialize_copy` method for `self`, implemented by cloning
+    /// the wrapped `T`.
+    ///
+    /// This allows `dup` and `clone` to be used with instances of the class
+    /// `T` is wrapped as. An allocator function must also be defined for the
+    /// class, see [`RClass::define_alloc_func`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{define_class, eval, function, method, prelude::*};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// #[derive(Clone, Default)]
+    /// #[magnus::wrap(class = "Name")]
+    /// struct Name(String);
+    ///
+    /// impl Name {
+    ///     fn new(s: String) -> Self {
+    ///         Self(s)
+    ///     }
+    ///
+    ///     fn to_s(&self) -> String {
+    ///         self.0.clone()
+    ///     }
+    /// }
+    ///
+    /// let class = define_class("Name", Default::default()).unwrap();
+    /// class.define_alloc_func::<Name>();
+    /// class.define_initialize_copy::<Name>();
+    /// class.define_singleton_method("new", function!(Name::new, 1));
+    /// class.define_method("to_s", method!(Name::to_s, 0));
+    ///
+    /// assert_eq!(eval::<String>(r#"Name.new("Alice").dup.to_s"#).unwrap(), "Alice");
+    /// assert_eq!(eval::<String>(r#"Name.new("Bob").clone.to_s"#).unwrap(), "Bob");
+    /// ```
+    pub fn define_initialize_copy<T>(self)
+    where
+        T: Clone + TypedData,
+    {
+        unsafe extern "C" fn initialize_copy<T: Clone + TypedData>(
+            rb_self: Value,
+            orig: Value,
+        ) -> Value {
+            let res = std::panic::catch_unwind(AssertUnwindSafe(|| {
+                protect(|| {
+                    Value::new(rb_obj_init_copy(rb_self.as_rb_value(), orig.as_rb_value()))
+                })?;
+                if rb_self.as_rb_value() == orig.as_rb_value() {
+                    return Ok(rb_self);
+                }
+                let orig = RTypedData::try_convert(&orig)?;
+                let dest = RTypedData::try_convert(&rb_self)?;
+                let data = T::clone(&*orig.get::<T>()?);
+                *dest.get_mut::<T>()? = data;
+                Ok(rb_self)
+            }));
+            match res {
+                Ok(Ok(v)) => v,
+                Ok(Err(e)) => raise(e),
+                Err(e) => raise(Error::from_panic(e)),
+            }
+        }
+
+        self.define_private_method(
+            "initialize_copy",
+            initialize_copy::<T> as unsafe extern "C" fn(Value, Value) -> Value,
+        );
+    }
 }
 
 impl Default for RClass {
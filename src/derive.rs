@@ -0,0 +1,279 @@
+//! Helpers for the code generated by the `TryConvert`, `IntoValue`, and
+//! `KwArgs` derive macros.
+//!
+//! This module is not part of the public API, and may change at any time.
+
+use std::{
+    cell::{Cell, RefCell},
+    os::raw::c_int,
+};
+
+use crate::{
+    error::{protect, Error},
+    exception::{self, ExceptionClass},
+    r_array::RArray,
+    r_hash::RHash,
+    r_string::RString,
+    ruby_sys::{rb_get_kwargs, rb_hash_dup, ID, VALUE},
+    symbol::Symbol,
+    try_convert::TryConvert,
+    value::{Id, Value, QNIL},
+};
+
+thread_local! {
+    // The path to the value currently being converted, e.g.
+    // `["config", "[:database]", "[:port]"]`.
+    static PATH: RefCell<Vec<String>> = Default::default();
+    // Set once an error has been prefixed with the path, so outer conversions
+    // don't prefix it again.
+    static QUALIFIED: Cell<bool> = Default::default();
+}
+
+// Removes a segment pushed to `PATH` when dropped.
+struct Segment(usize);
+
+impl Segment {
+    fn push(segment: String) -> Self {
+        PATH.with(|path| {
+            let mut path = path.borrow_mut();
+            let len = path.len();
+            path.push(segment);
+            Self(len)
+        })
+    }
+}
+
+impl Drop for Segment {
+    fn drop(&mut self) {
+        PATH.with(|path| path.borrow_mut().truncate(self.0));
+    }
+}
+
+fn path() -> String {
+    PATH.with(|path| path.borrow().concat())
+}
+
+fn qualified<T>(class: ExceptionClass, msg: T) -> Error
+where
+    T: std::fmt::Display,
+{
+    QUALIFIED.with(|q| q.set(true));
+    Error::new(class, format!("{}: {}", path(), msg))
+}
+
+// Prefix the error message with the current path, unless that has already
+// been done by a nested conversion.
+fn qualify(e: Error) -> Error {
+    if QUALIFIED.with(|q| q.get()) {
+        return e;
+    }
+    match e {
+        Error::Jump(_) => e,
+        Error::Error(class, msg) => qualified(class, msg),
+        Error::Exception(ex) => {
+            let class =
+                ExceptionClass::from_value(*ex.class()).unwrap_or_else(exception::type_error);
+            match ex.funcall::<_, _, String>("message", ()) {
+                Ok(msg) => qualified(class, msg),
+                Err(e) => e,
+            }
+        }
+    }
+}
+
+/// What to do when a field is missing.
+pub enum Missing<T> {
+    /// Return an error.
+    Required,
+    /// Convert `nil`.
+    Optional,
+    /// Call the function.
+    Default(fn() -> T),
+}
+
+/// Run a conversion for a type named `name`, which will be the start of the
+/// path in any error message if this is the outermost conversion.
+pub fn root<T, F>(name: &str, func: F) -> Result<T, Error>
+where
+    F: FnOnce() -> Result<T, Error>,
+{
+    let _segment = PATH.with(|path| path.borrow().is_empty()).then(|| {
+        QUALIFIED.with(|q| q.set(false));
+        Segment::push(name.to_owned())
+    });
+    func()
+}
+
+/// Convert `val` to a `RHash`.
+pub fn hash(val: &Value) -> Result<RHash, Error> {
+    RHash::try_convert(val).map_err(qualify)
+}
+
+/// Convert `val` to a `RArray` of length `len`.
+pub fn array(val: &Value, len: usize) -> Result<RArray, Error> {
+    let ary = RArray::try_convert(val).map_err(qualify)?;
+    if ary.len() != len {
+        return Err(qualified(
+            exception::type_error(),
+            format!("expected Array of length {}, got {}", len, ary.len()),
+        ));
+    }
+    Ok(ary)
+}
+
+/// Get `key` from `hash`, as either a Symbol or String, and convert it to
+/// `T`.
+pub fn field<T>(hash: RHash, key: &str, missing: Missing<T>) -> Result<T, Error>
+where
+    T: TryConvert,
+{
+    let _segment = Segment::push(format!("[:{}]", key));
+    let val = hash
+        .get(Symbol::new(key))
+        .or_else(|| hash.get(RString::new(key)));
+    match (val, missing) {
+        (Some(val), _) => val.try_convert().map_err(qualify),
+        (None, Missing::Optional) => QNIL.try_convert().map_err(qualify),
+        (None, Missing::Default(func)) => Ok(func()),
+        (None, Missing::Required) => Err(qualified(exception::key_error(), "missing key")),
+    }
+}
+
+/// Get the element at `index` from `ary` and convert it to `T`.
+pub fn element<T>(ary: RArray, index: usize) -> Result<T, Error>
+where
+    T: TryConvert,
+{
+    let _segment = Segment::push(format!("[{}]", index));
+    ary.entry::<Value>(index as isize)?
+        .try_convert()
+        .map_err(qualify)
+}
+
+/// Get the name of an enum variant from `val`, either a Symbol/String, or a
+/// Hash with the name at the key `tag`. The Hash is also returned, if there
+/// was one.
+pub fn variant(val: &Value, tag: &str) -> Result<(String, Option<RHash>), Error> {
+    if let Some(hash) = RHash::from_value(*val) {
+        let name = field::<Value>(hash, tag, Missing::Required)?;
+        let _segment = Segment::push(format!("[:{}]", tag));
+        return Ok((variant_name(name)?, Some(hash)));
+    }
+    Ok((variant_name(*val)?, None))
+}
+
+fn variant_name(val: Value) -> Result<String, Error> {
+    if let Some(sym) = Symbol::from_value(val) {
+        return sym.name().map(|n| n.into_owned()).map_err(qualify);
+    }
+    if let Some(s) = RString::from_value(val) {
+        return s.to_string().map_err(qualify);
+    }
+    Err(qualified(
+        exception::type_error(),
+        format!("no implicit conversion of {} into Symbol", unsafe {
+            val.classname()
+        }),
+    ))
+}
+
+/// Create an error for an unknown enum variant.
+pub fn unknown_variant(name: &str, expected: &[&str]) -> Error {
+    let expected = expected
+        .iter()
+        .map(|v| format!(":{}", v))
+        .collect::<Vec<_>>()
+        .join(", ");
+    qualified(
+        exception::arg_error(),
+        format!("unknown variant :{}, expected one of {}", name, expected),
+    )
+}
+
+/// Set `key` in `hash` to `val`, as either a Symbol or String. If `optional`
+/// is set `key` is omitted when `val` is `nil`.
+pub fn set_field<T>(hash: RHash, key: &str, val: T, string_key: bool, optional: bool)
+where
+    T: Into<Value>,
+{
+    let val = val.into();
+    if optional && val.is_nil() {
+        return;
+    }
+    // hash was just created by the caller, so can't be frozen
+    let _ = if string_key {
+        hash.aset(RString::new(key), val)
+    } else {
+        hash.aset(Symbol::new(key), val)
+    };
+}
+
+/// Create a Hash with the enum variant `name` at the key `tag`.
+pub fn tagged_hash(tag: &str, name: &str, string_key: bool) -> RHash {
+    let hash = RHash::new();
+    set_field(hash, tag, Symbol::new(name), string_key, false);
+    hash
+}
+
+/// Keyword arguments extracted with [`kwargs`].
+pub struct Keywords {
+    values: Vec<Value>,
+    rest: RHash,
+}
+
+impl Keywords {
+    /// The keywords that were not extracted.
+    pub fn rest(&self) -> RHash {
+        self.rest
+    }
+}
+
+/// Extract the `required` and `optional` keywords from `kw`, without
+/// modifying `kw`.
+///
+/// Missing required keywords are an error, as are any other keywords unless
+/// `rest` is set.
+pub fn kwargs(
+    kw: RHash,
+    required: &[&str],
+    optional: &[&str],
+    rest: bool,
+) -> Result<Keywords, Error> {
+    let ids = required
+        .iter()
+        .chain(optional.iter())
+        .map(|&k| Id::from(k))
+        .collect::<Vec<_>>();
+    let optional_len = if rest {
+        -(optional.len() as c_int + 1)
+    } else {
+        optional.len() as c_int
+    };
+    let mut values = vec![*QNIL; ids.len()];
+    let rest = unsafe {
+        let rest = RHash::from_rb_value_unchecked(rb_hash_dup(kw.as_rb_value()));
+        protect(|| {
+            rb_get_kwargs(
+                rest.as_rb_value(),
+                ids.as_ptr() as *const ID,
+                required.len() as c_int,
+                optional_len,
+                values.as_mut_ptr() as *mut VALUE,
+            );
+            *QNIL
+        })?;
+        rest
+    };
+    Ok(Keywords { values, rest })
+}
+
+/// Convert the keyword at `index` (counting required then optional
+/// keywords) to `T`. Missing optional keywords are converted from `nil`.
+pub fn kwarg<T>(kw: &Keywords, index: usize, key: &str) -> Result<T, Error>
+where
+    T: TryConvert,
+{
+    let val = kw.values[index];
+    let val = if val.is_undef() { *QNIL } else { val };
+    root(key, || val.try_convert().map_err(qualify))
+}
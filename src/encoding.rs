@@ -0,0 +1,345 @@
+//! Types and functions for working with encodings.
+//!
+//! Ruby has three representations of an encoding, all of which can be
+//! converted between with [`From`]/[`Into`]:
+//!
+//! * [`Encoding`], an instance of Ruby's `Encoding` class.
+//! * [`RbEncoding`], a pointer to Ruby's internal encoding struct.
+//! * [`Index`], the index of the encoding in Ruby's table of encodings.
+//!
+//! Each can also be converted from a Ruby `Encoding` or encoding name with
+//! [`TryConvert`].
+
+use std::{
+    ffi::{CStr, CString},
+    fmt,
+    ops::Deref,
+    os::raw::c_int,
+    ptr::NonNull,
+};
+
+use crate::{
+    class,
+    error::{protect, Error},
+    ruby_sys::{
+        rb_ascii8bit_encindex, rb_ascii8bit_encoding, rb_default_external_encoding,
+        rb_default_internal_encoding, rb_enc_find_index, rb_enc_from_encoding, rb_enc_from_index,
+        rb_enc_to_index, rb_encoding, rb_to_encoding, rb_usascii_encindex, rb_usascii_encoding,
+        rb_utf8_encindex, rb_utf8_encoding, VALUE,
+    },
+    try_convert::TryConvert,
+    value::{NonZeroValue, Value},
+};
+
+/// Wrapper type for a Value known to be an instance of Ruby's Encoding class.
+///
+/// All [`Value`] methods should be available on this type through [`Deref`],
+/// but some may be missed by this documentation.
+#[derive(Clone, Copy)]
+#[repr(transparent)]
+pub struct Encoding(NonZeroValue);
+
+impl Encoding {
+    /// Return `Some(Encoding)` if `val` is an `Encoding`, `None` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{encoding::Encoding, eval};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// assert!(Encoding::from_value(eval("Encoding::US_ASCII").unwrap()).is_some());
+    /// assert!(Encoding::from_value(eval("nil").unwrap()).is_none());
+    /// ```
+    #[inline]
+    pub fn from_value(val: Value) -> Option<Self> {
+        unsafe {
+            val.is_kind_of(class::encoding())
+                .then(|| Self(NonZeroValue::new_unchecked(val)))
+        }
+    }
+
+    #[inline]
+    pub(crate) unsafe fn from_rb_value_unchecked(val: VALUE) -> Self {
+        Self(NonZeroValue::new_unchecked(Value::new(val)))
+    }
+
+    /// Returns the default external encoding.
+    ///
+    /// This is the encoding used for data read from the outside world, such
+    /// as files and sockets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::encoding::Encoding;
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let enc = Encoding::default_external();
+    /// assert!(enc.is_kind_of(magnus::class::encoding()));
+    /// ```
+    pub fn default_external() -> Self {
+        RbEncoding::default_external().into()
+    }
+
+    /// Returns the default internal encoding, if one has been set.
+    ///
+    /// When set, strings read from the outside world are transcoded from the
+    /// default external encoding to this encoding.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::encoding::Encoding;
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// assert!(Encoding::default_internal().is_none());
+    /// ```
+    pub fn default_internal() -> Option<Self> {
+        RbEncoding::default_internal().map(Into::into)
+    }
+}
+
+impl Deref for Encoding {
+    type Target = Value;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.get_ref()
+    }
+}
+
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", unsafe { self.to_s_infallible() })
+    }
+}
+
+impl fmt::Debug for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.inspect())
+    }
+}
+
+impl From<Encoding> for Value {
+    fn from(val: Encoding) -> Self {
+        *val
+    }
+}
+
+impl From<RbEncoding> for Encoding {
+    fn from(val: RbEncoding) -> Self {
+        unsafe { Encoding::from_rb_value_unchecked(rb_enc_from_encoding(val.as_ptr())) }
+    }
+}
+
+impl From<Index> for Encoding {
+    fn from(val: Index) -> Self {
+        RbEncoding::from(val).into()
+    }
+}
+
+impl TryConvert for Encoding {
+    fn try_convert(val: &Value) -> Result<Self, Error> {
+        if let Some(enc) = Self::from_value(*val) {
+            return Ok(enc);
+        }
+        unsafe {
+            protect(|| Value::new(rb_enc_from_encoding(rb_to_encoding(val.as_rb_value()))))
+                .map(|v| Self::from_rb_value_unchecked(v.as_rb_value()))
+        }
+    }
+}
+
+/// Ruby's internal representation of an encoding.
+///
+/// Encodings are never freed, so unlike most Ruby types this can be freely
+/// stored on the heap.
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct RbEncoding(NonNull<rb_encoding>);
+
+impl RbEncoding {
+    fn new(inner: *mut rb_encoding) -> Option<Self> {
+        NonNull::new(inner).map(Self)
+    }
+
+    pub(crate) fn as_ptr(&self) -> *mut rb_encoding {
+        self.0.as_ptr()
+    }
+
+    /// Returns the encoding that represents ASCII-8BIT a.k.a. binary.
+    pub fn ascii8bit() -> Self {
+        Self::new(unsafe { rb_ascii8bit_encoding() }).unwrap()
+    }
+
+    /// Returns the encoding that represents UTF-8.
+    pub fn utf8() -> Self {
+        Self::new(unsafe { rb_utf8_encoding() }).unwrap()
+    }
+
+    /// Returns the encoding that represents US-ASCII.
+    pub fn usascii() -> Self {
+        Self::new(unsafe { rb_usascii_encoding() }).unwrap()
+    }
+
+    /// Returns the default external encoding.
+    ///
+    /// See [`Encoding::default_external`].
+    pub fn default_external() -> Self {
+        Self::new(unsafe { rb_default_external_encoding() }).unwrap()
+    }
+
+    /// Returns the default internal encoding, if one has been set.
+    ///
+    /// See [`Encoding::default_internal`].
+    pub fn default_internal() -> Option<Self> {
+        Self::new(unsafe { rb_default_internal_encoding() })
+    }
+
+    /// Returns the encoding with the name or alias `name`, or `None` if no
+    /// such encoding exists.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::encoding::RbEncoding;
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// assert_eq!(RbEncoding::find("SJIS").unwrap().name(), "Shift_JIS");
+    /// assert!(RbEncoding::find("none").is_none());
+    /// ```
+    pub fn find(name: &str) -> Option<Self> {
+        Index::find(name).map(Into::into)
+    }
+
+    /// Returns the canonical name of the encoding.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::encoding::RbEncoding;
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// assert_eq!(RbEncoding::utf8().name(), "UTF-8");
+    /// ```
+    pub fn name(&self) -> &str {
+        // encoding names are always ASCII
+        unsafe { CStr::from_ptr(self.0.as_ref().name).to_str().unwrap() }
+    }
+
+    /// Returns the minimum number of bytes a character may take in this
+    /// encoding.
+    pub fn mbminlen(&self) -> usize {
+        unsafe { self.0.as_ref().min_enc_len as usize }
+    }
+
+    /// Returns the maximum number of bytes a character may take in this
+    /// encoding.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::encoding::RbEncoding;
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// assert_eq!(RbEncoding::ascii8bit().mbmaxlen(), 1);
+    /// assert_eq!(RbEncoding::utf8().mbmaxlen(), 4);
+    /// ```
+    pub fn mbmaxlen(&self) -> usize {
+        unsafe { self.0.as_ref().max_enc_len as usize }
+    }
+}
+
+impl fmt::Debug for RbEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("RbEncoding").field(&self.name()).finish()
+    }
+}
+
+impl From<Encoding> for RbEncoding {
+    fn from(val: Encoding) -> Self {
+        Self::new(unsafe { rb_to_encoding(val.as_rb_value()) }).unwrap()
+    }
+}
+
+impl From<Index> for RbEncoding {
+    fn from(val: Index) -> Self {
+        Self::new(unsafe { rb_enc_from_index(val.to_int()) }).unwrap()
+    }
+}
+
+impl TryConvert for RbEncoding {
+    fn try_convert(val: &Value) -> Result<Self, Error> {
+        Encoding::try_convert(val).map(Into::into)
+    }
+}
+
+/// The index of an encoding in Ruby's table of encodings.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct Index(c_int);
+
+impl Index {
+    pub(crate) fn from_int(i: c_int) -> Self {
+        Self(i)
+    }
+
+    pub(crate) fn to_int(self) -> c_int {
+        self.0
+    }
+
+    /// Returns the index for ASCII-8BIT a.k.a. binary.
+    pub fn ascii8bit() -> Self {
+        Self(unsafe { rb_ascii8bit_encindex() })
+    }
+
+    /// Returns the index for UTF-8.
+    pub fn utf8() -> Self {
+        Self(unsafe { rb_utf8_encindex() })
+    }
+
+    /// Returns the index for US-ASCII.
+    pub fn usascii() -> Self {
+        Self(unsafe { rb_usascii_encindex() })
+    }
+
+    /// Returns the index of the encoding with the name or alias `name`, or
+    /// `None` if no such encoding exists.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::encoding::Index;
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// assert_eq!(Index::find("BINARY"), Some(Index::ascii8bit()));
+    /// assert!(Index::find("none").is_none());
+    /// ```
+    pub fn find(name: &str) -> Option<Self> {
+        let name = CString::new(name).ok()?;
+        let i = unsafe { rb_enc_find_index(name.as_ptr()) };
+        if i >= 0 {
+            Some(Self(i))
+        } else {
+            None
+        }
+    }
+}
+
+impl From<Encoding> for Index {
+    fn from(val: Encoding) -> Self {
+        RbEncoding::from(val).into()
+    }
+}
+
+impl From<RbEncoding> for Index {
+    fn from(val: RbEncoding) -> Self {
+        Self(unsafe { rb_enc_to_index(val.as_ptr()) })
+    }
+}
+
+impl TryConvert for Index {
+    fn try_convert(val: &Value) -> Result<Self, Error> {
+        Encoding::try_convert(val).map(Into::into)
+    }
+}
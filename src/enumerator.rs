@@ -1,15 +1,32 @@
-use std::{fmt, ops::Deref};
+use std::{
+    cell::RefCell,
+    fmt,
+    iter::Peekable,
+    ops::Deref,
+    os::raw::c_int,
+    panic::{catch_unwind, AssertUnwindSafe},
+    ptr,
+    sync::Once,
+};
 
 use crate::{
-    class,
-    error::Error,
+    block::Captured,
+    class::{self, RClass},
+    error::{raise, Error},
     exception,
+    method::MethodN,
+    module::Module,
     object::Object,
-    ruby_sys::VALUE,
+    r_array::RArray,
+    ruby_sys::{rb_block_call, rb_funcallv, VALUE},
     try_convert::TryConvert,
-    value::{NonZeroValue, Value},
+    value::{Id, NonZeroValue, Value, QNIL},
 };
 
+// The Rust iterator backing an Enumerator created with
+// `Enumerator::from_iterator`.
+type IterState = RefCell<Peekable<Box<dyn Iterator<Item = Value>>>>;
+
 /// Wrapper type for a Value known to be an instance of Ruby's Enumerator class.
 ///
 /// All [`Value`] methods should be available on this type through [`Deref`],
@@ -32,6 +49,219 @@ impl Enumerator {
     pub(crate) unsafe fn from_rb_value_unchecked(val: VALUE) -> Self {
         Self(NonZeroValue::new_unchecked(Value::new(val)))
     }
+
+    /// Create a new `Enumerator` from the Rust iterator `iter`.
+    ///
+    /// Unlike an Enumerator returned from a method with
+    /// [`Yield`](crate::block::Yield), or created with
+    /// [`Value::enumeratorize`], `next`, `peek`, `next_values`, and
+    /// `peek_values` take items directly from `iter` rather than running
+    /// `each` in a Ruby Fiber. `size` returns the number of items remaining,
+    /// if known exactly from `iter`'s [`size_hint`](Iterator::size_hint).
+    ///
+    /// As a Rust iterator can't be restarted all methods share `iter`'s
+    /// position, so `each` yields only the items remaining, and `rewind`
+    /// raises a `TypeError`.
+    ///
+    /// `iter` is dropped when the Enumerator is garbage collected. Ruby
+    /// values held directly by `iter` are kept alive, but values held within
+    /// a heap allocation (such as in a `Vec`) are not tracked by the garbage
+    /// collector, and must be kept alive by some other means.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, Enumerator};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let enumerator = Enumerator::from_iterator((1..=4).map(|i| i * i));
+    /// let res: bool = eval!(
+    ///     r#"
+    ///         enumerator.size == 4 &&
+    ///         enumerator.next == 1 &&
+    ///         enumerator.peek == 4 &&
+    ///         enumerator.size == 3 &&
+    ///         enumerator.to_a == [4, 9, 16]
+    ///     "#,
+    ///     enumerator
+    /// )
+    /// .unwrap();
+    /// assert!(res);
+    /// ```
+    pub fn from_iterator<I, T>(iter: I) -> Self
+    where
+        I: Iterator<Item = T> + 'static,
+        T: Into<Value> + 'static,
+    {
+        let iter: Box<dyn Iterator<Item = Value>> = Box::new(iter.map(Into::into));
+        let state: IterState = RefCell::new(iter.peekable());
+        let state = Captured::wrap(state);
+        unsafe {
+            let fptr = Self::iter_each
+                as unsafe extern "C" fn(VALUE, VALUE, c_int, *const VALUE, VALUE) -> VALUE;
+            #[cfg(ruby_lt_2_7)]
+            let fptr: unsafe extern "C" fn() -> VALUE = std::mem::transmute(fptr);
+            let enumerator = Self::from_rb_value_unchecked(rb_block_call(
+                Self::iterator_class().as_rb_value(),
+                Id::from("new").as_rb_id(),
+                0,
+                ptr::null(),
+                Some(fptr),
+                state.as_rb_value(),
+            ));
+            // the enumerator has just been created, so can't be frozen
+            let _ = enumerator.ivar_set("__magnus_iter", state);
+            enumerator
+        }
+    }
+
+    // Hidden subclass of Enumerator for `from_iterator`, overriding the
+    // methods that would otherwise run `each` in a Fiber.
+    fn iterator_class() -> RClass {
+        static INIT: Once = Once::new();
+        static mut CLASS: VALUE = 0;
+        unsafe {
+            INIT.call_once(|| {
+                let class = RClass::new(class::enumerator()).unwrap();
+                class.define_method(
+                    "next",
+                    Self::iter_next as unsafe extern "C" fn(Value) -> Value,
+                );
+                class.define_method(
+                    "peek",
+                    Self::iter_peek as unsafe extern "C" fn(Value) -> Value,
+                );
+                class.define_method(
+                    "next_values",
+                    Self::iter_next_values as unsafe extern "C" fn(Value) -> Value,
+                );
+                class.define_method(
+                    "peek_values",
+                    Self::iter_peek_values as unsafe extern "C" fn(Value) -> Value,
+                );
+                class.define_method(
+                    "size",
+                    Self::iter_size as unsafe extern "C" fn(Value) -> Value,
+                );
+                class.define_method(
+                    "rewind",
+                    Self::iter_rewind as unsafe extern "C" fn(Value) -> Value,
+                );
+                class.leak();
+                CLASS = class.as_rb_value();
+            });
+            RClass::from_rb_value_unchecked(CLASS)
+        }
+    }
+
+    fn with_iter_state<F, R>(state: Value, func: F) -> Result<R, Error>
+    where
+        F: FnOnce(&mut Peekable<Box<dyn Iterator<Item = Value>>>) -> R,
+    {
+        let cell = unsafe { &*Captured::get::<IterState>(state) };
+        let mut iter = cell
+            .try_borrow_mut()
+            .map_err(|_| Error::new(exception::runtime_error(), "iterator is already in use"))?;
+        Ok(func(&mut iter))
+    }
+
+    fn with_iter<F, R>(self, func: F) -> Result<R, Error>
+    where
+        F: FnOnce(&mut Peekable<Box<dyn Iterator<Item = Value>>>) -> R,
+    {
+        Self::with_iter_state(self.ivar_get("__magnus_iter")?, func)
+    }
+
+    fn stop_iteration() -> Error {
+        Error::new(exception::stop_iteration(), "iteration reached an end")
+    }
+
+    // Enumerator.new block for `from_iterator`.
+    //
+    // `yielder << val` can switch fibers (when Ruby's external enumeration,
+    // e.g. `zip`, runs `each` in a Fiber), and that fiber may never be
+    // resumed, in which case its stack is discarded without being unwound.
+    // So, as with `Fiber::yield_values`, there must be nothing to drop when
+    // calling it. `protect` itself is fine across a fiber switch, but isn't
+    // needed here, as with nothing to clean up any exception can simply
+    // propagate to the caller.
+    unsafe extern "C" fn iter_each(
+        yielder: VALUE,
+        state: VALUE,
+        _argc: c_int,
+        _argv: *const VALUE,
+        _blockarg: VALUE,
+    ) -> VALUE {
+        let state = Value::new(state);
+        let push = Id::from("<<").as_rb_id();
+        loop {
+            let res = catch_unwind(AssertUnwindSafe(|| {
+                Self::with_iter_state(state, |iter| iter.next())
+            }));
+            let val = match res {
+                Ok(Ok(Some(v))) => v.as_rb_value(),
+                Ok(Ok(None)) => return QNIL.as_rb_value(),
+                Ok(Err(e)) => raise(e),
+                Err(e) => raise(Error::from_panic(e)),
+            };
+            rb_funcallv(yielder, push, 1, &val);
+        }
+    }
+
+    unsafe extern "C" fn iter_next(rb_self: Value) -> Value {
+        MethodN::new(|rb_self: Self| {
+            rb_self
+                .with_iter(|iter| iter.next())?
+                .ok_or_else(Self::stop_iteration)
+        })
+        .call_handle_error([rb_self])
+    }
+
+    unsafe extern "C" fn iter_peek(rb_self: Value) -> Value {
+        MethodN::new(|rb_self: Self| {
+            rb_self
+                .with_iter(|iter| iter.peek().copied())?
+                .ok_or_else(Self::stop_iteration)
+        })
+        .call_handle_error([rb_self])
+    }
+
+    unsafe extern "C" fn iter_next_values(rb_self: Value) -> Value {
+        MethodN::new(|rb_self: Self| {
+            rb_self
+                .with_iter(|iter| iter.next())?
+                .map(|v| RArray::from_slice(&[v]))
+                .ok_or_else(Self::stop_iteration)
+        })
+        .call_handle_error([rb_self])
+    }
+
+    unsafe extern "C" fn iter_peek_values(rb_self: Value) -> Value {
+        MethodN::new(|rb_self: Self| {
+            rb_self
+                .with_iter(|iter| iter.peek().copied())?
+                .map(|v| RArray::from_slice(&[v]))
+                .ok_or_else(Self::stop_iteration)
+        })
+        .call_handle_error([rb_self])
+    }
+
+    unsafe extern "C" fn iter_size(rb_self: Value) -> Value {
+        MethodN::new(|rb_self: Self| {
+            rb_self.with_iter(|iter| match iter.size_hint() {
+                (lower, Some(upper)) if lower == upper => Some(lower),
+                _ => None,
+            })
+        })
+        .call_handle_error([rb_self])
+    }
+
+    unsafe extern "C" fn iter_rewind(rb_self: Value) -> Value {
+        MethodN::new(|_: Self| -> Result<Value, Error> {
+            Err(Error::new(exception::type_error(), "can't rewind"))
+        })
+        .call_handle_error([rb_self])
+    }
 }
 
 impl Iterator for Enumerator {
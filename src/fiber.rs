@@ -0,0 +1,276 @@
+use std::{
+    fmt,
+    ops::Deref,
+    os::raw::c_int,
+    panic::{catch_unwind, AssertUnwindSafe},
+    slice,
+};
+
+use crate::{
+    block::Captured,
+    error::{protect, raise, Error},
+    exception,
+    method::ReturnValue,
+    object::Object,
+    r_array::RArray,
+    r_typed_data::BorrowScope,
+    ruby_sys::{
+        rb_fiber_alive_p, rb_fiber_current, rb_fiber_new, rb_fiber_resume, rb_fiber_yield, VALUE,
+    },
+    try_convert::{ArgList, TryConvert},
+    value::{NonZeroValue, Value},
+};
+
+#[cfg(ruby_gte_3_1)]
+use crate::ruby_sys::rb_obj_is_fiber;
+
+#[cfg(ruby_lt_3_1)]
+use crate::{class, module::Module, RClass};
+
+/// Wrapper type for a Value known to be an instance of Ruby's Fiber class.
+///
+/// All [`Value`] methods should be available on this type through [`Deref`],
+/// but some may be missed by this documentation.
+#[derive(Clone, Copy)]
+#[repr(transparent)]
+pub struct Fiber(NonZeroValue);
+
+impl Fiber {
+    /// Return `Some(Fiber)` if `val` is a `Fiber`, `None` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, Fiber};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// assert!(Fiber::from_value(eval("Fiber.new {}").unwrap()).is_some());
+    /// assert!(Fiber::from_value(eval("nil").unwrap()).is_none());
+    /// ```
+    #[inline]
+    pub fn from_value(val: Value) -> Option<Self> {
+        #[cfg(ruby_gte_3_1)]
+        let is_fiber = unsafe { Value::new(rb_obj_is_fiber(val.as_rb_value())).to_bool() };
+        #[cfg(ruby_lt_3_1)]
+        let is_fiber = val.is_kind_of(class::object().const_get::<_, RClass>("Fiber").unwrap());
+        unsafe { is_fiber.then(|| Self(NonZeroValue::new_unchecked(val))) }
+    }
+
+    #[inline]
+    pub(crate) unsafe fn from_rb_value_unchecked(val: VALUE) -> Self {
+        Self(NonZeroValue::new_unchecked(Value::new(val)))
+    }
+
+    /// Create a new fiber that will run `func` when first resumed.
+    ///
+    /// `func` receives the arguments passed to the first call to
+    /// [`resume`](Fiber::resume), and its return value is returned from the
+    /// final call to `resume`. If `func` returns an `Err` or panics the error
+    /// is raised from `resume`.
+    ///
+    /// `func` is kept alive until it is called, along with any Ruby values
+    /// it captures directly. Values captured within a heap allocation (such
+    /// as in a `Vec`) are not tracked by the garbage collector, and must be
+    /// kept alive by some other means.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::Fiber;
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let fib = Fiber::new(|args| {
+    ///     let a: i64 = args[0].try_convert()?;
+    ///     let b: i64 = Fiber::yield_values((a + 1,))?;
+    ///     Ok::<_, magnus::Error>(a + b)
+    /// })
+    /// .unwrap();
+    /// assert_eq!(fib.resume::<_, i64>((1,)).unwrap(), 2);
+    /// assert_eq!(fib.resume::<_, i64>((10,)).unwrap(), 11);
+    /// assert!(!fib.is_alive());
+    /// ```
+    pub fn new<F, R>(func: F) -> Result<Self, Error>
+    where
+        F: FnOnce(&[Value]) -> R + 'static,
+        R: ReturnValue,
+    {
+        unsafe extern "C" fn call<F, R>(
+            _yielded_arg: VALUE,
+            callback_arg: VALUE,
+            argc: c_int,
+            argv: *const VALUE,
+            _blockarg: VALUE,
+        ) -> VALUE
+        where
+            F: FnOnce(&[Value]) -> R + 'static,
+            R: ReturnValue,
+        {
+            let func = (*Captured::get::<Option<F>>(Value::new(callback_arg)))
+                .take()
+                .unwrap();
+            let args = if argc == 0 {
+                &[]
+            } else {
+                slice::from_raw_parts(argv as *const Value, argc as usize)
+            };
+            let res = match catch_unwind(AssertUnwindSafe(|| {
+                let _scope = BorrowScope::new();
+                func(args).into_return_value()
+            })) {
+                Ok(v) => v,
+                Err(e) => Err(Error::from_panic(e)),
+            };
+            match res {
+                Ok(v) => v.as_rb_value(),
+                Err(e) => raise(e),
+            }
+        }
+
+        let captured = Captured::wrap(Some(func));
+        unsafe {
+            let fptr = call::<F, R>
+                as unsafe extern "C" fn(VALUE, VALUE, c_int, *const VALUE, VALUE) -> VALUE;
+            #[cfg(ruby_lt_2_7)]
+            let fptr: unsafe extern "C" fn() -> VALUE = std::mem::transmute(fptr);
+            protect(|| Value::new(rb_fiber_new(Some(fptr), captured.as_rb_value())))
+                .map(|v| Self::from_rb_value_unchecked(v.as_rb_value()))
+        }
+    }
+
+    /// Return the currently running fiber.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::Fiber;
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// assert!(Fiber::current().is_alive());
+    /// ```
+    pub fn current() -> Self {
+        unsafe { Self::from_rb_value_unchecked(rb_fiber_current()) }
+    }
+
+    /// Run `self` until it yields or finishes.
+    ///
+    /// On the first call `args` are passed to the fiber's block, after that
+    /// they are returned from the [`yield_values`](Fiber::yield_values) call
+    /// that suspended the fiber. Returns the values passed to
+    /// `yield_values`, or the result of the block when the fiber finishes.
+    ///
+    /// Errors if `self` is dead, or raises an exception.
+    pub fn resume<A, T>(self, args: A) -> Result<T, Error>
+    where
+        A: ArgList,
+        T: TryConvert,
+    {
+        let args = args.into_arg_list();
+        let slice = args.as_ref();
+        unsafe {
+            protect(|| {
+                Value::new(rb_fiber_resume(
+                    self.as_rb_value(),
+                    slice.len() as c_int,
+                    slice.as_ptr() as *const VALUE,
+                ))
+            })
+            .and_then(|v| v.try_convert())
+        }
+    }
+
+    /// Suspend the current fiber, returning `args` from the
+    /// [`resume`](Fiber::resume) call that started it running.
+    ///
+    /// Returns the arguments passed to the next call to `resume`.
+    ///
+    /// Errors if called from the root fiber.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Fiber};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let fib = Fiber::new(|_| {
+    ///     for i in 0..3 {
+    ///         let _: magnus::Value = Fiber::yield_values((i,))?;
+    ///     }
+    ///     Ok::<_, Error>(())
+    /// })
+    /// .unwrap();
+    /// assert_eq!(fib.resume::<_, i64>(()).unwrap(), 0);
+    /// assert_eq!(fib.resume::<_, i64>(()).unwrap(), 1);
+    /// assert_eq!(fib.resume::<_, i64>(()).unwrap(), 2);
+    ///
+    /// assert!(Fiber::yield_values::<_, magnus::Value>(()).is_err());
+    /// ```
+    pub fn yield_values<A, T>(args: A) -> Result<T, Error>
+    where
+        A: ArgList,
+        T: TryConvert,
+    {
+        // this fiber may never be resumed, in which case its stack is
+        // discarded without being unwound, so nothing must need dropping when
+        // switching. Move the args to a Ruby array, dropping the Rust values.
+        // `protect` is fine across a fiber switch, and catches exceptions
+        // raised in the fiber with `Fiber#raise`.
+        let ary = RArray::from_slice(args.into_arg_list().as_ref());
+        unsafe {
+            let slice = ary.as_slice();
+            protect(|| {
+                Value::new(rb_fiber_yield(
+                    slice.len() as c_int,
+                    slice.as_ptr() as *const VALUE,
+                ))
+            })
+            .and_then(|v| v.try_convert())
+        }
+    }
+
+    /// Returns whether `self` can still be resumed.
+    pub fn is_alive(self) -> bool {
+        unsafe { Value::new(rb_fiber_alive_p(self.as_rb_value())).to_bool() }
+    }
+}
+
+impl Deref for Fiber {
+    type Target = Value;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.get_ref()
+    }
+}
+
+impl fmt::Display for Fiber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", unsafe { self.to_s_infallible() })
+    }
+}
+
+impl fmt::Debug for Fiber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.inspect())
+    }
+}
+
+impl From<Fiber> for Value {
+    fn from(val: Fiber) -> Self {
+        *val
+    }
+}
+
+impl Object for Fiber {}
+
+impl TryConvert for Fiber {
+    #[inline]
+    fn try_convert(val: &Value) -> Result<Self, Error> {
+        Self::from_value(*val).ok_or_else(|| {
+            Error::new(
+                exception::type_error(),
+                format!("no implicit conversion of {} into Fiber", unsafe {
+                    val.classname()
+                },),
+            )
+        })
+    }
+}
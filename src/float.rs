@@ -1,4 +1,8 @@
-use std::{fmt, ops::Deref};
+use std::{
+    cmp::Ordering,
+    fmt,
+    ops::{Add, Deref, Div, Mul, Neg, Rem, Sub},
+};
 
 use crate::{
     debug_assert_value,
@@ -80,6 +84,30 @@ impl Float {
     pub fn to_f64(self) -> f64 {
         unsafe { rb_float_value(self.as_rb_value()) }
     }
+
+    /// Return the remainder of dividing `self` by `other`, with the same
+    /// sign as `other`.
+    ///
+    /// This matches the behaviour of Ruby's `Float#%`, and is used by the
+    /// `%` operator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::Float;
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let res = Float::from_f64(-7.0).modulo(Float::from_f64(2.0));
+    /// assert_eq!(res.to_f64(), 1.0);
+    /// ```
+    pub fn modulo(self, other: Self) -> Self {
+        let (a, b) = (self.to_f64(), other.to_f64());
+        let mut res = a % b;
+        if b * res < 0.0 {
+            res += b;
+        }
+        Self::from_f64(res)
+    }
 }
 
 impl Deref for Float {
@@ -123,3 +151,76 @@ impl TryConvert for Float {
         }
     }
 }
+
+macro_rules! impl_binop {
+    ($trait:ident, $fn:ident, $op:tt) => {
+        impl $trait for Float {
+            type Output = Float;
+
+            fn $fn(self, other: Float) -> Float {
+                Float::from_f64(self.to_f64() $op other.to_f64())
+            }
+        }
+
+        impl $trait<f64> for Float {
+            type Output = Float;
+
+            fn $fn(self, other: f64) -> Float {
+                Float::from_f64(self.to_f64() $op other)
+            }
+        }
+    };
+}
+
+impl_binop!(Add, add, +);
+impl_binop!(Sub, sub, -);
+impl_binop!(Mul, mul, *);
+impl_binop!(Div, div, /);
+
+impl Rem for Float {
+    type Output = Float;
+
+    fn rem(self, other: Float) -> Float {
+        self.modulo(other)
+    }
+}
+
+impl Rem<f64> for Float {
+    type Output = Float;
+
+    fn rem(self, other: f64) -> Float {
+        self.modulo(Float::from_f64(other))
+    }
+}
+
+impl Neg for Float {
+    type Output = Float;
+
+    fn neg(self) -> Float {
+        Float::from_f64(-self.to_f64())
+    }
+}
+
+impl PartialEq for Float {
+    fn eq(&self, other: &Self) -> bool {
+        self.to_f64() == other.to_f64()
+    }
+}
+
+impl PartialOrd for Float {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.to_f64().partial_cmp(&other.to_f64())
+    }
+}
+
+impl PartialEq<f64> for Float {
+    fn eq(&self, other: &f64) -> bool {
+        self.to_f64() == *other
+    }
+}
+
+impl PartialOrd<f64> for Float {
+    fn partial_cmp(&self, other: &f64) -> Option<Ordering> {
+        self.to_f64().partial_cmp(other)
+    }
+}
@@ -1,11 +1,21 @@
-use std::{fmt, ops::Deref};
+use std::{
+    cmp::Ordering,
+    convert::TryFrom,
+    fmt,
+    ops::{Add, BitAnd, BitOr, BitXor, Deref, Div, Mul, Neg, Not, Rem, Shl, Shr, Sub},
+};
 
 use crate::{
     debug_assert_value,
     error::{protect, Error},
     exception,
-    r_bignum::RBignum,
-    ruby_sys::{rb_ll2inum, rb_to_int, rb_ull2inum, ruby_special_consts, ruby_value_type, VALUE},
+    float::Float,
+    r_bignum::{unpack_le, RBignum, INTEGER_PACK_2COMP},
+    ruby_sys::{
+        rb_big_and, rb_big_cmp, rb_big_div, rb_big_lshift, rb_big_minus, rb_big_modulo, rb_big_mul,
+        rb_big_or, rb_big_plus, rb_big_pow, rb_big_rshift, rb_big_xor, rb_float_new, rb_int2big,
+        rb_ll2inum, rb_to_int, rb_ull2inum, ruby_special_consts, ruby_value_type, VALUE,
+    },
     try_convert::TryConvert,
     value::{Fixnum, NonZeroValue, Value},
 };
@@ -313,6 +323,350 @@ impl Integer {
             IntegerType::Bignum(big) => big.to_usize(),
         }
     }
+
+    /// Create a new `Integer` from an `i128.`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, Integer};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let res: bool = eval!("i == 0", i = Integer::from_i128(0)).unwrap();
+    /// assert!(res);
+    /// let res: bool = eval!("i == -(2 ** 127)", i = Integer::from_i128(i128::MIN)).unwrap();
+    /// assert!(res);
+    /// ```
+    pub fn from_i128(n: i128) -> Self {
+        if let Ok(n) = i64::try_from(n) {
+            return Self::from_i64(n);
+        }
+        unpack_le(&n.to_le_bytes(), INTEGER_PACK_2COMP)
+    }
+
+    /// Create a new `Integer` from a `u128.`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, Integer};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let res: bool = eval!("i == 0", i = Integer::from_u128(0)).unwrap();
+    /// assert!(res);
+    /// let res: bool = eval!("i == 2 ** 128 - 1", i = Integer::from_u128(u128::MAX)).unwrap();
+    /// assert!(res);
+    /// ```
+    pub fn from_u128(n: u128) -> Self {
+        if let Ok(n) = u64::try_from(n) {
+            return Self::from_u64(n);
+        }
+        unpack_le(&n.to_le_bytes(), 0)
+    }
+
+    /// Convert `self` to an `i128`. Returns `Err` if `self` is out of range
+    /// for `i128`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, Integer};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// assert_eq!(eval::<Integer>("-1").unwrap().to_i128().unwrap(), -1);
+    /// assert_eq!(eval::<Integer>("2 ** 127 - 1").unwrap().to_i128().unwrap(), i128::MAX);
+    /// assert!(eval::<Integer>("2 ** 127").unwrap().to_i128().is_err());
+    /// ```
+    pub fn to_i128(self) -> Result<i128, Error> {
+        match self.integer_type() {
+            IntegerType::Fixnum(fix) => Ok(fix.to_i64() as i128),
+            IntegerType::Bignum(big) => big.to_i128(),
+        }
+    }
+
+    /// Convert `self` to a `u128`. Returns `Err` if `self` is negative or out
+    /// of range for `u128`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, Integer};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// assert_eq!(eval::<Integer>("1").unwrap().to_u128().unwrap(), 1);
+    /// assert_eq!(eval::<Integer>("2 ** 128 - 1").unwrap().to_u128().unwrap(), u128::MAX);
+    /// assert!(eval::<Integer>("-1").unwrap().to_u128().is_err());
+    /// ```
+    pub fn to_u128(self) -> Result<u128, Error> {
+        match self.integer_type() {
+            IntegerType::Fixnum(fix) => fix.to_u64().map(|n| n as u128),
+            IntegerType::Bignum(big) => big.to_u128(),
+        }
+    }
+
+    /// Add `self` and `other`.
+    ///
+    /// This is the fallible version of the `+` operator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, Integer};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let a = Integer::from_i64(4611686018427387903);
+    /// let res = a.checked_add(Integer::from_i64(1)).unwrap();
+    /// assert_eq!(res.to_u64().unwrap(), 4611686018427387904);
+    /// ```
+    pub fn checked_add(self, other: Self) -> Result<Self, Error> {
+        if let (Some(a), Some(b)) = (self.fixnum_value(), other.fixnum_value()) {
+            // fixnums are at most 63 bits, so this can't overflow
+            return Ok(Self::from_i64(a + b));
+        }
+        self.big_op(other, rb_big_plus)
+    }
+
+    /// Subtract `other` from `self`.
+    ///
+    /// This is the fallible version of the `-` operator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, Integer};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let a = Integer::from_i64(-4611686018427387904);
+    /// let res = a.checked_sub(Integer::from_i64(1)).unwrap();
+    /// assert_eq!(res.to_i64().unwrap(), -4611686018427387905);
+    /// ```
+    pub fn checked_sub(self, other: Self) -> Result<Self, Error> {
+        if let (Some(a), Some(b)) = (self.fixnum_value(), other.fixnum_value()) {
+            return Ok(Self::from_i64(a - b));
+        }
+        self.big_op(other, rb_big_minus)
+    }
+
+    /// Multiply `self` by `other`.
+    ///
+    /// This is the fallible version of the `*` operator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, Integer};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let a = Integer::from_i64(4294967296);
+    /// let res = a.checked_mul(a).unwrap();
+    /// let res: bool = eval!("res == 2 ** 64", res).unwrap();
+    /// assert!(res);
+    /// ```
+    pub fn checked_mul(self, other: Self) -> Result<Self, Error> {
+        if let (Some(a), Some(b)) = (self.fixnum_value(), other.fixnum_value()) {
+            if let Some(res) = a.checked_mul(b) {
+                return Ok(Self::from_i64(res));
+            }
+        }
+        self.big_op(other, rb_big_mul)
+    }
+
+    /// Divide `self` by `other`, rounding towards negative infinity.
+    ///
+    /// This is the fallible version of the `/` operator, and matches the
+    /// behaviour of Ruby's `Integer#/`. Returns `Err` if `other` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, Integer};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let res = Integer::from_i64(-7).checked_div(Integer::from_i64(2)).unwrap();
+    /// assert_eq!(res.to_i64().unwrap(), -4);
+    /// assert!(Integer::from_i64(1).checked_div(Integer::from_i64(0)).is_err());
+    /// ```
+    pub fn checked_div(self, other: Self) -> Result<Self, Error> {
+        if let (Some(a), Some(b)) = (self.fixnum_value(), other.fixnum_value()) {
+            if b != 0 {
+                let mut res = a / b;
+                if a % b != 0 && (a < 0) != (b < 0) {
+                    res -= 1;
+                }
+                return Ok(Self::from_i64(res));
+            }
+        }
+        self.big_op(other, rb_big_div)
+    }
+
+    /// Return the remainder of dividing `self` by `other`, with the same
+    /// sign as `other`.
+    ///
+    /// This is the fallible version of the `%` operator, and matches the
+    /// behaviour of Ruby's `Integer#%`. Returns `Err` if `other` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, Integer};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let res = Integer::from_i64(-7).checked_rem(Integer::from_i64(2)).unwrap();
+    /// assert_eq!(res.to_i64().unwrap(), 1);
+    /// assert!(Integer::from_i64(1).checked_rem(Integer::from_i64(0)).is_err());
+    /// ```
+    pub fn checked_rem(self, other: Self) -> Result<Self, Error> {
+        if let (Some(a), Some(b)) = (self.fixnum_value(), other.fixnum_value()) {
+            if b != 0 {
+                let mut res = a % b;
+                if res != 0 && (res < 0) != (b < 0) {
+                    res += b;
+                }
+                return Ok(Self::from_i64(res));
+            }
+        }
+        self.big_op(other, rb_big_modulo)
+    }
+
+    /// Raise `self` to the power of `exp`.
+    ///
+    /// Returns `Err` if the result is too large to be represented.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, Integer};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// assert_eq!(Integer::from_i64(3).pow(4).unwrap().to_i64().unwrap(), 81);
+    /// let res: bool = eval!("res == 2 ** 100", res = Integer::from_i64(2).pow(100).unwrap()).unwrap();
+    /// assert!(res);
+    /// ```
+    pub fn pow(self, exp: u32) -> Result<Self, Error> {
+        if let Some(res) = self.fixnum_value().and_then(|a| a.checked_pow(exp)) {
+            return Ok(Self::from_i64(res));
+        }
+        let exp = Self::from_u64(exp as u64);
+        unsafe {
+            protect(|| Value::new(rb_big_pow(self.bignum_value(), exp.as_rb_value()))).and_then(
+                |v| {
+                    Self::from_value(v)
+                        .ok_or_else(|| Error::new(exception::range_error(), "exponent too large"))
+                },
+            )
+        }
+    }
+
+    fn checked_neg(self) -> Result<Self, Error> {
+        if let Some(a) = self.fixnum_value() {
+            return Ok(Self::from_i64(-a));
+        }
+        Self::from_i64(0).big_op(self, rb_big_minus)
+    }
+
+    fn checked_not(self) -> Result<Self, Error> {
+        if let Some(a) = self.fixnum_value() {
+            return Ok(Self::from_i64(!a));
+        }
+        self.big_op(Self::from_i64(-1), rb_big_xor)
+    }
+
+    fn checked_bitand(self, other: Self) -> Result<Self, Error> {
+        if let (Some(a), Some(b)) = (self.fixnum_value(), other.fixnum_value()) {
+            return Ok(Self::from_i64(a & b));
+        }
+        self.big_op(other, rb_big_and)
+    }
+
+    fn checked_bitor(self, other: Self) -> Result<Self, Error> {
+        if let (Some(a), Some(b)) = (self.fixnum_value(), other.fixnum_value()) {
+            return Ok(Self::from_i64(a | b));
+        }
+        self.big_op(other, rb_big_or)
+    }
+
+    fn checked_bitxor(self, other: Self) -> Result<Self, Error> {
+        if let (Some(a), Some(b)) = (self.fixnum_value(), other.fixnum_value()) {
+            return Ok(Self::from_i64(a ^ b));
+        }
+        self.big_op(other, rb_big_xor)
+    }
+
+    fn checked_shl(self, n: u32) -> Result<Self, Error> {
+        if let Some(a) = self.fixnum_value() {
+            if n < 64 && (a << n) >> n == a {
+                return Ok(Self::from_i64(a << n));
+            }
+        }
+        self.big_op(Self::from_u64(n as u64), rb_big_lshift)
+    }
+
+    fn checked_shr(self, n: u32) -> Result<Self, Error> {
+        if let Some(a) = self.fixnum_value() {
+            return Ok(Self::from_i64(a >> n.min(63)));
+        }
+        self.big_op(Self::from_u64(n as u64), rb_big_rshift)
+    }
+
+    /// Compare `self` with `other` exactly, without converting `self` to a
+    /// float. Returns `None` if `other` is NaN.
+    fn partial_cmp_f64(self, other: f64) -> Option<Ordering> {
+        if let Some(a) = self.fixnum_value() {
+            if other.is_nan() {
+                return None;
+            }
+            // fixnums are at most 63 bits, so are always between these
+            if other >= 9223372036854775808.0 {
+                return Some(Ordering::Less);
+            }
+            if other < -9223372036854775808.0 {
+                return Some(Ordering::Greater);
+            }
+            let trunc = other.trunc();
+            return match a.cmp(&(trunc as i64)) {
+                Ordering::Equal => 0.0.partial_cmp(&(other - trunc)),
+                ord => Some(ord),
+            };
+        }
+        let res = unsafe { Value::new(rb_big_cmp(self.as_rb_value(), rb_float_new(other))) };
+        Self::from_value(res).and_then(|res| res.fixnum_value().map(|res| res.cmp(&0)))
+    }
+
+    /// Returns the value of `self` if `self` is a `Fixnum`, without the
+    /// overhead of a function call.
+    #[inline]
+    fn fixnum_value(self) -> Option<i64> {
+        (self.as_rb_value() & ruby_special_consts::RUBY_FIXNUM_FLAG as VALUE != 0)
+            .then(|| (self.as_rb_value() as isize >> 1) as i64)
+    }
+
+    /// Returns `self` as a bignum, allocating a new bignum if `self` is a
+    /// `Fixnum`. Must be called within `protect`.
+    unsafe fn bignum_value(self) -> VALUE {
+        match self.fixnum_value() {
+            Some(n) => rb_int2big(n as isize),
+            None => self.as_rb_value(),
+        }
+    }
+
+    fn big_cmp(self, other: Self) -> Ordering {
+        unsafe {
+            let res =
+                Self::from_rb_value_unchecked(rb_big_cmp(self.as_rb_value(), other.as_rb_value()));
+            res.fixnum_value().unwrap().cmp(&0)
+        }
+    }
+
+    fn big_op(
+        self,
+        other: Self,
+        func: unsafe extern "C" fn(VALUE, VALUE) -> VALUE,
+    ) -> Result<Self, Error> {
+        unsafe {
+            protect(|| Value::new(func(self.bignum_value(), other.as_rb_value())))
+                .map(|v| Self::from_rb_value_unchecked(v.as_rb_value()))
+        }
+    }
 }
 
 impl Deref for Integer {
@@ -356,3 +710,229 @@ impl TryConvert for Integer {
         }
     }
 }
+
+impl From<RBignum> for Integer {
+    fn from(val: RBignum) -> Self {
+        unsafe { Self::from_rb_value_unchecked(val.as_rb_value()) }
+    }
+}
+
+impl From<Fixnum> for Integer {
+    fn from(val: Fixnum) -> Self {
+        unsafe { Self::from_rb_value_unchecked(val.as_rb_value()) }
+    }
+}
+
+macro_rules! impl_binop {
+    ($trait:ident, $fn:ident, $checked:ident) => {
+        impl $trait for Integer {
+            type Output = Integer;
+
+            fn $fn(self, other: Integer) -> Integer {
+                self.$checked(other).unwrap_or_else(|e| panic!("{}", e))
+            }
+        }
+
+        impl $trait<i64> for Integer {
+            type Output = Integer;
+
+            fn $fn(self, other: i64) -> Integer {
+                self.$fn(Integer::from_i64(other))
+            }
+        }
+
+        impl $trait<Integer> for RBignum {
+            type Output = Integer;
+
+            fn $fn(self, other: Integer) -> Integer {
+                Integer::from(self).$fn(other)
+            }
+        }
+
+        impl $trait<i64> for RBignum {
+            type Output = Integer;
+
+            fn $fn(self, other: i64) -> Integer {
+                Integer::from(self).$fn(Integer::from_i64(other))
+            }
+        }
+    };
+}
+
+impl_binop!(Add, add, checked_add);
+impl_binop!(Sub, sub, checked_sub);
+impl_binop!(Mul, mul, checked_mul);
+impl_binop!(Div, div, checked_div);
+impl_binop!(Rem, rem, checked_rem);
+impl_binop!(BitAnd, bitand, checked_bitand);
+impl_binop!(BitOr, bitor, checked_bitor);
+impl_binop!(BitXor, bitxor, checked_bitxor);
+
+macro_rules! impl_unop {
+    ($trait:ident, $fn:ident, $checked:ident) => {
+        impl $trait for Integer {
+            type Output = Integer;
+
+            fn $fn(self) -> Integer {
+                self.$checked().unwrap_or_else(|e| panic!("{}", e))
+            }
+        }
+
+        impl $trait for RBignum {
+            type Output = Integer;
+
+            fn $fn(self) -> Integer {
+                Integer::from(self).$fn()
+            }
+        }
+    };
+}
+
+impl_unop!(Neg, neg, checked_neg);
+impl_unop!(Not, not, checked_not);
+
+macro_rules! impl_shift {
+    ($trait:ident, $fn:ident, $checked:ident) => {
+        impl $trait<u32> for Integer {
+            type Output = Integer;
+
+            fn $fn(self, n: u32) -> Integer {
+                self.$checked(n).unwrap_or_else(|e| panic!("{}", e))
+            }
+        }
+
+        impl $trait<u32> for RBignum {
+            type Output = Integer;
+
+            fn $fn(self, n: u32) -> Integer {
+                Integer::from(self).$fn(n)
+            }
+        }
+    };
+}
+
+impl_shift!(Shl, shl, checked_shl);
+impl_shift!(Shr, shr, checked_shr);
+
+impl PartialEq for Integer {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Integer {}
+
+impl PartialOrd for Integer {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Integer {
+    fn cmp(&self, other: &Self) -> Ordering {
+        if let (Some(a), Some(b)) = (self.fixnum_value(), other.fixnum_value()) {
+            return a.cmp(&b);
+        }
+        // rb_big_cmp requires a bignum lhs, but a fixnum is fine as rhs
+        if self.fixnum_value().is_some() {
+            other.big_cmp(*self).reverse()
+        } else {
+            self.big_cmp(*other)
+        }
+    }
+}
+
+impl PartialEq<RBignum> for Integer {
+    fn eq(&self, other: &RBignum) -> bool {
+        *self == Integer::from(*other)
+    }
+}
+
+impl PartialOrd<RBignum> for Integer {
+    fn partial_cmp(&self, other: &RBignum) -> Option<Ordering> {
+        self.partial_cmp(&Integer::from(*other))
+    }
+}
+
+impl PartialEq<Integer> for RBignum {
+    fn eq(&self, other: &Integer) -> bool {
+        Integer::from(*self) == *other
+    }
+}
+
+impl PartialOrd<Integer> for RBignum {
+    fn partial_cmp(&self, other: &Integer) -> Option<Ordering> {
+        Integer::from(*self).partial_cmp(other)
+    }
+}
+
+impl PartialEq<Float> for Integer {
+    fn eq(&self, other: &Float) -> bool {
+        self.partial_cmp_f64(other.to_f64()) == Some(Ordering::Equal)
+    }
+}
+
+impl PartialOrd<Float> for Integer {
+    fn partial_cmp(&self, other: &Float) -> Option<Ordering> {
+        self.partial_cmp_f64(other.to_f64())
+    }
+}
+
+impl PartialEq<Integer> for Float {
+    fn eq(&self, other: &Integer) -> bool {
+        other == self
+    }
+}
+
+impl PartialOrd<Integer> for Float {
+    fn partial_cmp(&self, other: &Integer) -> Option<Ordering> {
+        other.partial_cmp(self).map(Ordering::reverse)
+    }
+}
+
+impl PartialEq<f64> for Integer {
+    fn eq(&self, other: &f64) -> bool {
+        self.partial_cmp_f64(*other) == Some(Ordering::Equal)
+    }
+}
+
+impl PartialOrd<f64> for Integer {
+    fn partial_cmp(&self, other: &f64) -> Option<Ordering> {
+        self.partial_cmp_f64(*other)
+    }
+}
+
+macro_rules! impl_primitive_cmp {
+    ($from:ident, $cast:ty, $($prim:ty),*) => {
+        $(
+            impl PartialEq<$prim> for Integer {
+                fn eq(&self, other: &$prim) -> bool {
+                    *self == Integer::$from(*other as $cast)
+                }
+            }
+
+            impl PartialOrd<$prim> for Integer {
+                fn partial_cmp(&self, other: &$prim) -> Option<Ordering> {
+                    self.partial_cmp(&Integer::$from(*other as $cast))
+                }
+            }
+
+            impl PartialEq<$prim> for RBignum {
+                fn eq(&self, other: &$prim) -> bool {
+                    Integer::from(*self) == *other
+                }
+            }
+
+            impl PartialOrd<$prim> for RBignum {
+                fn partial_cmp(&self, other: &$prim) -> Option<Ordering> {
+                    Integer::from(*self).partial_cmp(other)
+                }
+            }
+        )*
+    };
+}
+
+impl_primitive_cmp!(from_i64, i64, i8, i16, i32, i64, isize);
+impl_primitive_cmp!(from_u64, u64, u8, u16, u32, u64, usize);
+impl_primitive_cmp!(from_i128, i128, i128);
+impl_primitive_cmp!(from_u128, u128, u128);
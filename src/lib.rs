@@ -8,16 +8,17 @@
 //! types are available. These wrappers will [`Deref`](`std::ops::Deref`) to
 //! `Value`, so you can still use `Value`'s methods on them.
 //!
-//! | Ruby Class | Magnus Type |
-//! |------------|-------------|
-//! | `String`   | [`RString`] |
-//! | `Integer`  | [`Integer`] |
-//! | `Float`    | [`Float`]   |
-//! | `Array`    | [`RArray`]  |
-//! | `Hash`     | [`RHash`]   |
-//! | `Symbol`   | [`Symbol`]  |
-//! | `Class`    | [`RClass`]  |
-//! | `Module`   | [`RModule`] |
+//! | Ruby Class | Magnus Type    |
+//! |------------|----------------|
+//! | `String`   | [`RString`]    |
+//! | `Integer`  | [`Integer`]    |
+//! | `Float`    | [`Float`]      |
+//! | `Array`    | [`RArray`]     |
+//! | `Hash`     | [`RHash`]      |
+//! | `Symbol`   | [`Symbol`]     |
+//! | `Encoding` | [`Encoding`]   |
+//! | `Class`    | [`RClass`]     |
+//! | `Module`   | [`RModule`]    |
 //!
 //! When writing Rust code to be called from Ruby the [`init`] attribute can
 //! be used to mark your init function that Ruby will call when your library
@@ -93,21 +94,52 @@
 
 #![warn(missing_docs)]
 
+// Expands to the number of token trees passed to it, as a constant expression.
+macro_rules! count {
+    () => { 0 };
+    ($head:tt $($tail:tt)*) => { 1 + count!($($tail)*) };
+}
+
+// Invokes `$mac` with each prefix of a list of `Type index` pairs, from the
+// empty list up to 32 pairs, for implementing traits on tuples and functions.
+macro_rules! for_each_tuple {
+    ($mac:ident) => {
+        for_each_tuple!($mac []
+            T0 0 T1 1 T2 2 T3 3 T4 4 T5 5 T6 6 T7 7 T8 8 T9 9 T10 10 T11 11 T12 12 T13 13 T14 14
+            T15 15 T16 16 T17 17 T18 18 T19 19 T20 20 T21 21 T22 22 T23 23 T24 24 T25 25 T26 26
+            T27 27 T28 28 T29 29 T30 30 T31 31
+        );
+    };
+    ($mac:ident [$($done:tt)*]) => {
+        $mac!($($done)*);
+    };
+    ($mac:ident [$($done:tt)*] $t:ident $i:tt $($rest:tt)*) => {
+        $mac!($($done)*);
+        for_each_tuple!($mac [$($done)* $t $i] $($rest)*);
+    };
+}
+
+#[cfg(feature = "num-bigint")]
+mod bigint;
 mod binding;
 pub mod block;
 pub mod class;
+#[doc(hidden)]
+pub mod derive;
 #[cfg(feature = "embed")]
 pub mod embed;
+pub mod encoding;
 mod enumerator;
 pub mod error;
 pub mod exception;
+mod fiber;
 mod float;
 pub mod gc;
 mod integer;
 pub mod method;
 pub mod module;
 mod object;
-mod r_array;
+pub mod r_array;
 mod r_bignum;
 mod r_complex;
 mod r_file;
@@ -119,38 +151,53 @@ mod r_rational;
 mod r_regexp;
 pub mod r_string;
 pub mod r_struct;
+mod r_thread;
+mod r_time;
 pub mod r_typed_data;
 mod range;
 mod ruby_sys;
 pub mod scan_args;
+#[cfg(feature = "serde")]
+pub mod serde;
 mod symbol;
 mod try_convert;
 pub mod value;
 
-use std::{ffi::CString, mem::transmute};
+use std::{
+    ffi::CString,
+    mem::transmute,
+    os::raw::c_void,
+    panic::{catch_unwind, AssertUnwindSafe},
+    ptr,
+};
 
-pub use magnus_macros::{init, wrap, DataTypeFunctions, TypedData};
+pub use magnus_macros::{
+    init, methods, wrap, DataTypeFunctions, IntoValue, KwArgs, TryConvert, TypedData,
+};
 
 use error::protect;
 use method::Method;
 use ruby_sys::{
-    rb_define_class, rb_define_global_function, rb_define_module, rb_define_variable, rb_errinfo,
-    rb_eval_string_protect, rb_set_errinfo, VALUE,
+    rb_backref_get, rb_backref_set, rb_define_class, rb_define_global_function, rb_define_module,
+    rb_define_variable, rb_errinfo, rb_eval_string_protect, rb_set_errinfo,
+    rb_thread_call_without_gvl, VALUE,
 };
 
 pub use value::{Fixnum, Flonum, StaticSymbol, Value, QFALSE, QNIL, QTRUE};
 pub use {
     binding::Binding,
     class::RClass,
+    encoding::Encoding,
     enumerator::Enumerator,
     error::Error,
     exception::{Exception, ExceptionClass},
+    fiber::Fiber,
     float::Float,
     integer::Integer,
     module::Module,
     module::RModule,
     object::Object,
-    r_array::RArray,
+    r_array::{RArray, TypedArray},
     r_bignum::RBignum,
     r_complex::RComplex,
     r_file::RFile,
@@ -162,6 +209,8 @@ pub use {
     r_regexp::RRegexp,
     r_string::RString,
     r_struct::RStruct,
+    r_thread::RThread,
+    r_time::RTime,
     r_typed_data::{DataType, DataTypeFunctions, RTypedData, TypedData},
     range::Range,
     symbol::Symbol,
@@ -245,6 +294,53 @@ where
     }
 }
 
+/// Return the last match (`$~`) in the calling Ruby frame.
+///
+/// Returns `None` if the last match failed, or there has been no match.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{backref_get, define_global_function, eval, function};
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// fn last_match_text() -> Option<String> {
+///     backref_get().and_then(|m| m.nth(0)).map(|s| s.to_string().unwrap())
+/// }
+///
+/// define_global_function("last_match_text", function!(last_match_text, 0));
+///
+/// let res: Option<String> = eval(r#"/o+/ =~ "foo"; last_match_text"#).unwrap();
+/// assert_eq!(res.unwrap(), "oo");
+/// ```
+pub fn backref_get() -> Option<RMatch> {
+    RMatch::from_value(unsafe { Value::new(rb_backref_get()) })
+}
+
+/// Set the last match (`$~`) in the calling Ruby frame.
+///
+/// Pass `None` to clear the last match.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{backref_set, define_global_function, eval, function};
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// fn forget_match() {
+///     backref_set(None)
+/// }
+///
+/// define_global_function("forget_match", function!(forget_match, 0));
+///
+/// let res: bool = eval(r#"/o+/ =~ "foo"; forget_match; $~.nil?"#).unwrap();
+/// assert!(res);
+/// ```
+pub fn backref_set(m: Option<RMatch>) {
+    let val = m.map(|m| *m).unwrap_or_else(|| *QNIL);
+    unsafe { rb_backref_set(val.as_rb_value()) }
+}
+
 /// Evaluate a string of Ruby code, converting the result to a `T`.
 ///
 /// Ruby will use the 'ASCII-8BIT' (aka binary) encoding for any Ruby string
@@ -286,3 +382,120 @@ where
         other => Err(Error::Jump(unsafe { transmute(other) })),
     }
 }
+
+/// Run `func` with the Global VM Lock (GVL) released, allowing other Ruby
+/// threads to run in parallel.
+///
+/// Ruby objects must not be accessed without holding the GVL. As [`Value`]
+/// (and all the types wrapping it) is not [`Send`], the `Send` bounds on
+/// `func` and its return value ensure it can not capture or return any Ruby
+/// objects.
+///
+/// If Ruby needs to interrupt the current thread while `func` is running,
+/// e.g. the thread is killed or the process receives a signal, the `unblock`
+/// function is called (from a different thread) and should cause `func` to
+/// return as soon as possible. `unblock` may be `None` if `func` can not be
+/// interrupted, in which case the interrupt will be delayed until `func`
+/// returns.
+///
+/// Once `func` returns any pending interrupts are handled, so an `Err` may be
+/// returned even if `func` completed. If `func` panics the panic is returned
+/// as an `Err`.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::without_gvl;
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// let data = vec![1_u64, 2, 3, 4];
+/// let sum = without_gvl(|| data.iter().sum::<u64>(), None::<fn()>).unwrap();
+/// assert_eq!(sum, 10);
+/// ```
+///
+/// Using an `unblock` function to stop long running work:
+///
+/// ```
+/// use std::sync::{
+///     atomic::{AtomicBool, Ordering},
+///     Arc,
+/// };
+///
+/// use magnus::without_gvl;
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// let cancelled = Arc::new(AtomicBool::new(false));
+/// let flag = cancelled.clone();
+/// let res = without_gvl(
+///     || {
+///         let mut i = 0_u64;
+///         while i < 1_000 && !cancelled.load(Ordering::Relaxed) {
+///             i += 1;
+///         }
+///         i
+///     },
+///     Some(move || flag.store(true, Ordering::Relaxed)),
+/// );
+/// assert_eq!(res.unwrap(), 1_000);
+/// ```
+pub fn without_gvl<F, R, U>(func: F, unblock: Option<U>) -> Result<R, Error>
+where
+    F: FnOnce() -> R + Send,
+    R: Send,
+    U: FnMut() + Send,
+{
+    unsafe extern "C" fn call<F, R>(data: *mut c_void) -> *mut c_void
+    where
+        F: FnOnce() -> R,
+    {
+        let (func, res) = &mut *(data as *mut (Option<F>, Option<std::thread::Result<R>>));
+        if let Some(func) = func.take() {
+            *res = Some(catch_unwind(AssertUnwindSafe(func)));
+        }
+        ptr::null_mut()
+    }
+
+    unsafe extern "C" fn call_unblock<U>(data: *mut c_void)
+    where
+        U: FnMut(),
+    {
+        let unblock = &mut *(data as *mut U);
+        // this is called from another thread, with nowhere to report errors
+        // to, and unwinding into C is undefined behaviour, so give up.
+        if catch_unwind(AssertUnwindSafe(unblock)).is_err() {
+            std::process::abort();
+        }
+    }
+
+    let mut data: (Option<F>, Option<std::thread::Result<R>>) = (Some(func), None);
+    let mut unblock = unblock;
+    let (ubf, ubf_data) = match unblock.as_mut() {
+        Some(u) => (
+            Some(call_unblock::<U> as unsafe extern "C" fn(*mut c_void)),
+            u as *mut U as *mut c_void,
+        ),
+        None => (None, ptr::null_mut()),
+    };
+    // rb_thread_call_without_gvl will check for interrupts after `func`
+    // returns, which may raise.
+    let interrupt = unsafe {
+        protect(|| {
+            rb_thread_call_without_gvl(
+                Some(call::<F, R>),
+                &mut data as *mut _ as *mut c_void,
+                ubf,
+                ubf_data,
+            );
+            *QNIL
+        })
+    };
+    interrupt?;
+    match data.1 {
+        Some(Ok(v)) => Ok(v),
+        Some(Err(e)) => Err(Error::from_panic(e)),
+        None => Err(Error::new(
+            exception::runtime_error(),
+            "function was not called",
+        )),
+    }
+}
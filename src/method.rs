@@ -4,7 +4,10 @@
 #![allow(clippy::many_single_char_names)]
 #![allow(clippy::missing_safety_doc)]
 
-use std::{ffi::c_void, marker::PhantomData, os::raw::c_int, panic::AssertUnwindSafe, slice};
+use std::{
+    cell::UnsafeCell, ffi::c_void, fmt, marker::PhantomData, os::raw::c_int,
+    panic::AssertUnwindSafe, slice, sync::Once,
+};
 
 use crate::{
     block::{
@@ -12,9 +15,12 @@ use crate::{
         YieldValues,
     },
     error::{raise, Error},
+    exception,
     r_array::RArray,
+    r_typed_data::BorrowScope,
+    scan_args::{scan_params, ArgSpec},
     try_convert::{ArgList, TryConvert},
-    value::Value,
+    value::{Value, QNIL},
 };
 
 mod private {
@@ -46,323 +52,47 @@ mod private {
         }
     }
 
-    unsafe impl Method for unsafe extern "C" fn(Value) -> Value {
-        fn arity() -> i8 {
-            0
-        }
-
-        fn as_ptr(self) -> *mut c_void {
-            self as *mut c_void
-        }
-    }
-
-    unsafe impl Method for unsafe extern "C" fn(Value, Value) -> Value {
-        fn arity() -> i8 {
-            1
-        }
-
-        fn as_ptr(self) -> *mut c_void {
-            self as *mut c_void
-        }
-    }
-
-    unsafe impl Method for unsafe extern "C" fn(Value, Value, Value) -> Value {
-        fn arity() -> i8 {
-            2
-        }
-
-        fn as_ptr(self) -> *mut c_void {
-            self as *mut c_void
-        }
-    }
-
-    unsafe impl Method for unsafe extern "C" fn(Value, Value, Value, Value) -> Value {
-        fn arity() -> i8 {
-            3
-        }
-
-        fn as_ptr(self) -> *mut c_void {
-            self as *mut c_void
-        }
-    }
-
-    unsafe impl Method for unsafe extern "C" fn(Value, Value, Value, Value, Value) -> Value {
-        fn arity() -> i8 {
-            4
-        }
-
-        fn as_ptr(self) -> *mut c_void {
-            self as *mut c_void
-        }
-    }
-
-    unsafe impl Method for unsafe extern "C" fn(Value, Value, Value, Value, Value, Value) -> Value {
-        fn arity() -> i8 {
-            5
-        }
-
-        fn as_ptr(self) -> *mut c_void {
-            self as *mut c_void
-        }
-    }
-
-    unsafe impl Method
-        for unsafe extern "C" fn(Value, Value, Value, Value, Value, Value, Value) -> Value
-    {
-        fn arity() -> i8 {
-            6
-        }
-
-        fn as_ptr(self) -> *mut c_void {
-            self as *mut c_void
-        }
-    }
-
-    unsafe impl Method
-        for unsafe extern "C" fn(Value, Value, Value, Value, Value, Value, Value, Value) -> Value
-    {
-        fn arity() -> i8 {
-            7
-        }
-
-        fn as_ptr(self) -> *mut c_void {
-            self as *mut c_void
-        }
-    }
-
-    unsafe impl Method
-        for unsafe extern "C" fn(
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-        ) -> Value
-    {
-        fn arity() -> i8 {
-            8
-        }
-
-        fn as_ptr(self) -> *mut c_void {
-            self as *mut c_void
-        }
-    }
-
-    unsafe impl Method
-        for unsafe extern "C" fn(
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-        ) -> Value
-    {
-        fn arity() -> i8 {
-            9
-        }
-
-        fn as_ptr(self) -> *mut c_void {
-            self as *mut c_void
-        }
-    }
-
-    unsafe impl Method
-        for unsafe extern "C" fn(
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-        ) -> Value
-    {
-        fn arity() -> i8 {
-            10
-        }
-
-        fn as_ptr(self) -> *mut c_void {
-            self as *mut c_void
-        }
-    }
-
-    unsafe impl Method
-        for unsafe extern "C" fn(
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-        ) -> Value
-    {
-        fn arity() -> i8 {
-            11
-        }
-
-        fn as_ptr(self) -> *mut c_void {
-            self as *mut c_void
-        }
-    }
-
-    unsafe impl Method
-        for unsafe extern "C" fn(
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-        ) -> Value
-    {
-        fn arity() -> i8 {
-            12
-        }
-
-        fn as_ptr(self) -> *mut c_void {
-            self as *mut c_void
-        }
-    }
-
-    unsafe impl Method
-        for unsafe extern "C" fn(
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-        ) -> Value
-    {
-        fn arity() -> i8 {
-            13
-        }
-
-        fn as_ptr(self) -> *mut c_void {
-            self as *mut c_void
-        }
-    }
-
-    unsafe impl Method
-        for unsafe extern "C" fn(
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-        ) -> Value
-    {
-        fn arity() -> i8 {
-            14
-        }
-
-        fn as_ptr(self) -> *mut c_void {
-            self as *mut c_void
-        }
-    }
-
-    unsafe impl Method
-        for unsafe extern "C" fn(
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-        ) -> Value
-    {
-        fn arity() -> i8 {
-            15
-        }
+    macro_rules! impl_method {
+        ($arity:literal $(, $arg:ty)*) => {
+            unsafe impl Method for unsafe extern "C" fn(Value $(, $arg)*) -> Value {
+                fn arity() -> i8 {
+                    $arity
+                }
 
-        fn as_ptr(self) -> *mut c_void {
-            self as *mut c_void
-        }
+                fn as_ptr(self) -> *mut c_void {
+                    self as *mut c_void
+                }
+            }
+        };
     }
 
-    unsafe impl Method
-        for unsafe extern "C" fn(
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-            Value,
-        ) -> Value
-    {
-        fn arity() -> i8 {
-            16
-        }
-
-        fn as_ptr(self) -> *mut c_void {
-            self as *mut c_void
-        }
-    }
+    impl_method!(0);
+    impl_method!(1, Value);
+    impl_method!(2, Value, Value);
+    impl_method!(3, Value, Value, Value);
+    impl_method!(4, Value, Value, Value, Value);
+    impl_method!(5, Value, Value, Value, Value, Value);
+    impl_method!(6, Value, Value, Value, Value, Value, Value);
+    impl_method!(7, Value, Value, Value, Value, Value, Value, Value);
+    impl_method!(8, Value, Value, Value, Value, Value, Value, Value, Value);
+    impl_method!(9, Value, Value, Value, Value, Value, Value, Value, Value, Value);
+    impl_method!(10, Value, Value, Value, Value, Value, Value, Value, Value, Value, Value);
+    impl_method!(11, Value, Value, Value, Value, Value, Value, Value, Value, Value, Value, Value);
+    impl_method!(
+        12, Value, Value, Value, Value, Value, Value, Value, Value, Value, Value, Value, Value
+    );
+    impl_method!(
+        13, Value, Value, Value, Value, Value, Value, Value, Value, Value, Value, Value, Value,
+        Value
+    );
+    impl_method!(
+        14, Value, Value, Value, Value, Value, Value, Value, Value, Value, Value, Value, Value,
+        Value, Value
+    );
+    impl_method!(
+        15, Value, Value, Value, Value, Value, Value, Value, Value, Value, Value, Value, Value,
+        Value, Value, Value
+    );
 
     pub trait ReturnValue {
         fn into_return_value(self) -> Result<Value, Error>;
@@ -520,9 +250,9 @@ mod private {
 /// |     1 | `unsafe extern "C" fn(Value, Value) -> Value;`               |
 /// |     2 | `unsafe extern "C" fn(Value, Value, Value) -> Value;`        |
 /// |   ... | ...                                                          |
-/// |    16 | ...                                                          |
+/// |    15 | ...                                                          |
 ///
-/// note: for arity 0..=16 the number of arguments is 1 greater than the arity,
+/// note: for arity 0..=15 the number of arguments is 1 greater than the arity,
 /// due to the initial `self` argument.
 pub trait Method: private::Method {}
 
@@ -591,6 +321,15 @@ pub trait BlockReturn: private::BlockReturn {}
 
 impl<T> BlockReturn for T where T: private::BlockReturn {}
 
+/// Trait for types that can define their Ruby methods.
+///
+/// This is implemented by the [`methods`](magnus_macros::methods) attribute
+/// macro, see that for more details.
+pub trait DefineMethods {
+    /// Define the Ruby methods for `Self`, on the class `Self` is wrapped as.
+    fn define_methods();
+}
+
 /// Helper type for wrapping a function with type conversions and error
 /// handling, as an 'init' function.
 ///
@@ -673,12 +412,13 @@ where
     }
 
     #[inline]
-    unsafe fn call_convert_value(
+    pub(crate) unsafe fn call_convert_value(
         mut self,
         argc: c_int,
         argv: *const Value,
         blockarg: Value,
     ) -> Result<Value, Error> {
+        let _scope = BorrowScope::new();
         let args = slice::from_raw_parts(argv, argc as usize);
         (self.func)(args, Proc::from_value(blockarg)).into_block_return()
     }
@@ -716,6 +456,7 @@ where
 
     #[inline]
     unsafe fn call_convert_value(self, rb_self: Value, args: RArray) -> Result<Value, Error> {
+        let _scope = BorrowScope::new();
         (self.func)(rb_self.try_convert()?, args.try_convert()?).into_return_value()
     }
 
@@ -768,6 +509,7 @@ where
         argv: *const Value,
         rb_self: Value,
     ) -> Result<Value, Error> {
+        let _scope = BorrowScope::new();
         let args = slice::from_raw_parts(argv, argc as usize);
         (self.func)(rb_self.try_convert()?, args).into_return_value()
     }
@@ -792,145 +534,220 @@ where
     }
 }
 
-/// Helper type for wrapping a function as a Ruby method taking self and no
-/// arguments, with type conversions and error handling.
+/// Helper trait for calling a function with arguments converted from a slice
+/// of [`Value`]s.
 ///
-/// See the [`method`] macro.
+/// See [`MethodN`] and [`FunctionN`].
 #[doc(hidden)]
-pub struct Method0<Func, RbSelf, Res> {
-    func: Func,
-    rb_self: PhantomData<RbSelf>,
-    res: PhantomData<Res>,
+pub trait CallParams<Args, Res> {
+    const LEN: usize;
+
+    type Values: AsRef<[Value]>;
+
+    fn call_params(&self, args: &[Value]) -> Result<Res, Error>;
 }
 
-#[allow(missing_docs)]
-impl<Func, RbSelf, Res> Method0<Func, RbSelf, Res>
-where
-    Func: Fn(RbSelf) -> Res,
-    RbSelf: TryConvert,
-    Res: ReturnValue,
-{
-    #[inline]
-    pub fn new(func: Func) -> Self {
-        Self {
-            func,
-            rb_self: Default::default(),
+macro_rules! impl_call_params {
+    ($($arg:ident $i:tt)*) => {
+        impl<Func, $($arg,)* Res> CallParams<($($arg,)*), Res> for Func
+        where
+            Func: Fn($($arg),*) -> Res,
+            $($arg: TryConvert,)*
+        {
+            const LEN: usize = count!($($arg)*);
 
-            res: Default::default(),
+            type Values = [Value; count!($($arg)*)];
+
+            #[inline]
+            #[allow(unused_variables)]
+            fn call_params(&self, args: &[Value]) -> Result<Res, Error> {
+                Ok(self($(args[$i].try_convert()?),*))
+            }
+        }
+    };
+}
+
+for_each_tuple!(impl_call_params);
+
+/// The parameters of a function wrapped with the [`method`] or [`function`]
+/// macros that does not have a dedicated Ruby arity.
+#[doc(hidden)]
+#[derive(Clone, Copy)]
+pub struct Params(ParamsKind);
+
+#[derive(Clone, Copy)]
+enum ParamsKind {
+    List(&'static str, ArgSpec),
+    Arity(usize),
+}
+
+impl Params {
+    // The number of arguments the parameters will be split up in to.
+    fn len(self) -> usize {
+        match self.0 {
+            ParamsKind::List(_, arg_spec) => arg_spec.len(),
+            ParamsKind::Arity(arity) => arity,
+        }
+    }
+
+    // Checks `args` against the parameters, writing one value per parameter
+    // to `out`.
+    fn scan(self, args: &[Value], out: &mut [Value]) -> Result<(), Error> {
+        match self.0 {
+            ParamsKind::List(_, arg_spec) => scan_params(args, arg_spec, out)?,
+            ParamsKind::Arity(arity) => {
+                if args.len() != arity {
+                    return Err(Error::new(
+                        exception::arg_error(),
+                        format!(
+                            "wrong number of arguments (given {}, expected {})",
+                            args.len(),
+                            arity
+                        ),
+                    ));
+                }
+                out[..arity].copy_from_slice(args);
+            }
         }
+        Ok(())
     }
 
-    #[inline]
-    unsafe fn call_convert_value(self, rb_self: Value) -> Result<Value, Error> {
-        (self.func)(rb_self.try_convert()?).into_return_value()
+    // Panics if the parameters don't describe `len` arguments.
+    fn check(self, len: usize, desc: &str) {
+        if self.len() != len {
+            panic!("{} does not match function taking {}", self, desc);
+        }
     }
+}
 
-    #[inline]
-    pub unsafe fn call_handle_error(self, rb_self: Value) -> Value {
-        let res =
-            match std::panic::catch_unwind(AssertUnwindSafe(|| self.call_convert_value(rb_self))) {
-                Ok(v) => v,
-                Err(e) => Err(Error::from_panic(e)),
-            };
-        match res {
-            Ok(v) => v,
-            Err(e) => raise(e),
+impl fmt::Display for Params {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            ParamsKind::List(list, _) => write!(f, "parameter list `{}`", list),
+            ParamsKind::Arity(arity) => write!(f, "arity {}", arity),
         }
     }
 }
 
-/// Helper type for wrapping a function as a Ruby method taking self and 1
-/// argument, with type conversions and error handling.
-///
-/// See the [`method`] macro.
+impl From<&'static str> for Params {
+    fn from(val: &'static str) -> Self {
+        Self(ParamsKind::List(val, ArgSpec::parse(val)))
+    }
+}
+
+impl From<usize> for Params {
+    fn from(val: usize) -> Self {
+        Self(ParamsKind::Arity(val))
+    }
+}
+
+/// Storage for the [`Params`] of a function wrapped with the [`method`] or
+/// [`function`] macros, parsed once as the method is defined.
 #[doc(hidden)]
-pub struct Method1<Func, RbSelf, A, Res> {
-    func: Func,
-    rb_self: PhantomData<RbSelf>,
-    a: PhantomData<A>,
-    res: PhantomData<Res>,
+pub struct ParamsCell {
+    init: Once,
+    params: UnsafeCell<Option<Params>>,
 }
 
+// only written once, guarded by `init`
+unsafe impl Sync for ParamsCell {}
+
 #[allow(missing_docs)]
-impl<Func, RbSelf, A, Res> Method1<Func, RbSelf, A, Res>
-where
-    Func: Fn(RbSelf, A) -> Res,
-    RbSelf: TryConvert,
-    A: TryConvert,
-    Res: ReturnValue,
-{
-    #[inline]
-    pub fn new(func: Func) -> Self {
+impl ParamsCell {
+    #[allow(clippy::new_without_default)]
+    pub const fn new() -> Self {
         Self {
-            func,
-            rb_self: Default::default(),
-            a: Default::default(),
-            res: Default::default(),
+            init: Once::new(),
+            params: UnsafeCell::new(None),
         }
     }
 
-    #[inline]
-    unsafe fn call_convert_value(self, rb_self: Value, a: Value) -> Result<Value, Error> {
-        (self.func)(rb_self.try_convert()?, a.try_convert()?).into_return_value()
+    pub fn set(&self, params: Params) {
+        self.init
+            .call_once(|| unsafe { *self.params.get() = Some(params) });
     }
 
     #[inline]
-    pub unsafe fn call_handle_error(self, rb_self: Value, a: Value) -> Value {
-        let res = match std::panic::catch_unwind(AssertUnwindSafe(|| {
-            self.call_convert_value(rb_self, a)
-        })) {
-            Ok(v) => v,
-            Err(e) => Err(Error::from_panic(e)),
-        };
-        match res {
-            Ok(v) => v,
-            Err(e) => raise(e),
-        }
+    pub fn get(&self) -> Params {
+        assert!(
+            self.init.is_completed(),
+            "method called before being defined"
+        );
+        unsafe { (*self.params.get()).unwrap() }
     }
 }
 
-/// Helper type for wrapping a function as a Ruby method taking self and 2
-/// arguments, with type conversions and error handling.
+/// Helper type for wrapping a function as a Ruby method taking self and
+/// arguments described by [`Params`], with type conversions and error
+/// handling.
 ///
 /// See the [`method`] macro.
 #[doc(hidden)]
-pub struct Method2<Func, RbSelf, A, B, Res> {
+pub struct MethodParams<Func, Args, Res> {
     func: Func,
-    rb_self: PhantomData<RbSelf>,
-    a: PhantomData<A>,
-    b: PhantomData<B>,
+    params: Params,
+    args: PhantomData<Args>,
     res: PhantomData<Res>,
 }
 
 #[allow(missing_docs)]
-impl<Func, RbSelf, A, B, Res> Method2<Func, RbSelf, A, B, Res>
+impl<Func, Args, Res> MethodParams<Func, Args, Res>
 where
-    Func: Fn(RbSelf, A, B) -> Res,
-    RbSelf: TryConvert,
-    A: TryConvert,
-    B: TryConvert,
+    Func: CallParams<Args, Res>,
     Res: ReturnValue,
 {
     #[inline]
-    pub fn new(func: Func) -> Self {
+    pub fn new(func: Func, params: Params) -> Self {
         Self {
             func,
-            rb_self: Default::default(),
-            a: Default::default(),
-            b: Default::default(),
+            params,
+            args: Default::default(),
             res: Default::default(),
         }
     }
 
+    // Parses `params`, panicking if they don't match `func`. Used by the
+    // `method` macro when the method is defined, rather than on every call.
+    pub fn params<P>(_func: &Func, params: P) -> Params
+    where
+        P: Into<Params>,
+    {
+        let params = params.into();
+        params.check(
+            Func::LEN.saturating_sub(1),
+            &format!("self and {} arguments", Func::LEN.saturating_sub(1)),
+        );
+        params
+    }
+
     #[inline]
-    unsafe fn call_convert_value(self, rb_self: Value, a: Value, b: Value) -> Result<Value, Error> {
-        (self.func)(rb_self.try_convert()?, a.try_convert()?, b.try_convert()?).into_return_value()
+    unsafe fn call_convert_value(
+        self,
+        argc: c_int,
+        argv: *const Value,
+        rb_self: Value,
+    ) -> Result<Value, Error> {
+        let _scope = BorrowScope::new();
+        let args = slice::from_raw_parts(argv, argc as usize);
+        // kept on the stack so any values created by scanning are visible to
+        // the GC
+        let mut buf = [*QNIL; 33];
+        buf[0] = rb_self;
+        self.params.scan(args, &mut buf[1..])?;
+        self.func
+            .call_params(&buf[..Func::LEN])?
+            .into_return_value()
     }
 
     #[inline]
-    pub unsafe fn call_handle_error(self, rb_self: Value, a: Value, b: Value) -> Value {
+    pub unsafe fn call_handle_error(
+        self,
+        argc: c_int,
+        argv: *const Value,
+        rb_self: Value,
+    ) -> Value {
         let res = match std::panic::catch_unwind(AssertUnwindSafe(|| {
-            self.call_convert_value(rb_self, a, b)
+            self.call_convert_value(argc, argv, rb_self)
         })) {
             Ok(v) => v,
             Err(e) => Err(Error::from_panic(e)),
@@ -942,64 +759,42 @@ where
     }
 }
 
-/// Helper type for wrapping a function as a Ruby method taking self and 3
-/// arguments, with type conversions and error handling.
+/// Helper type for wrapping a function as a Ruby method taking self and a
+/// fixed number of arguments, with type conversions and error handling.
 ///
 /// See the [`method`] macro.
 #[doc(hidden)]
-pub struct Method3<Func, RbSelf, A, B, C, Res> {
+pub struct MethodN<Func, Args, Res> {
     func: Func,
-    rb_self: PhantomData<RbSelf>,
-    a: PhantomData<A>,
-    b: PhantomData<B>,
-    c: PhantomData<C>,
+    args: PhantomData<Args>,
     res: PhantomData<Res>,
 }
 
 #[allow(missing_docs)]
-impl<Func, RbSelf, A, B, C, Res> Method3<Func, RbSelf, A, B, C, Res>
+impl<Func, Args, Res> MethodN<Func, Args, Res>
 where
-    Func: Fn(RbSelf, A, B, C) -> Res,
-    RbSelf: TryConvert,
-    A: TryConvert,
-    B: TryConvert,
-    C: TryConvert,
+    Func: CallParams<Args, Res>,
     Res: ReturnValue,
 {
     #[inline]
     pub fn new(func: Func) -> Self {
         Self {
             func,
-            rb_self: Default::default(),
-            a: Default::default(),
-            b: Default::default(),
-            c: Default::default(),
+            args: Default::default(),
             res: Default::default(),
         }
     }
 
     #[inline]
-    unsafe fn call_convert_value(
-        self,
-        rb_self: Value,
-        a: Value,
-        b: Value,
-        c: Value,
-    ) -> Result<Value, Error> {
-        (self.func)(
-            rb_self.try_convert()?,
-            a.try_convert()?,
-            b.try_convert()?,
-            c.try_convert()?,
-        )
-        .into_return_value()
+    unsafe fn call_convert_value(self, args: Func::Values) -> Result<Value, Error> {
+        let _scope = BorrowScope::new();
+        self.func.call_params(args.as_ref())?.into_return_value()
     }
 
     #[inline]
-    pub unsafe fn call_handle_error(self, rb_self: Value, a: Value, b: Value, c: Value) -> Value {
-        let res = match std::panic::catch_unwind(AssertUnwindSafe(|| {
-            self.call_convert_value(rb_self, a, b, c)
-        })) {
+    pub unsafe fn call_handle_error(self, args: Func::Values) -> Value {
+        let res = match std::panic::catch_unwind(AssertUnwindSafe(|| self.call_convert_value(args)))
+        {
             Ok(v) => v,
             Err(e) => Err(Error::from_panic(e)),
         };
@@ -1010,76 +805,235 @@ where
     }
 }
 
-/// Helper type for wrapping a function as a Ruby method taking self and 4
-/// arguments, with type conversions and error handling.
+/// Wrap a Rust function item with Ruby type conversion and error handling.
 ///
-/// See the [`method`] macro.
+/// This macro wraps the given function and returns a function pointer
+/// implementing the [`Method`] trait, suitable for passing to functions that
+/// define Ruby methods such as
+/// [`define_method`](crate::module::Module::define_method).
+///
+/// Ruby code implicitly always has a `self` parameter available. In the
+/// extention API this is passed explicitly. As a result there is always an
+/// extra `self` argument before the arguments explitly passed in Ruby, and the
+/// number of Rust argument will be one more than the Ruby arity.
+///
+/// The values `-2` and `-1` for `arity` have special meaning. Both indicate
+/// functions with any number of arguments, with `-2` the arguments are passed
+/// as a [`RArray`], with `-1` they are passed as a slice of [`Value`]s.
+/// Arity of `-1` can be used with [`scan_args`](crate::scan_args::scan_args)
+/// and [`get_kwargs`](crate::scan_args::get_kwargs) for more complex method
+/// signatures.
+///
+/// | Arity | Signature                                                 |
+/// |-------|-----------------------------------------------------------|
+/// |    -2 | `fn(rb_self: T, arguments: RArray) -> Result<R, Error>`   |
+/// |    -1 | `fn(rb_self: T, arguments: &[Value]) -> Result<R, Error>` |
+/// |     0 | `fn(rb_self: T) -> Result<R, Error>`                      |
+/// |     1 | `fn(rb_self: T, arg1: U) -> Result<R, Error>`             |
+/// |     2 | `fn(rb_self: T, arg1: U, arg2: V) -> Result<R, Error>`    |
+/// |   ... | ...                                                       |
+/// |    15 | ...                                                       |
+///
+/// Ruby limits methods to an arity of 15. Methods with a greater arity, up to
+/// 31, are defined with an arity of `-1` and the number of arguments checked
+/// when called. As the arity can't be checked against the function's
+/// signature at compile time, evaluating the macro panics if they differ.
+///
+/// Where `T`, `U`, `V` and so on are any types that implement `TryConvert`,
+/// and `R` implements `Into<Value>`. It is also possible to return just `R`
+/// rather than a `Result` for functions that will never error, and omit the
+/// return value (i.e. return `()`) for a function that returns `nil` to Ruby.
+/// See [`ReturnValue`] for more details on what can be returned.
+///
+/// In place of `arity` a string of Ruby-style parameters can be given, such
+/// as `"a, b = nil, *rest, c, **kwargs, &block"`. The arguments are checked
+/// and split up as with [`scan_args`](crate::scan_args::scan_args), raising
+/// the same `ArgumentError` as Ruby for the wrong number of arguments, and the
+/// wrapped function receives one argument per parameter after `self`.
+/// Optional parameters that weren't given are passed as `nil`, so will often
+/// be `Option<T>`, the splat is passed as a [`RArray`], keywords as a
+/// [`RHash`](crate::RHash) (which is empty if no keywords were given), and
+/// the block as a [`Proc`](crate::block::Proc) or `Option<Proc>`. Parameter
+/// names and default values are ignored, and individual keywords (`a:`) are
+/// not supported. The parameter list is parsed once when the macro is
+/// evaluated, which panics if it is invalid or doesn't match the number of
+/// arguments the function takes.
+///
+/// For types wrapped with [`TypedData`](crate::TypedData) `T` can be `&Type`
+/// or `&mut Type`. Borrows are tracked for the duration of the method call,
+/// and a conflicting borrow (e.g. the method re-entered with the same object
+/// via a block) will raise a `RuntimeError`.
+///
+/// See the [`function`] macro for cases where there is no need to handle the
+/// `self` argument.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{define_class, method, prelude::*, Error};
+///
+/// fn rb_is_blank(rb_self: String) -> bool {
+///     rb_self.contains(|c: char| !c.is_whitespace())
+/// }
+///
+/// #[magnus::init]
+/// fn init() -> Result<(), Error> {
+///     let class = define_class("String", Default::default())?;
+///     class.define_method("blank?", method!(rb_is_blank, 0));
+///     Ok(())
+/// }
+/// ```
+///
+/// With a parameter list:
+///
+/// ```
+/// use magnus::{define_class, method, prelude::*, Error};
+///
+/// fn rb_pad(rb_self: String, width: usize, fill: Option<char>) -> String {
+///     let fill = fill.unwrap_or(' ');
+///     let mut s = rb_self;
+///     while s.chars().count() < width {
+///         s.push(fill);
+///     }
+///     s
+/// }
+///
+/// #[magnus::init]
+/// fn init() -> Result<(), Error> {
+///     let class = define_class("String", Default::default())?;
+///     class.define_method("pad", method!(rb_pad, "width, fill = nil"));
+///     Ok(())
+/// }
+/// ```
+#[macro_export]
+macro_rules! method {
+    ($name:expr, -2) => {{
+        unsafe extern "C" fn anon(rb_self: $crate::Value, args: $crate::RArray) -> $crate::Value {
+            $crate::method::MethodRbAry::new($name).call_handle_error(rb_self, args)
+        }
+        anon as unsafe extern "C" fn($crate::Value, $crate::RArray) -> $crate::Value
+    }};
+    ($name:expr, -1) => {{
+        unsafe extern "C" fn anon(
+            argc: std::os::raw::c_int,
+            argv: *const $crate::Value,
+            rb_self: $crate::Value,
+        ) -> $crate::Value {
+            $crate::method::MethodCAry::new($name).call_handle_error(argc, argv, rb_self)
+        }
+        anon as unsafe extern "C" fn(
+            std::os::raw::c_int,
+            *const $crate::Value,
+            $crate::Value,
+        ) -> $crate::Value
+    }};
+    ($name:expr, 0) => {
+        $crate::__fixed_arity!(@method $name)
+    };
+    ($name:expr, 1) => {
+        $crate::__fixed_arity!(@method $name, a)
+    };
+    ($name:expr, 2) => {
+        $crate::__fixed_arity!(@method $name, a, b)
+    };
+    ($name:expr, 3) => {
+        $crate::__fixed_arity!(@method $name, a, b, c)
+    };
+    ($name:expr, 4) => {
+        $crate::__fixed_arity!(@method $name, a, b, c, d)
+    };
+    ($name:expr, 5) => {
+        $crate::__fixed_arity!(@method $name, a, b, c, d, e)
+    };
+    ($name:expr, 6) => {
+        $crate::__fixed_arity!(@method $name, a, b, c, d, e, f)
+    };
+    ($name:expr, 7) => {
+        $crate::__fixed_arity!(@method $name, a, b, c, d, e, f, g)
+    };
+    ($name:expr, 8) => {
+        $crate::__fixed_arity!(@method $name, a, b, c, d, e, f, g, h)
+    };
+    ($name:expr, 9) => {
+        $crate::__fixed_arity!(@method $name, a, b, c, d, e, f, g, h, i)
+    };
+    ($name:expr, 10) => {
+        $crate::__fixed_arity!(@method $name, a, b, c, d, e, f, g, h, i, j)
+    };
+    ($name:expr, 11) => {
+        $crate::__fixed_arity!(@method $name, a, b, c, d, e, f, g, h, i, j, k)
+    };
+    ($name:expr, 12) => {
+        $crate::__fixed_arity!(@method $name, a, b, c, d, e, f, g, h, i, j, k, l)
+    };
+    ($name:expr, 13) => {
+        $crate::__fixed_arity!(@method $name, a, b, c, d, e, f, g, h, i, j, k, l, m)
+    };
+    ($name:expr, 14) => {
+        $crate::__fixed_arity!(@method $name, a, b, c, d, e, f, g, h, i, j, k, l, m, n)
+    };
+    ($name:expr, 15) => {
+        $crate::__fixed_arity!(@method $name, a, b, c, d, e, f, g, h, i, j, k, l, m, n, o)
+    };
+    ($name:expr, $params:literal) => {{
+        static PARAMS: $crate::method::ParamsCell = $crate::method::ParamsCell::new();
+        unsafe extern "C" fn anon(
+            argc: std::os::raw::c_int,
+            argv: *const $crate::Value,
+            rb_self: $crate::Value,
+        ) -> $crate::Value {
+            $crate::method::MethodParams::new($name, PARAMS.get())
+                .call_handle_error(argc, argv, rb_self)
+        }
+        PARAMS.set($crate::method::MethodParams::params(&$name, $params));
+        anon as unsafe extern "C" fn(
+            std::os::raw::c_int,
+            *const $crate::Value,
+            $crate::Value,
+        ) -> $crate::Value
+    }};
+    ($name:expr, $arity:expr) => {
+        compile_error!("arity must be an integer literal of -2 or greater, or a parameter list")
+    };
+}
+
+/// Helper type for wrapping a function as a Ruby method ignoring self and
+/// taking a Ruby array of arguments, with type conversions and error handling.
+///
+/// See the [`function`] macro.
 #[doc(hidden)]
-pub struct Method4<Func, RbSelf, A, B, C, D, Res> {
+pub struct FunctionRbAry<Func, Args, Res> {
     func: Func,
-    rb_self: PhantomData<RbSelf>,
-    a: PhantomData<A>,
-    b: PhantomData<B>,
-    c: PhantomData<C>,
-    d: PhantomData<D>,
+    args: PhantomData<Args>,
     res: PhantomData<Res>,
 }
 
 #[allow(missing_docs)]
-impl<Func, RbSelf, A, B, C, D, Res> Method4<Func, RbSelf, A, B, C, D, Res>
+impl<Func, Args, Res> FunctionRbAry<Func, Args, Res>
 where
-    Func: Fn(RbSelf, A, B, C, D) -> Res,
-    RbSelf: TryConvert,
-    A: TryConvert,
-    B: TryConvert,
-    C: TryConvert,
-    D: TryConvert,
+    Func: Fn(Args) -> Res,
+    Args: TryConvert,
     Res: ReturnValue,
 {
     #[inline]
     pub fn new(func: Func) -> Self {
         Self {
             func,
-            rb_self: Default::default(),
-            a: Default::default(),
-            b: Default::default(),
-            c: Default::default(),
-            d: Default::default(),
+            args: Default::default(),
             res: Default::default(),
         }
     }
 
     #[inline]
-    unsafe fn call_convert_value(
-        self,
-        rb_self: Value,
-        a: Value,
-        b: Value,
-        c: Value,
-        d: Value,
-    ) -> Result<Value, Error> {
-        (self.func)(
-            rb_self.try_convert()?,
-            a.try_convert()?,
-            b.try_convert()?,
-            c.try_convert()?,
-            d.try_convert()?,
-        )
-        .into_return_value()
+    unsafe fn call_convert_value(self, args: RArray) -> Result<Value, Error> {
+        let _scope = BorrowScope::new();
+        (self.func)(args.try_convert()?).into_return_value()
     }
 
     #[inline]
-    pub unsafe fn call_handle_error(
-        self,
-        rb_self: Value,
-        a: Value,
-        b: Value,
-        c: Value,
-        d: Value,
-    ) -> Value {
-        let res = match std::panic::catch_unwind(AssertUnwindSafe(|| {
-            self.call_convert_value(rb_self, a, b, c, d)
-        })) {
+    pub unsafe fn call_handle_error(self, args: RArray) -> Value {
+        let res = match std::panic::catch_unwind(AssertUnwindSafe(|| self.call_convert_value(args)))
+        {
             Ok(v) => v,
             Err(e) => Err(Error::from_panic(e)),
         };
@@ -1090,81 +1044,41 @@ where
     }
 }
 
-/// Helper type for wrapping a function as a Ruby method taking self and 5
-/// arguments, with type conversions and error handling.
+/// Helper type for wrapping a function as a Ruby method ignoring self and
+/// taking a slice of arguments, with type conversions and error handling.
 ///
-/// See the [`method`] macro.
+/// See the [`function`] macro.
 #[doc(hidden)]
-pub struct Method5<Func, RbSelf, A, B, C, D, E, Res> {
+pub struct FunctionCAry<Func, Res> {
     func: Func,
-    rb_self: PhantomData<RbSelf>,
-    a: PhantomData<A>,
-    b: PhantomData<B>,
-    c: PhantomData<C>,
-    d: PhantomData<D>,
-    e: PhantomData<E>,
     res: PhantomData<Res>,
 }
 
 #[allow(missing_docs)]
-impl<Func, RbSelf, A, B, C, D, E, Res> Method5<Func, RbSelf, A, B, C, D, E, Res>
+impl<Func, Res> FunctionCAry<Func, Res>
 where
-    Func: Fn(RbSelf, A, B, C, D, E) -> Res,
-    RbSelf: TryConvert,
-    A: TryConvert,
-    B: TryConvert,
-    C: TryConvert,
-    D: TryConvert,
-    E: TryConvert,
+    Func: Fn(&[Value]) -> Res,
     Res: ReturnValue,
 {
     #[inline]
     pub fn new(func: Func) -> Self {
         Self {
             func,
-            rb_self: Default::default(),
-            a: Default::default(),
-            b: Default::default(),
-            c: Default::default(),
-            d: Default::default(),
-            e: Default::default(),
             res: Default::default(),
         }
     }
 
     #[inline]
-    unsafe fn call_convert_value(
-        self,
-        rb_self: Value,
-        a: Value,
-        b: Value,
-        c: Value,
-        d: Value,
-        e: Value,
-    ) -> Result<Value, Error> {
-        (self.func)(
-            rb_self.try_convert()?,
-            a.try_convert()?,
-            b.try_convert()?,
-            c.try_convert()?,
-            d.try_convert()?,
-            e.try_convert()?,
-        )
-        .into_return_value()
+    unsafe fn call_convert_value(self, argc: c_int, argv: *const Value) -> Result<Value, Error> {
+        let _scope = BorrowScope::new();
+        let args = slice::from_raw_parts(argv, argc as usize);
+        (self.func)(args).into_return_value()
     }
 
     #[inline]
-    pub unsafe fn call_handle_error(
-        self,
-        rb_self: Value,
-        a: Value,
-        b: Value,
-        c: Value,
-        d: Value,
-        e: Value,
-    ) -> Value {
+    pub unsafe fn call_handle_error(self, argc: c_int, argv: *const Value) -> Value {
         let res = match std::panic::catch_unwind(AssertUnwindSafe(|| {
-            self.call_convert_value(rb_self, a, b, c, d, e)
+            self.call_convert_value(argc, argv)
         })) {
             Ok(v) => v,
             Err(e) => Err(Error::from_panic(e)),
@@ -1176,87 +1090,64 @@ where
     }
 }
 
-/// Helper type for wrapping a function as a Ruby method taking self and 6
-/// arguments, with type conversions and error handling.
+/// Helper type for wrapping a function as a Ruby method ignoring self and
+/// taking arguments described by [`Params`], with type conversions and error
+/// handling.
 ///
-/// See the [`method`] macro.
+/// See the [`function`] macro.
 #[doc(hidden)]
-pub struct Method6<Func, RbSelf, A, B, C, D, E, F, Res> {
+pub struct FunctionParams<Func, Args, Res> {
     func: Func,
-    rb_self: PhantomData<RbSelf>,
-    a: PhantomData<A>,
-    b: PhantomData<B>,
-    c: PhantomData<C>,
-    d: PhantomData<D>,
-    e: PhantomData<E>,
-    f: PhantomData<F>,
+    params: Params,
+    args: PhantomData<Args>,
     res: PhantomData<Res>,
 }
 
 #[allow(missing_docs)]
-impl<Func, RbSelf, A, B, C, D, E, F, Res> Method6<Func, RbSelf, A, B, C, D, E, F, Res>
+impl<Func, Args, Res> FunctionParams<Func, Args, Res>
 where
-    Func: Fn(RbSelf, A, B, C, D, E, F) -> Res,
-    RbSelf: TryConvert,
-    A: TryConvert,
-    B: TryConvert,
-    C: TryConvert,
-    D: TryConvert,
-    E: TryConvert,
-    F: TryConvert,
+    Func: CallParams<Args, Res>,
     Res: ReturnValue,
 {
     #[inline]
-    pub fn new(func: Func) -> Self {
+    pub fn new(func: Func, params: Params) -> Self {
         Self {
             func,
-            rb_self: Default::default(),
-            a: Default::default(),
-            b: Default::default(),
-            c: Default::default(),
-            d: Default::default(),
-            e: Default::default(),
-            f: Default::default(),
+            params,
+            args: Default::default(),
             res: Default::default(),
         }
     }
 
+    // Parses `params`, panicking if they don't match `func`. Used by the
+    // `function` macro when the function is defined, rather than on every
+    // call.
+    pub fn params<P>(_func: &Func, params: P) -> Params
+    where
+        P: Into<Params>,
+    {
+        let params = params.into();
+        params.check(Func::LEN, &format!("{} arguments", Func::LEN));
+        params
+    }
+
     #[inline]
-    unsafe fn call_convert_value(
-        self,
-        rb_self: Value,
-        a: Value,
-        b: Value,
-        c: Value,
-        d: Value,
-        e: Value,
-        f: Value,
-    ) -> Result<Value, Error> {
-        (self.func)(
-            rb_self.try_convert()?,
-            a.try_convert()?,
-            b.try_convert()?,
-            c.try_convert()?,
-            d.try_convert()?,
-            e.try_convert()?,
-            f.try_convert()?,
-        )
-        .into_return_value()
+    unsafe fn call_convert_value(self, argc: c_int, argv: *const Value) -> Result<Value, Error> {
+        let _scope = BorrowScope::new();
+        let args = slice::from_raw_parts(argv, argc as usize);
+        // kept on the stack so any values created by scanning are visible to
+        // the GC
+        let mut buf = [*QNIL; 32];
+        self.params.scan(args, &mut buf)?;
+        self.func
+            .call_params(&buf[..Func::LEN])?
+            .into_return_value()
     }
 
     #[inline]
-    pub unsafe fn call_handle_error(
-        self,
-        rb_self: Value,
-        a: Value,
-        b: Value,
-        c: Value,
-        d: Value,
-        e: Value,
-        f: Value,
-    ) -> Value {
+    pub unsafe fn call_handle_error(self, argc: c_int, argv: *const Value) -> Value {
         let res = match std::panic::catch_unwind(AssertUnwindSafe(|| {
-            self.call_convert_value(rb_self, a, b, c, d, e, f)
+            self.call_convert_value(argc, argv)
         })) {
             Ok(v) => v,
             Err(e) => Err(Error::from_panic(e)),
@@ -1268,94 +1159,42 @@ where
     }
 }
 
-/// Helper type for wrapping a function as a Ruby method taking self and 7
-/// arguments, with type conversions and error handling.
+/// Helper type for wrapping a function as a Ruby method ignoring self and
+/// taking a fixed number of arguments, with type conversions and error handling.
 ///
-/// See the [`method`] macro.
+/// See the [`function`] macro.
 #[doc(hidden)]
-pub struct Method7<Func, RbSelf, A, B, C, D, E, F, G, Res> {
+pub struct FunctionN<Func, Args, Res> {
     func: Func,
-    rb_self: PhantomData<RbSelf>,
-    a: PhantomData<A>,
-    b: PhantomData<B>,
-    c: PhantomData<C>,
-    d: PhantomData<D>,
-    e: PhantomData<E>,
-    f: PhantomData<F>,
-    g: PhantomData<G>,
+    args: PhantomData<Args>,
     res: PhantomData<Res>,
 }
 
 #[allow(missing_docs)]
-impl<Func, RbSelf, A, B, C, D, E, F, G, Res> Method7<Func, RbSelf, A, B, C, D, E, F, G, Res>
+impl<Func, Args, Res> FunctionN<Func, Args, Res>
 where
-    Func: Fn(RbSelf, A, B, C, D, E, F, G) -> Res,
-    RbSelf: TryConvert,
-    A: TryConvert,
-    B: TryConvert,
-    C: TryConvert,
-    D: TryConvert,
-    E: TryConvert,
-    F: TryConvert,
-    G: TryConvert,
+    Func: CallParams<Args, Res>,
     Res: ReturnValue,
 {
     #[inline]
     pub fn new(func: Func) -> Self {
         Self {
             func,
-            rb_self: Default::default(),
-            a: Default::default(),
-            b: Default::default(),
-            c: Default::default(),
-            d: Default::default(),
-            e: Default::default(),
-            f: Default::default(),
-            g: Default::default(),
+            args: Default::default(),
             res: Default::default(),
         }
     }
 
     #[inline]
-    unsafe fn call_convert_value(
-        self,
-        rb_self: Value,
-        a: Value,
-        b: Value,
-        c: Value,
-        d: Value,
-        e: Value,
-        f: Value,
-        g: Value,
-    ) -> Result<Value, Error> {
-        (self.func)(
-            rb_self.try_convert()?,
-            a.try_convert()?,
-            b.try_convert()?,
-            c.try_convert()?,
-            d.try_convert()?,
-            e.try_convert()?,
-            f.try_convert()?,
-            g.try_convert()?,
-        )
-        .into_return_value()
+    unsafe fn call_convert_value(self, args: Func::Values) -> Result<Value, Error> {
+        let _scope = BorrowScope::new();
+        self.func.call_params(args.as_ref())?.into_return_value()
     }
 
     #[inline]
-    pub unsafe fn call_handle_error(
-        self,
-        rb_self: Value,
-        a: Value,
-        b: Value,
-        c: Value,
-        d: Value,
-        e: Value,
-        f: Value,
-        g: Value,
-    ) -> Value {
-        let res = match std::panic::catch_unwind(AssertUnwindSafe(|| {
-            self.call_convert_value(rb_self, a, b, c, d, e, f, g)
-        })) {
+    pub unsafe fn call_handle_error(self, args: Func::Values) -> Value {
+        let res = match std::panic::catch_unwind(AssertUnwindSafe(|| self.call_convert_value(args)))
+        {
             Ok(v) => v,
             Err(e) => Err(Error::from_panic(e)),
         };
@@ -1366,3899 +1205,194 @@ where
     }
 }
 
-/// Helper type for wrapping a function as a Ruby method taking self and 8
-/// arguments, with type conversions and error handling.
+/// Wrap a Rust function item with Ruby type conversion and error handling,
+/// ignoring Ruby's `self` argument.
 ///
-/// See the [`method`] macro.
-#[doc(hidden)]
-pub struct Method8<Func, RbSelf, A, B, C, D, E, F, G, H, Res> {
-    func: Func,
-    rb_self: PhantomData<RbSelf>,
-    a: PhantomData<A>,
-    b: PhantomData<B>,
-    c: PhantomData<C>,
-    d: PhantomData<D>,
-    e: PhantomData<E>,
-    f: PhantomData<F>,
-    g: PhantomData<G>,
-    h: PhantomData<H>,
-    res: PhantomData<Res>,
-}
-
-#[allow(missing_docs)]
-impl<Func, RbSelf, A, B, C, D, E, F, G, H, Res> Method8<Func, RbSelf, A, B, C, D, E, F, G, H, Res>
-where
-    Func: Fn(RbSelf, A, B, C, D, E, F, G, H) -> Res,
-    RbSelf: TryConvert,
-    A: TryConvert,
-    B: TryConvert,
-    C: TryConvert,
-    D: TryConvert,
-    E: TryConvert,
-    F: TryConvert,
-    G: TryConvert,
-    H: TryConvert,
-    Res: ReturnValue,
-{
-    #[inline]
-    pub fn new(func: Func) -> Self {
-        Self {
-            func,
-            rb_self: Default::default(),
-            a: Default::default(),
-            b: Default::default(),
-            c: Default::default(),
-            d: Default::default(),
-            e: Default::default(),
-            f: Default::default(),
-            g: Default::default(),
-            h: Default::default(),
-            res: Default::default(),
-        }
-    }
-
-    #[inline]
-    unsafe fn call_convert_value(
-        self,
-        rb_self: Value,
-        a: Value,
-        b: Value,
-        c: Value,
-        d: Value,
-        e: Value,
-        f: Value,
-        g: Value,
-        h: Value,
-    ) -> Result<Value, Error> {
-        (self.func)(
-            rb_self.try_convert()?,
-            a.try_convert()?,
-            b.try_convert()?,
-            c.try_convert()?,
-            d.try_convert()?,
-            e.try_convert()?,
-            f.try_convert()?,
-            g.try_convert()?,
-            h.try_convert()?,
-        )
-        .into_return_value()
-    }
-
-    #[inline]
-    pub unsafe fn call_handle_error(
-        self,
-        rb_self: Value,
-        a: Value,
-        b: Value,
-        c: Value,
-        d: Value,
-        e: Value,
-        f: Value,
-        g: Value,
-        h: Value,
-    ) -> Value {
-        let res = match std::panic::catch_unwind(AssertUnwindSafe(|| {
-            self.call_convert_value(rb_self, a, b, c, d, e, f, g, h)
-        })) {
-            Ok(v) => v,
-            Err(e) => Err(Error::from_panic(e)),
-        };
-        match res {
-            Ok(v) => v,
-            Err(e) => raise(e),
-        }
-    }
-}
-
-/// Helper type for wrapping a function as a Ruby method taking self and 9
-/// arguments, with type conversions and error handling.
-///
-/// See the [`method`] macro.
-#[doc(hidden)]
-pub struct Method9<Func, RbSelf, A, B, C, D, E, F, G, H, I, Res> {
-    func: Func,
-    rb_self: PhantomData<RbSelf>,
-    a: PhantomData<A>,
-    b: PhantomData<B>,
-    c: PhantomData<C>,
-    d: PhantomData<D>,
-    e: PhantomData<E>,
-    f: PhantomData<F>,
-    g: PhantomData<G>,
-    h: PhantomData<H>,
-    i: PhantomData<I>,
-    res: PhantomData<Res>,
-}
-
-#[allow(missing_docs)]
-impl<Func, RbSelf, A, B, C, D, E, F, G, H, I, Res>
-    Method9<Func, RbSelf, A, B, C, D, E, F, G, H, I, Res>
-where
-    Func: Fn(RbSelf, A, B, C, D, E, F, G, H, I) -> Res,
-    RbSelf: TryConvert,
-    A: TryConvert,
-    B: TryConvert,
-    C: TryConvert,
-    D: TryConvert,
-    E: TryConvert,
-    F: TryConvert,
-    G: TryConvert,
-    H: TryConvert,
-    I: TryConvert,
-    Res: ReturnValue,
-{
-    #[inline]
-    pub fn new(func: Func) -> Self {
-        Self {
-            func,
-            rb_self: Default::default(),
-            a: Default::default(),
-            b: Default::default(),
-            c: Default::default(),
-            d: Default::default(),
-            e: Default::default(),
-            f: Default::default(),
-            g: Default::default(),
-            h: Default::default(),
-            i: Default::default(),
-            res: Default::default(),
-        }
-    }
-
-    #[inline]
-    unsafe fn call_convert_value(
-        self,
-        rb_self: Value,
-        a: Value,
-        b: Value,
-        c: Value,
-        d: Value,
-        e: Value,
-        f: Value,
-        g: Value,
-        h: Value,
-        i: Value,
-    ) -> Result<Value, Error> {
-        (self.func)(
-            rb_self.try_convert()?,
-            a.try_convert()?,
-            b.try_convert()?,
-            c.try_convert()?,
-            d.try_convert()?,
-            e.try_convert()?,
-            f.try_convert()?,
-            g.try_convert()?,
-            h.try_convert()?,
-            i.try_convert()?,
-        )
-        .into_return_value()
-    }
-
-    #[inline]
-    pub unsafe fn call_handle_error(
-        self,
-        rb_self: Value,
-        a: Value,
-        b: Value,
-        c: Value,
-        d: Value,
-        e: Value,
-        f: Value,
-        g: Value,
-        h: Value,
-        i: Value,
-    ) -> Value {
-        let res = match std::panic::catch_unwind(AssertUnwindSafe(|| {
-            self.call_convert_value(rb_self, a, b, c, d, e, f, g, h, i)
-        })) {
-            Ok(v) => v,
-            Err(e) => Err(Error::from_panic(e)),
-        };
-        match res {
-            Ok(v) => v,
-            Err(e) => raise(e),
-        }
-    }
-}
-
-/// Helper type for wrapping a function as a Ruby method taking self and 10
-/// arguments, with type conversions and error handling.
-///
-/// See the [`method`] macro.
-#[doc(hidden)]
-pub struct Method10<Func, RbSelf, A, B, C, D, E, F, G, H, I, J, Res> {
-    func: Func,
-    rb_self: PhantomData<RbSelf>,
-    a: PhantomData<A>,
-    b: PhantomData<B>,
-    c: PhantomData<C>,
-    d: PhantomData<D>,
-    e: PhantomData<E>,
-    f: PhantomData<F>,
-    g: PhantomData<G>,
-    h: PhantomData<H>,
-    i: PhantomData<I>,
-    j: PhantomData<J>,
-    res: PhantomData<Res>,
-}
-
-#[allow(missing_docs)]
-impl<Func, RbSelf, A, B, C, D, E, F, G, H, I, J, Res>
-    Method10<Func, RbSelf, A, B, C, D, E, F, G, H, I, J, Res>
-where
-    Func: Fn(RbSelf, A, B, C, D, E, F, G, H, I, J) -> Res,
-    RbSelf: TryConvert,
-    A: TryConvert,
-    B: TryConvert,
-    C: TryConvert,
-    D: TryConvert,
-    E: TryConvert,
-    F: TryConvert,
-    G: TryConvert,
-    H: TryConvert,
-    I: TryConvert,
-    J: TryConvert,
-    Res: ReturnValue,
-{
-    #[inline]
-    pub fn new(func: Func) -> Self {
-        Self {
-            func,
-            rb_self: Default::default(),
-            a: Default::default(),
-            b: Default::default(),
-            c: Default::default(),
-            d: Default::default(),
-            e: Default::default(),
-            f: Default::default(),
-            g: Default::default(),
-            h: Default::default(),
-            i: Default::default(),
-            j: Default::default(),
-            res: Default::default(),
-        }
-    }
-
-    #[inline]
-    unsafe fn call_convert_value(
-        self,
-        rb_self: Value,
-        a: Value,
-        b: Value,
-        c: Value,
-        d: Value,
-        e: Value,
-        f: Value,
-        g: Value,
-        h: Value,
-        i: Value,
-        j: Value,
-    ) -> Result<Value, Error> {
-        (self.func)(
-            rb_self.try_convert()?,
-            a.try_convert()?,
-            b.try_convert()?,
-            c.try_convert()?,
-            d.try_convert()?,
-            e.try_convert()?,
-            f.try_convert()?,
-            g.try_convert()?,
-            h.try_convert()?,
-            i.try_convert()?,
-            j.try_convert()?,
-        )
-        .into_return_value()
-    }
-
-    #[inline]
-    pub unsafe fn call_handle_error(
-        self,
-        rb_self: Value,
-        a: Value,
-        b: Value,
-        c: Value,
-        d: Value,
-        e: Value,
-        f: Value,
-        g: Value,
-        h: Value,
-        i: Value,
-        j: Value,
-    ) -> Value {
-        let res = match std::panic::catch_unwind(AssertUnwindSafe(|| {
-            self.call_convert_value(rb_self, a, b, c, d, e, f, g, h, i, j)
-        })) {
-            Ok(v) => v,
-            Err(e) => Err(Error::from_panic(e)),
-        };
-        match res {
-            Ok(v) => v,
-            Err(e) => raise(e),
-        }
-    }
-}
-
-/// Helper type for wrapping a function as a Ruby method taking self and 11
-/// arguments, with type conversions and error handling.
-///
-/// See the [`method`] macro.
-#[doc(hidden)]
-pub struct Method11<Func, RbSelf, A, B, C, D, E, F, G, H, I, J, K, Res> {
-    func: Func,
-    rb_self: PhantomData<RbSelf>,
-    a: PhantomData<A>,
-    b: PhantomData<B>,
-    c: PhantomData<C>,
-    d: PhantomData<D>,
-    e: PhantomData<E>,
-    f: PhantomData<F>,
-    g: PhantomData<G>,
-    h: PhantomData<H>,
-    i: PhantomData<I>,
-    j: PhantomData<J>,
-    k: PhantomData<K>,
-    res: PhantomData<Res>,
-}
-
-#[allow(missing_docs)]
-impl<Func, RbSelf, A, B, C, D, E, F, G, H, I, J, K, Res>
-    Method11<Func, RbSelf, A, B, C, D, E, F, G, H, I, J, K, Res>
-where
-    Func: Fn(RbSelf, A, B, C, D, E, F, G, H, I, J, K) -> Res,
-    RbSelf: TryConvert,
-    A: TryConvert,
-    B: TryConvert,
-    C: TryConvert,
-    D: TryConvert,
-    E: TryConvert,
-    F: TryConvert,
-    G: TryConvert,
-    H: TryConvert,
-    I: TryConvert,
-    J: TryConvert,
-    K: TryConvert,
-    Res: ReturnValue,
-{
-    #[inline]
-    pub fn new(func: Func) -> Self {
-        Self {
-            func,
-            rb_self: Default::default(),
-            a: Default::default(),
-            b: Default::default(),
-            c: Default::default(),
-            d: Default::default(),
-            e: Default::default(),
-            f: Default::default(),
-            g: Default::default(),
-            h: Default::default(),
-            i: Default::default(),
-            j: Default::default(),
-            k: Default::default(),
-            res: Default::default(),
-        }
-    }
-
-    #[inline]
-    unsafe fn call_convert_value(
-        self,
-        rb_self: Value,
-        a: Value,
-        b: Value,
-        c: Value,
-        d: Value,
-        e: Value,
-        f: Value,
-        g: Value,
-        h: Value,
-        i: Value,
-        j: Value,
-        k: Value,
-    ) -> Result<Value, Error> {
-        (self.func)(
-            rb_self.try_convert()?,
-            a.try_convert()?,
-            b.try_convert()?,
-            c.try_convert()?,
-            d.try_convert()?,
-            e.try_convert()?,
-            f.try_convert()?,
-            g.try_convert()?,
-            h.try_convert()?,
-            i.try_convert()?,
-            j.try_convert()?,
-            k.try_convert()?,
-        )
-        .into_return_value()
-    }
-
-    #[inline]
-    pub unsafe fn call_handle_error(
-        self,
-        rb_self: Value,
-        a: Value,
-        b: Value,
-        c: Value,
-        d: Value,
-        e: Value,
-        f: Value,
-        g: Value,
-        h: Value,
-        i: Value,
-        j: Value,
-        k: Value,
-    ) -> Value {
-        let res = match std::panic::catch_unwind(AssertUnwindSafe(|| {
-            self.call_convert_value(rb_self, a, b, c, d, e, f, g, h, i, j, k)
-        })) {
-            Ok(v) => v,
-            Err(e) => Err(Error::from_panic(e)),
-        };
-        match res {
-            Ok(v) => v,
-            Err(e) => raise(e),
-        }
-    }
-}
-
-/// Helper type for wrapping a function as a Ruby method taking self and 12
-/// arguments, with type conversions and error handling.
-///
-/// See the [`method`] macro.
-#[doc(hidden)]
-pub struct Method12<Func, RbSelf, A, B, C, D, E, F, G, H, I, J, K, L, Res> {
-    func: Func,
-    rb_self: PhantomData<RbSelf>,
-    a: PhantomData<A>,
-    b: PhantomData<B>,
-    c: PhantomData<C>,
-    d: PhantomData<D>,
-    e: PhantomData<E>,
-    f: PhantomData<F>,
-    g: PhantomData<G>,
-    h: PhantomData<H>,
-    i: PhantomData<I>,
-    j: PhantomData<J>,
-    k: PhantomData<K>,
-    l: PhantomData<L>,
-    res: PhantomData<Res>,
-}
-
-#[allow(missing_docs)]
-impl<Func, RbSelf, A, B, C, D, E, F, G, H, I, J, K, L, Res>
-    Method12<Func, RbSelf, A, B, C, D, E, F, G, H, I, J, K, L, Res>
-where
-    Func: Fn(RbSelf, A, B, C, D, E, F, G, H, I, J, K, L) -> Res,
-    RbSelf: TryConvert,
-    A: TryConvert,
-    B: TryConvert,
-    C: TryConvert,
-    D: TryConvert,
-    E: TryConvert,
-    F: TryConvert,
-    G: TryConvert,
-    H: TryConvert,
-    I: TryConvert,
-    J: TryConvert,
-    K: TryConvert,
-    L: TryConvert,
-    Res: ReturnValue,
-{
-    #[inline]
-    pub fn new(func: Func) -> Self {
-        Self {
-            func,
-            rb_self: Default::default(),
-            a: Default::default(),
-            b: Default::default(),
-            c: Default::default(),
-            d: Default::default(),
-            e: Default::default(),
-            f: Default::default(),
-            g: Default::default(),
-            h: Default::default(),
-            i: Default::default(),
-            j: Default::default(),
-            k: Default::default(),
-            l: Default::default(),
-            res: Default::default(),
-        }
-    }
-
-    #[inline]
-    unsafe fn call_convert_value(
-        self,
-        rb_self: Value,
-        a: Value,
-        b: Value,
-        c: Value,
-        d: Value,
-        e: Value,
-        f: Value,
-        g: Value,
-        h: Value,
-        i: Value,
-        j: Value,
-        k: Value,
-        l: Value,
-    ) -> Result<Value, Error> {
-        (self.func)(
-            rb_self.try_convert()?,
-            a.try_convert()?,
-            b.try_convert()?,
-            c.try_convert()?,
-            d.try_convert()?,
-            e.try_convert()?,
-            f.try_convert()?,
-            g.try_convert()?,
-            h.try_convert()?,
-            i.try_convert()?,
-            j.try_convert()?,
-            k.try_convert()?,
-            l.try_convert()?,
-        )
-        .into_return_value()
-    }
-
-    #[inline]
-    pub unsafe fn call_handle_error(
-        self,
-        rb_self: Value,
-        a: Value,
-        b: Value,
-        c: Value,
-        d: Value,
-        e: Value,
-        f: Value,
-        g: Value,
-        h: Value,
-        i: Value,
-        j: Value,
-        k: Value,
-        l: Value,
-    ) -> Value {
-        let res = match std::panic::catch_unwind(AssertUnwindSafe(|| {
-            self.call_convert_value(rb_self, a, b, c, d, e, f, g, h, i, j, k, l)
-        })) {
-            Ok(v) => v,
-            Err(e) => Err(Error::from_panic(e)),
-        };
-        match res {
-            Ok(v) => v,
-            Err(e) => raise(e),
-        }
-    }
-}
-
-/// Helper type for wrapping a function as a Ruby method taking self and 13
-/// arguments, with type conversions and error handling.
-///
-/// See the [`method`] macro.
-#[doc(hidden)]
-pub struct Method13<Func, RbSelf, A, B, C, D, E, F, G, H, I, J, K, L, M, Res> {
-    func: Func,
-    rb_self: PhantomData<RbSelf>,
-    a: PhantomData<A>,
-    b: PhantomData<B>,
-    c: PhantomData<C>,
-    d: PhantomData<D>,
-    e: PhantomData<E>,
-    f: PhantomData<F>,
-    g: PhantomData<G>,
-    h: PhantomData<H>,
-    i: PhantomData<I>,
-    j: PhantomData<J>,
-    k: PhantomData<K>,
-    l: PhantomData<L>,
-    m: PhantomData<M>,
-    res: PhantomData<Res>,
-}
-
-#[allow(missing_docs)]
-impl<Func, RbSelf, A, B, C, D, E, F, G, H, I, J, K, L, M, Res>
-    Method13<Func, RbSelf, A, B, C, D, E, F, G, H, I, J, K, L, M, Res>
-where
-    Func: Fn(RbSelf, A, B, C, D, E, F, G, H, I, J, K, L, M) -> Res,
-    RbSelf: TryConvert,
-    A: TryConvert,
-    B: TryConvert,
-    C: TryConvert,
-    D: TryConvert,
-    E: TryConvert,
-    F: TryConvert,
-    G: TryConvert,
-    H: TryConvert,
-    I: TryConvert,
-    J: TryConvert,
-    K: TryConvert,
-    L: TryConvert,
-    M: TryConvert,
-    Res: ReturnValue,
-{
-    #[inline]
-    pub fn new(func: Func) -> Self {
-        Self {
-            func,
-            rb_self: Default::default(),
-            a: Default::default(),
-            b: Default::default(),
-            c: Default::default(),
-            d: Default::default(),
-            e: Default::default(),
-            f: Default::default(),
-            g: Default::default(),
-            h: Default::default(),
-            i: Default::default(),
-            j: Default::default(),
-            k: Default::default(),
-            l: Default::default(),
-            m: Default::default(),
-            res: Default::default(),
-        }
-    }
-
-    #[inline]
-    unsafe fn call_convert_value(
-        self,
-        rb_self: Value,
-        a: Value,
-        b: Value,
-        c: Value,
-        d: Value,
-        e: Value,
-        f: Value,
-        g: Value,
-        h: Value,
-        i: Value,
-        j: Value,
-        k: Value,
-        l: Value,
-        m: Value,
-    ) -> Result<Value, Error> {
-        (self.func)(
-            rb_self.try_convert()?,
-            a.try_convert()?,
-            b.try_convert()?,
-            c.try_convert()?,
-            d.try_convert()?,
-            e.try_convert()?,
-            f.try_convert()?,
-            g.try_convert()?,
-            h.try_convert()?,
-            i.try_convert()?,
-            j.try_convert()?,
-            k.try_convert()?,
-            l.try_convert()?,
-            m.try_convert()?,
-        )
-        .into_return_value()
-    }
-
-    #[inline]
-    pub unsafe fn call_handle_error(
-        self,
-        rb_self: Value,
-        a: Value,
-        b: Value,
-        c: Value,
-        d: Value,
-        e: Value,
-        f: Value,
-        g: Value,
-        h: Value,
-        i: Value,
-        j: Value,
-        k: Value,
-        l: Value,
-        m: Value,
-    ) -> Value {
-        let res = match std::panic::catch_unwind(AssertUnwindSafe(|| {
-            self.call_convert_value(rb_self, a, b, c, d, e, f, g, h, i, j, k, l, m)
-        })) {
-            Ok(v) => v,
-            Err(e) => Err(Error::from_panic(e)),
-        };
-        match res {
-            Ok(v) => v,
-            Err(e) => raise(e),
-        }
-    }
-}
-
-/// Helper type for wrapping a function as a Ruby method taking self and 14
-/// arguments, with type conversions and error handling.
-///
-/// See the [`method`] macro.
-#[doc(hidden)]
-pub struct Method14<Func, RbSelf, A, B, C, D, E, F, G, H, I, J, K, L, M, N, Res> {
-    func: Func,
-    rb_self: PhantomData<RbSelf>,
-    a: PhantomData<A>,
-    b: PhantomData<B>,
-    c: PhantomData<C>,
-    d: PhantomData<D>,
-    e: PhantomData<E>,
-    f: PhantomData<F>,
-    g: PhantomData<G>,
-    h: PhantomData<H>,
-    i: PhantomData<I>,
-    j: PhantomData<J>,
-    k: PhantomData<K>,
-    l: PhantomData<L>,
-    m: PhantomData<M>,
-    n: PhantomData<N>,
-    res: PhantomData<Res>,
-}
-
-#[allow(missing_docs)]
-impl<Func, RbSelf, A, B, C, D, E, F, G, H, I, J, K, L, M, N, Res>
-    Method14<Func, RbSelf, A, B, C, D, E, F, G, H, I, J, K, L, M, N, Res>
-where
-    Func: Fn(RbSelf, A, B, C, D, E, F, G, H, I, J, K, L, M, N) -> Res,
-    RbSelf: TryConvert,
-    A: TryConvert,
-    B: TryConvert,
-    C: TryConvert,
-    D: TryConvert,
-    E: TryConvert,
-    F: TryConvert,
-    G: TryConvert,
-    H: TryConvert,
-    I: TryConvert,
-    J: TryConvert,
-    K: TryConvert,
-    L: TryConvert,
-    M: TryConvert,
-    N: TryConvert,
-    Res: ReturnValue,
-{
-    #[inline]
-    pub fn new(func: Func) -> Self {
-        Self {
-            func,
-            rb_self: Default::default(),
-            a: Default::default(),
-            b: Default::default(),
-            c: Default::default(),
-            d: Default::default(),
-            e: Default::default(),
-            f: Default::default(),
-            g: Default::default(),
-            h: Default::default(),
-            i: Default::default(),
-            j: Default::default(),
-            k: Default::default(),
-            l: Default::default(),
-            m: Default::default(),
-            n: Default::default(),
-            res: Default::default(),
-        }
-    }
-
-    #[inline]
-    unsafe fn call_convert_value(
-        self,
-        rb_self: Value,
-        a: Value,
-        b: Value,
-        c: Value,
-        d: Value,
-        e: Value,
-        f: Value,
-        g: Value,
-        h: Value,
-        i: Value,
-        j: Value,
-        k: Value,
-        l: Value,
-        m: Value,
-        n: Value,
-    ) -> Result<Value, Error> {
-        (self.func)(
-            rb_self.try_convert()?,
-            a.try_convert()?,
-            b.try_convert()?,
-            c.try_convert()?,
-            d.try_convert()?,
-            e.try_convert()?,
-            f.try_convert()?,
-            g.try_convert()?,
-            h.try_convert()?,
-            i.try_convert()?,
-            j.try_convert()?,
-            k.try_convert()?,
-            l.try_convert()?,
-            m.try_convert()?,
-            n.try_convert()?,
-        )
-        .into_return_value()
-    }
-
-    #[inline]
-    pub unsafe fn call_handle_error(
-        self,
-        rb_self: Value,
-        a: Value,
-        b: Value,
-        c: Value,
-        d: Value,
-        e: Value,
-        f: Value,
-        g: Value,
-        h: Value,
-        i: Value,
-        j: Value,
-        k: Value,
-        l: Value,
-        m: Value,
-        n: Value,
-    ) -> Value {
-        let res = match std::panic::catch_unwind(AssertUnwindSafe(|| {
-            self.call_convert_value(rb_self, a, b, c, d, e, f, g, h, i, j, k, l, m, n)
-        })) {
-            Ok(v) => v,
-            Err(e) => Err(Error::from_panic(e)),
-        };
-        match res {
-            Ok(v) => v,
-            Err(e) => raise(e),
-        }
-    }
-}
-
-/// Helper type for wrapping a function as a Ruby method taking self and 15
-/// arguments, with type conversions and error handling.
-///
-/// See the [`method`] macro.
-#[doc(hidden)]
-pub struct Method15<Func, RbSelf, A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, Res> {
-    func: Func,
-    rb_self: PhantomData<RbSelf>,
-    a: PhantomData<A>,
-    b: PhantomData<B>,
-    c: PhantomData<C>,
-    d: PhantomData<D>,
-    e: PhantomData<E>,
-    f: PhantomData<F>,
-    g: PhantomData<G>,
-    h: PhantomData<H>,
-    i: PhantomData<I>,
-    j: PhantomData<J>,
-    k: PhantomData<K>,
-    l: PhantomData<L>,
-    m: PhantomData<M>,
-    n: PhantomData<N>,
-    o: PhantomData<O>,
-    res: PhantomData<Res>,
-}
-
-#[allow(missing_docs)]
-impl<Func, RbSelf, A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, Res>
-    Method15<Func, RbSelf, A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, Res>
-where
-    Func: Fn(RbSelf, A, B, C, D, E, F, G, H, I, J, K, L, M, N, O) -> Res,
-    RbSelf: TryConvert,
-    A: TryConvert,
-    B: TryConvert,
-    C: TryConvert,
-    D: TryConvert,
-    E: TryConvert,
-    F: TryConvert,
-    G: TryConvert,
-    H: TryConvert,
-    I: TryConvert,
-    J: TryConvert,
-    K: TryConvert,
-    L: TryConvert,
-    M: TryConvert,
-    N: TryConvert,
-    O: TryConvert,
-    Res: ReturnValue,
-{
-    #[inline]
-    pub fn new(func: Func) -> Self {
-        Self {
-            func,
-            rb_self: Default::default(),
-            a: Default::default(),
-            b: Default::default(),
-            c: Default::default(),
-            d: Default::default(),
-            e: Default::default(),
-            f: Default::default(),
-            g: Default::default(),
-            h: Default::default(),
-            i: Default::default(),
-            j: Default::default(),
-            k: Default::default(),
-            l: Default::default(),
-            m: Default::default(),
-            n: Default::default(),
-            o: Default::default(),
-            res: Default::default(),
-        }
-    }
-
-    #[inline]
-    unsafe fn call_convert_value(
-        self,
-        rb_self: Value,
-        a: Value,
-        b: Value,
-        c: Value,
-        d: Value,
-        e: Value,
-        f: Value,
-        g: Value,
-        h: Value,
-        i: Value,
-        j: Value,
-        k: Value,
-        l: Value,
-        m: Value,
-        n: Value,
-        o: Value,
-    ) -> Result<Value, Error> {
-        (self.func)(
-            rb_self.try_convert()?,
-            a.try_convert()?,
-            b.try_convert()?,
-            c.try_convert()?,
-            d.try_convert()?,
-            e.try_convert()?,
-            f.try_convert()?,
-            g.try_convert()?,
-            h.try_convert()?,
-            i.try_convert()?,
-            j.try_convert()?,
-            k.try_convert()?,
-            l.try_convert()?,
-            m.try_convert()?,
-            n.try_convert()?,
-            o.try_convert()?,
-        )
-        .into_return_value()
-    }
-
-    #[inline]
-    pub unsafe fn call_handle_error(
-        self,
-        rb_self: Value,
-        a: Value,
-        b: Value,
-        c: Value,
-        d: Value,
-        e: Value,
-        f: Value,
-        g: Value,
-        h: Value,
-        i: Value,
-        j: Value,
-        k: Value,
-        l: Value,
-        m: Value,
-        n: Value,
-        o: Value,
-    ) -> Value {
-        let res = match std::panic::catch_unwind(AssertUnwindSafe(|| {
-            self.call_convert_value(rb_self, a, b, c, d, e, f, g, h, i, j, k, l, m, n, o)
-        })) {
-            Ok(v) => v,
-            Err(e) => Err(Error::from_panic(e)),
-        };
-        match res {
-            Ok(v) => v,
-            Err(e) => raise(e),
-        }
-    }
-}
-
-/// Helper type for wrapping a function as a Ruby method taking self and 16
-/// arguments, with type conversions and error handling.
-///
-/// See the [`method`] macro.
-#[doc(hidden)]
-pub struct Method16<Func, RbSelf, A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Res> {
-    func: Func,
-    rb_self: PhantomData<RbSelf>,
-    a: PhantomData<A>,
-    b: PhantomData<B>,
-    c: PhantomData<C>,
-    d: PhantomData<D>,
-    e: PhantomData<E>,
-    f: PhantomData<F>,
-    g: PhantomData<G>,
-    h: PhantomData<H>,
-    i: PhantomData<I>,
-    j: PhantomData<J>,
-    k: PhantomData<K>,
-    l: PhantomData<L>,
-    m: PhantomData<M>,
-    n: PhantomData<N>,
-    o: PhantomData<O>,
-    p: PhantomData<P>,
-    res: PhantomData<Res>,
-}
-
-#[allow(missing_docs)]
-impl<Func, RbSelf, A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Res>
-    Method16<Func, RbSelf, A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Res>
-where
-    Func: Fn(RbSelf, A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P) -> Res,
-    RbSelf: TryConvert,
-    A: TryConvert,
-    B: TryConvert,
-    C: TryConvert,
-    D: TryConvert,
-    E: TryConvert,
-    F: TryConvert,
-    G: TryConvert,
-    H: TryConvert,
-    I: TryConvert,
-    J: TryConvert,
-    K: TryConvert,
-    L: TryConvert,
-    M: TryConvert,
-    N: TryConvert,
-    O: TryConvert,
-    P: TryConvert,
-    Res: ReturnValue,
-{
-    #[inline]
-    pub fn new(func: Func) -> Self {
-        Self {
-            func,
-            rb_self: Default::default(),
-            a: Default::default(),
-            b: Default::default(),
-            c: Default::default(),
-            d: Default::default(),
-            e: Default::default(),
-            f: Default::default(),
-            g: Default::default(),
-            h: Default::default(),
-            i: Default::default(),
-            j: Default::default(),
-            k: Default::default(),
-            l: Default::default(),
-            m: Default::default(),
-            n: Default::default(),
-            o: Default::default(),
-            p: Default::default(),
-            res: Default::default(),
-        }
-    }
-
-    #[inline]
-    unsafe fn call_convert_value(
-        self,
-        rb_self: Value,
-        a: Value,
-        b: Value,
-        c: Value,
-        d: Value,
-        e: Value,
-        f: Value,
-        g: Value,
-        h: Value,
-        i: Value,
-        j: Value,
-        k: Value,
-        l: Value,
-        m: Value,
-        n: Value,
-        o: Value,
-        p: Value,
-    ) -> Result<Value, Error> {
-        (self.func)(
-            rb_self.try_convert()?,
-            a.try_convert()?,
-            b.try_convert()?,
-            c.try_convert()?,
-            d.try_convert()?,
-            e.try_convert()?,
-            f.try_convert()?,
-            g.try_convert()?,
-            h.try_convert()?,
-            i.try_convert()?,
-            j.try_convert()?,
-            k.try_convert()?,
-            l.try_convert()?,
-            m.try_convert()?,
-            n.try_convert()?,
-            o.try_convert()?,
-            p.try_convert()?,
-        )
-        .into_return_value()
-    }
-
-    #[inline]
-    pub unsafe fn call_handle_error(
-        self,
-        rb_self: Value,
-        a: Value,
-        b: Value,
-        c: Value,
-        d: Value,
-        e: Value,
-        f: Value,
-        g: Value,
-        h: Value,
-        i: Value,
-        j: Value,
-        k: Value,
-        l: Value,
-        m: Value,
-        n: Value,
-        o: Value,
-        p: Value,
-    ) -> Value {
-        let res = match std::panic::catch_unwind(AssertUnwindSafe(|| {
-            self.call_convert_value(rb_self, a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p)
-        })) {
-            Ok(v) => v,
-            Err(e) => Err(Error::from_panic(e)),
-        };
-        match res {
-            Ok(v) => v,
-            Err(e) => raise(e),
-        }
-    }
-}
-
-/// Wrap a Rust function item with Ruby type conversion and error handling.
-///
-/// This macro wraps the given function and returns a function pointer
-/// implementing the [`Method`] trait, suitable for passing to functions that
-/// define Ruby methods such as
-/// [`define_method`](crate::module::Module::define_method).
-///
-/// Ruby code implicitly always has a `self` parameter available. In the
-/// extention API this is passed explicitly. As a result there is always an
-/// extra `self` argument before the arguments explitly passed in Ruby, and the
-/// number of Rust argument will be one more than the Ruby arity.
-///
-/// The values `-2` and `-1` for `arity` have special meaning. Both indicate
-/// functions with any number of arguments, with `-2` the arguments are passed
-/// as a [`RArray`], with `-1` they are passed as a slice of [`Value`]s.
-/// Arity of `-1` can be used with [`scan_args`](crate::scan_args::scan_args)
-/// and [`get_kwargs`](crate::scan_args::get_kwargs) for more complex method
-/// signatures.
-///
-/// | Arity | Signature                                                 |
-/// |-------|-----------------------------------------------------------|
-/// |    -2 | `fn(rb_self: T, arguments: RArray) -> Result<R, Error>`   |
-/// |    -1 | `fn(rb_self: T, arguments: &[Value]) -> Result<R, Error>` |
-/// |     0 | `fn(rb_self: T) -> Result<R, Error>`                      |
-/// |     1 | `fn(rb_self: T, arg1: U) -> Result<R, Error>`             |
-/// |     2 | `fn(rb_self: T, arg1: U, arg2: V) -> Result<R, Error>`    |
-/// |   ... | ...                                                       |
-/// |    16 | ...                                                       |
-///
-/// Where `T`, `U`, `V` and so on are any types that implement `TryConvert`,
-/// and `R` implements `Into<Value>`. It is also possible to return just `R`
-/// rather than a `Result` for functions that will never error, and omit the
-/// return value (i.e. return `()`) for a function that returns `nil` to Ruby.
-/// See [`ReturnValue`] for more details on what can be returned.
-///
-/// See the [`function`] macro for cases where there is no need to handle the
-/// `self` argument.
-///
-/// # Examples
-///
-/// ```
-/// use magnus::{define_class, method, prelude::*, Error};
-///
-/// fn rb_is_blank(rb_self: String) -> bool {
-///     rb_self.contains(|c: char| !c.is_whitespace())
-/// }
-///
-/// #[magnus::init]
-/// fn init() -> Result<(), Error> {
-///     let class = define_class("String", Default::default())?;
-///     class.define_method("blank?", method!(rb_is_blank, 0));
-///     Ok(())
-/// }
-/// ```
-#[macro_export]
-macro_rules! method {
-    ($name:expr, -2) => {{
-        unsafe extern "C" fn anon(rb_self: $crate::Value, args: $crate::RArray) -> $crate::Value {
-            $crate::method::MethodRbAry::new($name).call_handle_error(rb_self, args)
-        }
-        anon as unsafe extern "C" fn($crate::Value, $crate::RArray) -> $crate::Value
-    }};
-    ($name:expr, -1) => {{
-        unsafe extern "C" fn anon(
-            argc: std::os::raw::c_int,
-            argv: *const $crate::Value,
-            rb_self: $crate::Value,
-        ) -> $crate::Value {
-            $crate::method::MethodCAry::new($name).call_handle_error(argc, argv, rb_self)
-        }
-        anon as unsafe extern "C" fn(
-            std::os::raw::c_int,
-            *const $crate::Value,
-            $crate::Value,
-        ) -> $crate::Value
-    }};
-    ($name:expr, 0) => {{
-        unsafe extern "C" fn anon(rb_self: $crate::Value) -> $crate::Value {
-            $crate::method::Method0::new($name).call_handle_error(rb_self)
-        }
-        anon as unsafe extern "C" fn($crate::Value) -> $crate::Value
-    }};
-    ($name:expr, 1) => {{
-        unsafe extern "C" fn anon(rb_self: $crate::Value, a: $crate::Value) -> $crate::Value {
-            $crate::method::Method1::new($name).call_handle_error(rb_self, a)
-        }
-        anon as unsafe extern "C" fn($crate::Value, $crate::Value) -> $crate::Value
-    }};
-    ($name:expr, 2) => {{
-        unsafe extern "C" fn anon(
-            rb_self: $crate::Value,
-            a: $crate::Value,
-            b: $crate::Value,
-        ) -> $crate::Value {
-            $crate::method::Method2::new($name).call_handle_error(rb_self, a, b)
-        }
-        anon as unsafe extern "C" fn($crate::Value, $crate::Value, $crate::Value) -> $crate::Value
-    }};
-    ($name:expr, 3) => {{
-        unsafe extern "C" fn anon(
-            rb_self: $crate::Value,
-            a: $crate::Value,
-            b: $crate::Value,
-            c: $crate::Value,
-        ) -> $crate::Value {
-            $crate::method::Method3::new($name).call_handle_error(rb_self, a, b, c)
-        }
-        anon as unsafe extern "C" fn(
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-        ) -> $crate::Value
-    }};
-    ($name:expr, 4) => {{
-        unsafe extern "C" fn anon(
-            rb_self: $crate::Value,
-            a: $crate::Value,
-            b: $crate::Value,
-            c: $crate::Value,
-            d: $crate::Value,
-        ) -> $crate::Value {
-            $crate::method::Method4::new($name).call_handle_error(rb_self, a, b, c, d)
-        }
-        anon as unsafe extern "C" fn(
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-        ) -> $crate::Value
-    }};
-    ($name:expr, 5) => {{
-        unsafe extern "C" fn anon(
-            rb_self: $crate::Value,
-            a: $crate::Value,
-            b: $crate::Value,
-            c: $crate::Value,
-            d: $crate::Value,
-            e: $crate::Value,
-        ) -> $crate::Value {
-            $crate::method::Method5::new($name).call_handle_error(rb_self, a, b, c, d, e)
-        }
-        anon as unsafe extern "C" fn(
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-        ) -> $crate::Value
-    }};
-    ($name:expr, 6) => {{
-        unsafe extern "C" fn anon(
-            rb_self: $crate::Value,
-            a: $crate::Value,
-            b: $crate::Value,
-            c: $crate::Value,
-            d: $crate::Value,
-            e: $crate::Value,
-            f: $crate::Value,
-        ) -> $crate::Value {
-            $crate::method::Method6::new($name).call_handle_error(rb_self, a, b, c, d, e, f)
-        }
-        anon as unsafe extern "C" fn(
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-        ) -> $crate::Value
-    }};
-    ($name:expr, 7) => {{
-        unsafe extern "C" fn anon(
-            rb_self: $crate::Value,
-            a: $crate::Value,
-            b: $crate::Value,
-            c: $crate::Value,
-            d: $crate::Value,
-            e: $crate::Value,
-            f: $crate::Value,
-            g: $crate::Value,
-        ) -> $crate::Value {
-            $crate::method::Method7::new($name).call_handle_error(rb_self, a, b, c, d, e, f, g)
-        }
-        anon as unsafe extern "C" fn(
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-        ) -> $crate::Value
-    }};
-    ($name:expr, 8) => {{
-        unsafe extern "C" fn anon(
-            rb_self: $crate::Value,
-            a: $crate::Value,
-            b: $crate::Value,
-            c: $crate::Value,
-            d: $crate::Value,
-            e: $crate::Value,
-            f: $crate::Value,
-            g: $crate::Value,
-            h: $crate::Value,
-        ) -> $crate::Value {
-            $crate::method::Method8::new($name).call_handle_error(rb_self, a, b, c, d, e, f, g, h)
-        }
-        anon as unsafe extern "C" fn(
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-        ) -> $crate::Value
-    }};
-    ($name:expr, 9) => {{
-        unsafe extern "C" fn anon(
-            rb_self: $crate::Value,
-            a: $crate::Value,
-            b: $crate::Value,
-            c: $crate::Value,
-            d: $crate::Value,
-            e: $crate::Value,
-            f: $crate::Value,
-            g: $crate::Value,
-            h: $crate::Value,
-            i: $crate::Value,
-        ) -> $crate::Value {
-            $crate::method::Method9::new($name)
-                .call_handle_error(rb_self, a, b, c, d, e, f, g, h, i)
-        }
-        anon as unsafe extern "C" fn(
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-        ) -> $crate::Value
-    }};
-    ($name:expr, 10) => {{
-        unsafe extern "C" fn anon(
-            rb_self: $crate::Value,
-            a: $crate::Value,
-            b: $crate::Value,
-            c: $crate::Value,
-            d: $crate::Value,
-            e: $crate::Value,
-            f: $crate::Value,
-            g: $crate::Value,
-            h: $crate::Value,
-            i: $crate::Value,
-            j: $crate::Value,
-        ) -> $crate::Value {
-            $crate::method::Method10::new($name)
-                .call_handle_error(rb_self, a, b, c, d, e, f, g, h, i, j)
-        }
-        anon as unsafe extern "C" fn(
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-        ) -> $crate::Value
-    }};
-    ($name:expr, 11) => {{
-        unsafe extern "C" fn anon(
-            rb_self: $crate::Value,
-            a: $crate::Value,
-            b: $crate::Value,
-            c: $crate::Value,
-            d: $crate::Value,
-            e: $crate::Value,
-            f: $crate::Value,
-            g: $crate::Value,
-            h: $crate::Value,
-            i: $crate::Value,
-            j: $crate::Value,
-            k: $crate::Value,
-        ) -> $crate::Value {
-            $crate::method::Method11::new($name)
-                .call_handle_error(rb_self, a, b, c, d, e, f, g, h, i, j, k)
-        }
-        anon as unsafe extern "C" fn(
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-        ) -> $crate::Value
-    }};
-    ($name:expr, 12) => {{
-        unsafe extern "C" fn anon(
-            rb_self: $crate::Value,
-            a: $crate::Value,
-            b: $crate::Value,
-            c: $crate::Value,
-            d: $crate::Value,
-            e: $crate::Value,
-            f: $crate::Value,
-            g: $crate::Value,
-            h: $crate::Value,
-            i: $crate::Value,
-            j: $crate::Value,
-            k: $crate::Value,
-            l: $crate::Value,
-        ) -> $crate::Value {
-            $crate::method::Method12::new($name)
-                .call_handle_error(rb_self, a, b, c, d, e, f, g, h, i, j, k, l)
-        }
-        anon as unsafe extern "C" fn(
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-        ) -> $crate::Value
-    }};
-    ($name:expr, 13) => {{
-        unsafe extern "C" fn anon(
-            rb_self: $crate::Value,
-            a: $crate::Value,
-            b: $crate::Value,
-            c: $crate::Value,
-            d: $crate::Value,
-            e: $crate::Value,
-            f: $crate::Value,
-            g: $crate::Value,
-            h: $crate::Value,
-            i: $crate::Value,
-            j: $crate::Value,
-            k: $crate::Value,
-            l: $crate::Value,
-            m: $crate::Value,
-        ) -> $crate::Value {
-            $crate::method::Method13::new($name)
-                .call_handle_error(rb_self, a, b, c, d, e, f, g, h, i, j, k, l, m)
-        }
-        anon as unsafe extern "C" fn(
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-        ) -> $crate::Value
-    }};
-    ($name:expr, 14) => {{
-        unsafe extern "C" fn anon(
-            rb_self: $crate::Value,
-            a: $crate::Value,
-            b: $crate::Value,
-            c: $crate::Value,
-            d: $crate::Value,
-            e: $crate::Value,
-            f: $crate::Value,
-            g: $crate::Value,
-            h: $crate::Value,
-            i: $crate::Value,
-            j: $crate::Value,
-            k: $crate::Value,
-            l: $crate::Value,
-            m: $crate::Value,
-            n: $crate::Value,
-        ) -> $crate::Value {
-            $crate::method::Method14::new($name)
-                .call_handle_error(rb_self, a, b, c, d, e, f, g, h, i, j, k, l, m, n)
-        }
-        anon as unsafe extern "C" fn(
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-        ) -> $crate::Value
-    }};
-    ($name:expr, 15) => {{
-        unsafe extern "C" fn anon(
-            rb_self: $crate::Value,
-            a: $crate::Value,
-            b: $crate::Value,
-            c: $crate::Value,
-            d: $crate::Value,
-            e: $crate::Value,
-            f: $crate::Value,
-            g: $crate::Value,
-            h: $crate::Value,
-            i: $crate::Value,
-            j: $crate::Value,
-            k: $crate::Value,
-            l: $crate::Value,
-            m: $crate::Value,
-            n: $crate::Value,
-            o: $crate::Value,
-        ) -> $crate::Value {
-            $crate::method::Method15::new($name)
-                .call_handle_error(rb_self, a, b, c, d, e, f, g, h, i, j, k, l, m, n, o)
-        }
-        anon as unsafe extern "C" fn(
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-        ) -> $crate::Value
-    }};
-    ($name:expr, 16) => {{
-        unsafe extern "C" fn anon(
-            rb_self: $crate::Value,
-            a: $crate::Value,
-            b: $crate::Value,
-            c: $crate::Value,
-            d: $crate::Value,
-            e: $crate::Value,
-            f: $crate::Value,
-            g: $crate::Value,
-            h: $crate::Value,
-            i: $crate::Value,
-            j: $crate::Value,
-            k: $crate::Value,
-            l: $crate::Value,
-            m: $crate::Value,
-            n: $crate::Value,
-            o: $crate::Value,
-            p: $crate::Value,
-        ) -> $crate::Value {
-            $crate::method::Method16::new($name)
-                .call_handle_error(rb_self, a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p)
-        }
-        anon as unsafe extern "C" fn(
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-        ) -> $crate::Value
-    }};
-    ($name:expr, $arity:expr) => {
-        compile_error!("arity must be an integer literal between -2..=16")
-    };
-}
-
-/// Helper type for wrapping a function as a Ruby method ignoring self and
-/// taking a Ruby array of arguments, with type conversions and error handling.
-///
-/// See the [`function`] macro.
-#[doc(hidden)]
-pub struct FunctionRbAry<Func, Args, Res> {
-    func: Func,
-    args: PhantomData<Args>,
-    res: PhantomData<Res>,
-}
-
-#[allow(missing_docs)]
-impl<Func, Args, Res> FunctionRbAry<Func, Args, Res>
-where
-    Func: Fn(Args) -> Res,
-    Args: TryConvert,
-    Res: ReturnValue,
-{
-    #[inline]
-    pub fn new(func: Func) -> Self {
-        Self {
-            func,
-            args: Default::default(),
-            res: Default::default(),
-        }
-    }
-
-    #[inline]
-    unsafe fn call_convert_value(self, args: RArray) -> Result<Value, Error> {
-        (self.func)(args.try_convert()?).into_return_value()
-    }
-
-    #[inline]
-    pub unsafe fn call_handle_error(self, args: RArray) -> Value {
-        let res = match std::panic::catch_unwind(AssertUnwindSafe(|| self.call_convert_value(args)))
-        {
-            Ok(v) => v,
-            Err(e) => Err(Error::from_panic(e)),
-        };
-        match res {
-            Ok(v) => v,
-            Err(e) => raise(e),
-        }
-    }
-}
-
-/// Helper type for wrapping a function as a Ruby method ignoring self and
-/// taking a slice of arguments, with type conversions and error handling.
-///
-/// See the [`function`] macro.
-#[doc(hidden)]
-pub struct FunctionCAry<Func, Res> {
-    func: Func,
-    res: PhantomData<Res>,
-}
-
-#[allow(missing_docs)]
-impl<Func, Res> FunctionCAry<Func, Res>
-where
-    Func: Fn(&[Value]) -> Res,
-    Res: ReturnValue,
-{
-    #[inline]
-    pub fn new(func: Func) -> Self {
-        Self {
-            func,
-            res: Default::default(),
-        }
-    }
-
-    #[inline]
-    unsafe fn call_convert_value(self, argc: c_int, argv: *const Value) -> Result<Value, Error> {
-        let args = slice::from_raw_parts(argv, argc as usize);
-        (self.func)(args).into_return_value()
-    }
-
-    #[inline]
-    pub unsafe fn call_handle_error(self, argc: c_int, argv: *const Value) -> Value {
-        let res = match std::panic::catch_unwind(AssertUnwindSafe(|| {
-            self.call_convert_value(argc, argv)
-        })) {
-            Ok(v) => v,
-            Err(e) => Err(Error::from_panic(e)),
-        };
-        match res {
-            Ok(v) => v,
-            Err(e) => raise(e),
-        }
-    }
-}
-
-/// Helper type for wrapping a function as a Ruby method ignoring self and
-/// taking no arguments, with type conversions and error handling.
-///
-/// See the [`function`] macro.
-#[doc(hidden)]
-pub struct Function0<Func, Res> {
-    func: Func,
-    res: PhantomData<Res>,
-}
-
-#[allow(missing_docs)]
-impl<Func, Res> Function0<Func, Res>
-where
-    Func: Fn() -> Res,
-    Res: ReturnValue,
-{
-    #[inline]
-    pub fn new(func: Func) -> Self {
-        Self {
-            func,
-
-            res: Default::default(),
-        }
-    }
-
-    #[inline]
-    unsafe fn call_convert_value(self) -> Result<Value, Error> {
-        (self.func)().into_return_value()
-    }
-
-    #[inline]
-    pub unsafe fn call_handle_error(self) -> Value {
-        let res = match std::panic::catch_unwind(AssertUnwindSafe(|| self.call_convert_value())) {
-            Ok(v) => v,
-            Err(e) => Err(Error::from_panic(e)),
-        };
-        match res {
-            Ok(v) => v,
-            Err(e) => raise(e),
-        }
-    }
-}
-
-/// Helper type for wrapping a function as a Ruby method ignoring self and
-/// taking 1 argument, with type conversions and error handling.
-///
-/// See the [`function`] macro.
-#[doc(hidden)]
-pub struct Function1<Func, A, Res> {
-    func: Func,
-    a: PhantomData<A>,
-    res: PhantomData<Res>,
-}
-
-#[allow(missing_docs)]
-impl<Func, A, Res> Function1<Func, A, Res>
-where
-    Func: Fn(A) -> Res,
-    A: TryConvert,
-    Res: ReturnValue,
-{
-    #[inline]
-    pub fn new(func: Func) -> Self {
-        Self {
-            func,
-            a: Default::default(),
-            res: Default::default(),
-        }
-    }
-
-    #[inline]
-    unsafe fn call_convert_value(self, a: Value) -> Result<Value, Error> {
-        (self.func)(a.try_convert()?).into_return_value()
-    }
-
-    #[inline]
-    pub unsafe fn call_handle_error(self, a: Value) -> Value {
-        let res = match std::panic::catch_unwind(AssertUnwindSafe(|| self.call_convert_value(a))) {
-            Ok(v) => v,
-            Err(e) => Err(Error::from_panic(e)),
-        };
-        match res {
-            Ok(v) => v,
-            Err(e) => raise(e),
-        }
-    }
-}
-
-/// Helper type for wrapping a function as a Ruby method ignoring self and
-/// taking 2 arguments, with type conversions and error handling.
-///
-/// See the [`function`] macro.
-#[doc(hidden)]
-pub struct Function2<Func, A, B, Res> {
-    func: Func,
-    a: PhantomData<A>,
-    b: PhantomData<B>,
-    res: PhantomData<Res>,
-}
-
-#[allow(missing_docs)]
-impl<Func, A, B, Res> Function2<Func, A, B, Res>
-where
-    Func: Fn(A, B) -> Res,
-    A: TryConvert,
-    B: TryConvert,
-    Res: ReturnValue,
-{
-    #[inline]
-    pub fn new(func: Func) -> Self {
-        Self {
-            func,
-            a: Default::default(),
-            b: Default::default(),
-            res: Default::default(),
-        }
-    }
-
-    #[inline]
-    unsafe fn call_convert_value(self, a: Value, b: Value) -> Result<Value, Error> {
-        (self.func)(a.try_convert()?, b.try_convert()?).into_return_value()
-    }
-
-    #[inline]
-    pub unsafe fn call_handle_error(self, a: Value, b: Value) -> Value {
-        let res = match std::panic::catch_unwind(AssertUnwindSafe(|| self.call_convert_value(a, b)))
-        {
-            Ok(v) => v,
-            Err(e) => Err(Error::from_panic(e)),
-        };
-        match res {
-            Ok(v) => v,
-            Err(e) => raise(e),
-        }
-    }
-}
-
-/// Helper type for wrapping a function as a Ruby method ignoring self and
-/// taking 3 arguments, with type conversions and error handling.
-///
-/// See the [`function`] macro.
-#[doc(hidden)]
-pub struct Function3<Func, A, B, C, Res> {
-    func: Func,
-    a: PhantomData<A>,
-    b: PhantomData<B>,
-    c: PhantomData<C>,
-    res: PhantomData<Res>,
-}
-
-#[allow(missing_docs)]
-impl<Func, A, B, C, Res> Function3<Func, A, B, C, Res>
-where
-    Func: Fn(A, B, C) -> Res,
-    A: TryConvert,
-    B: TryConvert,
-    C: TryConvert,
-    Res: ReturnValue,
-{
-    #[inline]
-    pub fn new(func: Func) -> Self {
-        Self {
-            func,
-            a: Default::default(),
-            b: Default::default(),
-            c: Default::default(),
-            res: Default::default(),
-        }
-    }
-
-    #[inline]
-    unsafe fn call_convert_value(self, a: Value, b: Value, c: Value) -> Result<Value, Error> {
-        (self.func)(a.try_convert()?, b.try_convert()?, c.try_convert()?).into_return_value()
-    }
-
-    #[inline]
-    pub unsafe fn call_handle_error(self, a: Value, b: Value, c: Value) -> Value {
-        let res =
-            match std::panic::catch_unwind(AssertUnwindSafe(|| self.call_convert_value(a, b, c))) {
-                Ok(v) => v,
-                Err(e) => Err(Error::from_panic(e)),
-            };
-        match res {
-            Ok(v) => v,
-            Err(e) => raise(e),
-        }
-    }
-}
-
-/// Helper type for wrapping a function as a Ruby method ignoring self and
-/// taking 4 arguments, with type conversions and error handling.
-///
-/// See the [`function`] macro.
-#[doc(hidden)]
-pub struct Function4<Func, A, B, C, D, Res> {
-    func: Func,
-    a: PhantomData<A>,
-    b: PhantomData<B>,
-    c: PhantomData<C>,
-    d: PhantomData<D>,
-    res: PhantomData<Res>,
-}
-
-#[allow(missing_docs)]
-impl<Func, A, B, C, D, Res> Function4<Func, A, B, C, D, Res>
-where
-    Func: Fn(A, B, C, D) -> Res,
-    A: TryConvert,
-    B: TryConvert,
-    C: TryConvert,
-    D: TryConvert,
-    Res: ReturnValue,
-{
-    #[inline]
-    pub fn new(func: Func) -> Self {
-        Self {
-            func,
-            a: Default::default(),
-            b: Default::default(),
-            c: Default::default(),
-            d: Default::default(),
-            res: Default::default(),
-        }
-    }
-
-    #[inline]
-    unsafe fn call_convert_value(
-        self,
-        a: Value,
-        b: Value,
-        c: Value,
-        d: Value,
-    ) -> Result<Value, Error> {
-        (self.func)(
-            a.try_convert()?,
-            b.try_convert()?,
-            c.try_convert()?,
-            d.try_convert()?,
-        )
-        .into_return_value()
-    }
-
-    #[inline]
-    pub unsafe fn call_handle_error(self, a: Value, b: Value, c: Value, d: Value) -> Value {
-        let res = match std::panic::catch_unwind(AssertUnwindSafe(|| {
-            self.call_convert_value(a, b, c, d)
-        })) {
-            Ok(v) => v,
-            Err(e) => Err(Error::from_panic(e)),
-        };
-        match res {
-            Ok(v) => v,
-            Err(e) => raise(e),
-        }
-    }
-}
-
-/// Helper type for wrapping a function as a Ruby method ignoring self and
-/// taking 5 arguments, with type conversions and error handling.
-///
-/// See the [`function`] macro.
-#[doc(hidden)]
-pub struct Function5<Func, A, B, C, D, E, Res> {
-    func: Func,
-    a: PhantomData<A>,
-    b: PhantomData<B>,
-    c: PhantomData<C>,
-    d: PhantomData<D>,
-    e: PhantomData<E>,
-    res: PhantomData<Res>,
-}
-
-#[allow(missing_docs)]
-impl<Func, A, B, C, D, E, Res> Function5<Func, A, B, C, D, E, Res>
-where
-    Func: Fn(A, B, C, D, E) -> Res,
-    A: TryConvert,
-    B: TryConvert,
-    C: TryConvert,
-    D: TryConvert,
-    E: TryConvert,
-    Res: ReturnValue,
-{
-    #[inline]
-    pub fn new(func: Func) -> Self {
-        Self {
-            func,
-            a: Default::default(),
-            b: Default::default(),
-            c: Default::default(),
-            d: Default::default(),
-            e: Default::default(),
-            res: Default::default(),
-        }
-    }
-
-    #[inline]
-    unsafe fn call_convert_value(
-        self,
-        a: Value,
-        b: Value,
-        c: Value,
-        d: Value,
-        e: Value,
-    ) -> Result<Value, Error> {
-        (self.func)(
-            a.try_convert()?,
-            b.try_convert()?,
-            c.try_convert()?,
-            d.try_convert()?,
-            e.try_convert()?,
-        )
-        .into_return_value()
-    }
-
-    #[inline]
-    pub unsafe fn call_handle_error(
-        self,
-        a: Value,
-        b: Value,
-        c: Value,
-        d: Value,
-        e: Value,
-    ) -> Value {
-        let res = match std::panic::catch_unwind(AssertUnwindSafe(|| {
-            self.call_convert_value(a, b, c, d, e)
-        })) {
-            Ok(v) => v,
-            Err(e) => Err(Error::from_panic(e)),
-        };
-        match res {
-            Ok(v) => v,
-            Err(e) => raise(e),
-        }
-    }
-}
-
-/// Helper type for wrapping a function as a Ruby method ignoring self and
-/// taking 6 arguments, with type conversions and error handling.
-///
-/// See the [`function`] macro.
-#[doc(hidden)]
-pub struct Function6<Func, A, B, C, D, E, F, Res> {
-    func: Func,
-    a: PhantomData<A>,
-    b: PhantomData<B>,
-    c: PhantomData<C>,
-    d: PhantomData<D>,
-    e: PhantomData<E>,
-    f: PhantomData<F>,
-    res: PhantomData<Res>,
-}
-
-#[allow(missing_docs)]
-impl<Func, A, B, C, D, E, F, Res> Function6<Func, A, B, C, D, E, F, Res>
-where
-    Func: Fn(A, B, C, D, E, F) -> Res,
-    A: TryConvert,
-    B: TryConvert,
-    C: TryConvert,
-    D: TryConvert,
-    E: TryConvert,
-    F: TryConvert,
-    Res: ReturnValue,
-{
-    #[inline]
-    pub fn new(func: Func) -> Self {
-        Self {
-            func,
-            a: Default::default(),
-            b: Default::default(),
-            c: Default::default(),
-            d: Default::default(),
-            e: Default::default(),
-            f: Default::default(),
-            res: Default::default(),
-        }
-    }
-
-    #[inline]
-    unsafe fn call_convert_value(
-        self,
-        a: Value,
-        b: Value,
-        c: Value,
-        d: Value,
-        e: Value,
-        f: Value,
-    ) -> Result<Value, Error> {
-        (self.func)(
-            a.try_convert()?,
-            b.try_convert()?,
-            c.try_convert()?,
-            d.try_convert()?,
-            e.try_convert()?,
-            f.try_convert()?,
-        )
-        .into_return_value()
-    }
-
-    #[inline]
-    pub unsafe fn call_handle_error(
-        self,
-        a: Value,
-        b: Value,
-        c: Value,
-        d: Value,
-        e: Value,
-        f: Value,
-    ) -> Value {
-        let res = match std::panic::catch_unwind(AssertUnwindSafe(|| {
-            self.call_convert_value(a, b, c, d, e, f)
-        })) {
-            Ok(v) => v,
-            Err(e) => Err(Error::from_panic(e)),
-        };
-        match res {
-            Ok(v) => v,
-            Err(e) => raise(e),
-        }
-    }
-}
-
-/// Helper type for wrapping a function as a Ruby method ignoring self and
-/// taking 7 arguments, with type conversions and error handling.
-///
-/// See the [`function`] macro.
-#[doc(hidden)]
-pub struct Function7<Func, A, B, C, D, E, F, G, Res> {
-    func: Func,
-    a: PhantomData<A>,
-    b: PhantomData<B>,
-    c: PhantomData<C>,
-    d: PhantomData<D>,
-    e: PhantomData<E>,
-    f: PhantomData<F>,
-    g: PhantomData<G>,
-    res: PhantomData<Res>,
-}
-
-#[allow(missing_docs)]
-impl<Func, A, B, C, D, E, F, G, Res> Function7<Func, A, B, C, D, E, F, G, Res>
-where
-    Func: Fn(A, B, C, D, E, F, G) -> Res,
-    A: TryConvert,
-    B: TryConvert,
-    C: TryConvert,
-    D: TryConvert,
-    E: TryConvert,
-    F: TryConvert,
-    G: TryConvert,
-    Res: ReturnValue,
-{
-    #[inline]
-    pub fn new(func: Func) -> Self {
-        Self {
-            func,
-            a: Default::default(),
-            b: Default::default(),
-            c: Default::default(),
-            d: Default::default(),
-            e: Default::default(),
-            f: Default::default(),
-            g: Default::default(),
-            res: Default::default(),
-        }
-    }
-
-    #[inline]
-    unsafe fn call_convert_value(
-        self,
-        a: Value,
-        b: Value,
-        c: Value,
-        d: Value,
-        e: Value,
-        f: Value,
-        g: Value,
-    ) -> Result<Value, Error> {
-        (self.func)(
-            a.try_convert()?,
-            b.try_convert()?,
-            c.try_convert()?,
-            d.try_convert()?,
-            e.try_convert()?,
-            f.try_convert()?,
-            g.try_convert()?,
-        )
-        .into_return_value()
-    }
-
-    #[inline]
-    pub unsafe fn call_handle_error(
-        self,
-        a: Value,
-        b: Value,
-        c: Value,
-        d: Value,
-        e: Value,
-        f: Value,
-        g: Value,
-    ) -> Value {
-        let res = match std::panic::catch_unwind(AssertUnwindSafe(|| {
-            self.call_convert_value(a, b, c, d, e, f, g)
-        })) {
-            Ok(v) => v,
-            Err(e) => Err(Error::from_panic(e)),
-        };
-        match res {
-            Ok(v) => v,
-            Err(e) => raise(e),
-        }
-    }
-}
-
-/// Helper type for wrapping a function as a Ruby method ignoring self and
-/// taking 8 arguments, with type conversions and error handling.
-///
-/// See the [`function`] macro.
-#[doc(hidden)]
-pub struct Function8<Func, A, B, C, D, E, F, G, H, Res> {
-    func: Func,
-    a: PhantomData<A>,
-    b: PhantomData<B>,
-    c: PhantomData<C>,
-    d: PhantomData<D>,
-    e: PhantomData<E>,
-    f: PhantomData<F>,
-    g: PhantomData<G>,
-    h: PhantomData<H>,
-    res: PhantomData<Res>,
-}
-
-#[allow(missing_docs)]
-impl<Func, A, B, C, D, E, F, G, H, Res> Function8<Func, A, B, C, D, E, F, G, H, Res>
-where
-    Func: Fn(A, B, C, D, E, F, G, H) -> Res,
-    A: TryConvert,
-    B: TryConvert,
-    C: TryConvert,
-    D: TryConvert,
-    E: TryConvert,
-    F: TryConvert,
-    G: TryConvert,
-    H: TryConvert,
-    Res: ReturnValue,
-{
-    #[inline]
-    pub fn new(func: Func) -> Self {
-        Self {
-            func,
-            a: Default::default(),
-            b: Default::default(),
-            c: Default::default(),
-            d: Default::default(),
-            e: Default::default(),
-            f: Default::default(),
-            g: Default::default(),
-            h: Default::default(),
-            res: Default::default(),
-        }
-    }
-
-    #[inline]
-    unsafe fn call_convert_value(
-        self,
-        a: Value,
-        b: Value,
-        c: Value,
-        d: Value,
-        e: Value,
-        f: Value,
-        g: Value,
-        h: Value,
-    ) -> Result<Value, Error> {
-        (self.func)(
-            a.try_convert()?,
-            b.try_convert()?,
-            c.try_convert()?,
-            d.try_convert()?,
-            e.try_convert()?,
-            f.try_convert()?,
-            g.try_convert()?,
-            h.try_convert()?,
-        )
-        .into_return_value()
-    }
-
-    #[inline]
-    pub unsafe fn call_handle_error(
-        self,
-        a: Value,
-        b: Value,
-        c: Value,
-        d: Value,
-        e: Value,
-        f: Value,
-        g: Value,
-        h: Value,
-    ) -> Value {
-        let res = match std::panic::catch_unwind(AssertUnwindSafe(|| {
-            self.call_convert_value(a, b, c, d, e, f, g, h)
-        })) {
-            Ok(v) => v,
-            Err(e) => Err(Error::from_panic(e)),
-        };
-        match res {
-            Ok(v) => v,
-            Err(e) => raise(e),
-        }
-    }
-}
-
-/// Helper type for wrapping a function as a Ruby method ignoring self and
-/// taking 9 arguments, with type conversions and error handling.
-///
-/// See the [`function`] macro.
-#[doc(hidden)]
-pub struct Function9<Func, A, B, C, D, E, F, G, H, I, Res> {
-    func: Func,
-    a: PhantomData<A>,
-    b: PhantomData<B>,
-    c: PhantomData<C>,
-    d: PhantomData<D>,
-    e: PhantomData<E>,
-    f: PhantomData<F>,
-    g: PhantomData<G>,
-    h: PhantomData<H>,
-    i: PhantomData<I>,
-    res: PhantomData<Res>,
-}
-
-#[allow(missing_docs)]
-impl<Func, A, B, C, D, E, F, G, H, I, Res> Function9<Func, A, B, C, D, E, F, G, H, I, Res>
-where
-    Func: Fn(A, B, C, D, E, F, G, H, I) -> Res,
-    A: TryConvert,
-    B: TryConvert,
-    C: TryConvert,
-    D: TryConvert,
-    E: TryConvert,
-    F: TryConvert,
-    G: TryConvert,
-    H: TryConvert,
-    I: TryConvert,
-    Res: ReturnValue,
-{
-    #[inline]
-    pub fn new(func: Func) -> Self {
-        Self {
-            func,
-            a: Default::default(),
-            b: Default::default(),
-            c: Default::default(),
-            d: Default::default(),
-            e: Default::default(),
-            f: Default::default(),
-            g: Default::default(),
-            h: Default::default(),
-            i: Default::default(),
-            res: Default::default(),
-        }
-    }
-
-    #[inline]
-    unsafe fn call_convert_value(
-        self,
-        a: Value,
-        b: Value,
-        c: Value,
-        d: Value,
-        e: Value,
-        f: Value,
-        g: Value,
-        h: Value,
-        i: Value,
-    ) -> Result<Value, Error> {
-        (self.func)(
-            a.try_convert()?,
-            b.try_convert()?,
-            c.try_convert()?,
-            d.try_convert()?,
-            e.try_convert()?,
-            f.try_convert()?,
-            g.try_convert()?,
-            h.try_convert()?,
-            i.try_convert()?,
-        )
-        .into_return_value()
-    }
-
-    #[inline]
-    pub unsafe fn call_handle_error(
-        self,
-        a: Value,
-        b: Value,
-        c: Value,
-        d: Value,
-        e: Value,
-        f: Value,
-        g: Value,
-        h: Value,
-        i: Value,
-    ) -> Value {
-        let res = match std::panic::catch_unwind(AssertUnwindSafe(|| {
-            self.call_convert_value(a, b, c, d, e, f, g, h, i)
-        })) {
-            Ok(v) => v,
-            Err(e) => Err(Error::from_panic(e)),
-        };
-        match res {
-            Ok(v) => v,
-            Err(e) => raise(e),
-        }
-    }
-}
-
-/// Helper type for wrapping a function as a Ruby method ignoring self and
-/// taking 10 arguments, with type conversions and error handling.
-///
-/// See the [`function`] macro.
-#[doc(hidden)]
-pub struct Function10<Func, A, B, C, D, E, F, G, H, I, J, Res> {
-    func: Func,
-    a: PhantomData<A>,
-    b: PhantomData<B>,
-    c: PhantomData<C>,
-    d: PhantomData<D>,
-    e: PhantomData<E>,
-    f: PhantomData<F>,
-    g: PhantomData<G>,
-    h: PhantomData<H>,
-    i: PhantomData<I>,
-    j: PhantomData<J>,
-    res: PhantomData<Res>,
-}
-
-#[allow(missing_docs)]
-impl<Func, A, B, C, D, E, F, G, H, I, J, Res> Function10<Func, A, B, C, D, E, F, G, H, I, J, Res>
-where
-    Func: Fn(A, B, C, D, E, F, G, H, I, J) -> Res,
-    A: TryConvert,
-    B: TryConvert,
-    C: TryConvert,
-    D: TryConvert,
-    E: TryConvert,
-    F: TryConvert,
-    G: TryConvert,
-    H: TryConvert,
-    I: TryConvert,
-    J: TryConvert,
-    Res: ReturnValue,
-{
-    #[inline]
-    pub fn new(func: Func) -> Self {
-        Self {
-            func,
-            a: Default::default(),
-            b: Default::default(),
-            c: Default::default(),
-            d: Default::default(),
-            e: Default::default(),
-            f: Default::default(),
-            g: Default::default(),
-            h: Default::default(),
-            i: Default::default(),
-            j: Default::default(),
-            res: Default::default(),
-        }
-    }
-
-    #[inline]
-    unsafe fn call_convert_value(
-        self,
-        a: Value,
-        b: Value,
-        c: Value,
-        d: Value,
-        e: Value,
-        f: Value,
-        g: Value,
-        h: Value,
-        i: Value,
-        j: Value,
-    ) -> Result<Value, Error> {
-        (self.func)(
-            a.try_convert()?,
-            b.try_convert()?,
-            c.try_convert()?,
-            d.try_convert()?,
-            e.try_convert()?,
-            f.try_convert()?,
-            g.try_convert()?,
-            h.try_convert()?,
-            i.try_convert()?,
-            j.try_convert()?,
-        )
-        .into_return_value()
-    }
-
-    #[inline]
-    pub unsafe fn call_handle_error(
-        self,
-        a: Value,
-        b: Value,
-        c: Value,
-        d: Value,
-        e: Value,
-        f: Value,
-        g: Value,
-        h: Value,
-        i: Value,
-        j: Value,
-    ) -> Value {
-        let res = match std::panic::catch_unwind(AssertUnwindSafe(|| {
-            self.call_convert_value(a, b, c, d, e, f, g, h, i, j)
-        })) {
-            Ok(v) => v,
-            Err(e) => Err(Error::from_panic(e)),
-        };
-        match res {
-            Ok(v) => v,
-            Err(e) => raise(e),
-        }
-    }
-}
-
-/// Helper type for wrapping a function as a Ruby method ignoring self and
-/// taking 11 arguments, with type conversions and error handling.
-///
-/// See the [`function`] macro.
-#[doc(hidden)]
-pub struct Function11<Func, A, B, C, D, E, F, G, H, I, J, K, Res> {
-    func: Func,
-    a: PhantomData<A>,
-    b: PhantomData<B>,
-    c: PhantomData<C>,
-    d: PhantomData<D>,
-    e: PhantomData<E>,
-    f: PhantomData<F>,
-    g: PhantomData<G>,
-    h: PhantomData<H>,
-    i: PhantomData<I>,
-    j: PhantomData<J>,
-    k: PhantomData<K>,
-    res: PhantomData<Res>,
-}
-
-#[allow(missing_docs)]
-impl<Func, A, B, C, D, E, F, G, H, I, J, K, Res>
-    Function11<Func, A, B, C, D, E, F, G, H, I, J, K, Res>
-where
-    Func: Fn(A, B, C, D, E, F, G, H, I, J, K) -> Res,
-    A: TryConvert,
-    B: TryConvert,
-    C: TryConvert,
-    D: TryConvert,
-    E: TryConvert,
-    F: TryConvert,
-    G: TryConvert,
-    H: TryConvert,
-    I: TryConvert,
-    J: TryConvert,
-    K: TryConvert,
-    Res: ReturnValue,
-{
-    #[inline]
-    pub fn new(func: Func) -> Self {
-        Self {
-            func,
-            a: Default::default(),
-            b: Default::default(),
-            c: Default::default(),
-            d: Default::default(),
-            e: Default::default(),
-            f: Default::default(),
-            g: Default::default(),
-            h: Default::default(),
-            i: Default::default(),
-            j: Default::default(),
-            k: Default::default(),
-            res: Default::default(),
-        }
-    }
-
-    #[inline]
-    unsafe fn call_convert_value(
-        self,
-        a: Value,
-        b: Value,
-        c: Value,
-        d: Value,
-        e: Value,
-        f: Value,
-        g: Value,
-        h: Value,
-        i: Value,
-        j: Value,
-        k: Value,
-    ) -> Result<Value, Error> {
-        (self.func)(
-            a.try_convert()?,
-            b.try_convert()?,
-            c.try_convert()?,
-            d.try_convert()?,
-            e.try_convert()?,
-            f.try_convert()?,
-            g.try_convert()?,
-            h.try_convert()?,
-            i.try_convert()?,
-            j.try_convert()?,
-            k.try_convert()?,
-        )
-        .into_return_value()
-    }
-
-    #[inline]
-    pub unsafe fn call_handle_error(
-        self,
-        a: Value,
-        b: Value,
-        c: Value,
-        d: Value,
-        e: Value,
-        f: Value,
-        g: Value,
-        h: Value,
-        i: Value,
-        j: Value,
-        k: Value,
-    ) -> Value {
-        let res = match std::panic::catch_unwind(AssertUnwindSafe(|| {
-            self.call_convert_value(a, b, c, d, e, f, g, h, i, j, k)
-        })) {
-            Ok(v) => v,
-            Err(e) => Err(Error::from_panic(e)),
-        };
-        match res {
-            Ok(v) => v,
-            Err(e) => raise(e),
-        }
-    }
-}
-
-/// Helper type for wrapping a function as a Ruby method ignoring self and
-/// taking 12 arguments, with type conversions and error handling.
-///
-/// See the [`function`] macro.
-#[doc(hidden)]
-pub struct Function12<Func, A, B, C, D, E, F, G, H, I, J, K, L, Res> {
-    func: Func,
-    a: PhantomData<A>,
-    b: PhantomData<B>,
-    c: PhantomData<C>,
-    d: PhantomData<D>,
-    e: PhantomData<E>,
-    f: PhantomData<F>,
-    g: PhantomData<G>,
-    h: PhantomData<H>,
-    i: PhantomData<I>,
-    j: PhantomData<J>,
-    k: PhantomData<K>,
-    l: PhantomData<L>,
-    res: PhantomData<Res>,
-}
-
-#[allow(missing_docs)]
-impl<Func, A, B, C, D, E, F, G, H, I, J, K, L, Res>
-    Function12<Func, A, B, C, D, E, F, G, H, I, J, K, L, Res>
-where
-    Func: Fn(A, B, C, D, E, F, G, H, I, J, K, L) -> Res,
-    A: TryConvert,
-    B: TryConvert,
-    C: TryConvert,
-    D: TryConvert,
-    E: TryConvert,
-    F: TryConvert,
-    G: TryConvert,
-    H: TryConvert,
-    I: TryConvert,
-    J: TryConvert,
-    K: TryConvert,
-    L: TryConvert,
-    Res: ReturnValue,
-{
-    #[inline]
-    pub fn new(func: Func) -> Self {
-        Self {
-            func,
-            a: Default::default(),
-            b: Default::default(),
-            c: Default::default(),
-            d: Default::default(),
-            e: Default::default(),
-            f: Default::default(),
-            g: Default::default(),
-            h: Default::default(),
-            i: Default::default(),
-            j: Default::default(),
-            k: Default::default(),
-            l: Default::default(),
-            res: Default::default(),
-        }
-    }
-
-    #[inline]
-    unsafe fn call_convert_value(
-        self,
-        a: Value,
-        b: Value,
-        c: Value,
-        d: Value,
-        e: Value,
-        f: Value,
-        g: Value,
-        h: Value,
-        i: Value,
-        j: Value,
-        k: Value,
-        l: Value,
-    ) -> Result<Value, Error> {
-        (self.func)(
-            a.try_convert()?,
-            b.try_convert()?,
-            c.try_convert()?,
-            d.try_convert()?,
-            e.try_convert()?,
-            f.try_convert()?,
-            g.try_convert()?,
-            h.try_convert()?,
-            i.try_convert()?,
-            j.try_convert()?,
-            k.try_convert()?,
-            l.try_convert()?,
-        )
-        .into_return_value()
-    }
-
-    #[inline]
-    pub unsafe fn call_handle_error(
-        self,
-        a: Value,
-        b: Value,
-        c: Value,
-        d: Value,
-        e: Value,
-        f: Value,
-        g: Value,
-        h: Value,
-        i: Value,
-        j: Value,
-        k: Value,
-        l: Value,
-    ) -> Value {
-        let res = match std::panic::catch_unwind(AssertUnwindSafe(|| {
-            self.call_convert_value(a, b, c, d, e, f, g, h, i, j, k, l)
-        })) {
-            Ok(v) => v,
-            Err(e) => Err(Error::from_panic(e)),
-        };
-        match res {
-            Ok(v) => v,
-            Err(e) => raise(e),
-        }
-    }
-}
-
-/// Helper type for wrapping a function as a Ruby method ignoring self and
-/// taking 13 arguments, with type conversions and error handling.
-///
-/// See the [`function`] macro.
-#[doc(hidden)]
-pub struct Function13<Func, A, B, C, D, E, F, G, H, I, J, K, L, M, Res> {
-    func: Func,
-    a: PhantomData<A>,
-    b: PhantomData<B>,
-    c: PhantomData<C>,
-    d: PhantomData<D>,
-    e: PhantomData<E>,
-    f: PhantomData<F>,
-    g: PhantomData<G>,
-    h: PhantomData<H>,
-    i: PhantomData<I>,
-    j: PhantomData<J>,
-    k: PhantomData<K>,
-    l: PhantomData<L>,
-    m: PhantomData<M>,
-    res: PhantomData<Res>,
-}
-
-#[allow(missing_docs)]
-impl<Func, A, B, C, D, E, F, G, H, I, J, K, L, M, Res>
-    Function13<Func, A, B, C, D, E, F, G, H, I, J, K, L, M, Res>
-where
-    Func: Fn(A, B, C, D, E, F, G, H, I, J, K, L, M) -> Res,
-    A: TryConvert,
-    B: TryConvert,
-    C: TryConvert,
-    D: TryConvert,
-    E: TryConvert,
-    F: TryConvert,
-    G: TryConvert,
-    H: TryConvert,
-    I: TryConvert,
-    J: TryConvert,
-    K: TryConvert,
-    L: TryConvert,
-    M: TryConvert,
-    Res: ReturnValue,
-{
-    #[inline]
-    pub fn new(func: Func) -> Self {
-        Self {
-            func,
-            a: Default::default(),
-            b: Default::default(),
-            c: Default::default(),
-            d: Default::default(),
-            e: Default::default(),
-            f: Default::default(),
-            g: Default::default(),
-            h: Default::default(),
-            i: Default::default(),
-            j: Default::default(),
-            k: Default::default(),
-            l: Default::default(),
-            m: Default::default(),
-            res: Default::default(),
-        }
-    }
-
-    #[inline]
-    unsafe fn call_convert_value(
-        self,
-        a: Value,
-        b: Value,
-        c: Value,
-        d: Value,
-        e: Value,
-        f: Value,
-        g: Value,
-        h: Value,
-        i: Value,
-        j: Value,
-        k: Value,
-        l: Value,
-        m: Value,
-    ) -> Result<Value, Error> {
-        (self.func)(
-            a.try_convert()?,
-            b.try_convert()?,
-            c.try_convert()?,
-            d.try_convert()?,
-            e.try_convert()?,
-            f.try_convert()?,
-            g.try_convert()?,
-            h.try_convert()?,
-            i.try_convert()?,
-            j.try_convert()?,
-            k.try_convert()?,
-            l.try_convert()?,
-            m.try_convert()?,
-        )
-        .into_return_value()
-    }
-
-    #[inline]
-    pub unsafe fn call_handle_error(
-        self,
-        a: Value,
-        b: Value,
-        c: Value,
-        d: Value,
-        e: Value,
-        f: Value,
-        g: Value,
-        h: Value,
-        i: Value,
-        j: Value,
-        k: Value,
-        l: Value,
-        m: Value,
-    ) -> Value {
-        let res = match std::panic::catch_unwind(AssertUnwindSafe(|| {
-            self.call_convert_value(a, b, c, d, e, f, g, h, i, j, k, l, m)
-        })) {
-            Ok(v) => v,
-            Err(e) => Err(Error::from_panic(e)),
-        };
-        match res {
-            Ok(v) => v,
-            Err(e) => raise(e),
-        }
-    }
-}
-
-/// Helper type for wrapping a function as a Ruby method ignoring self and
-/// taking 14 arguments, with type conversions and error handling.
-///
-/// See the [`function`] macro.
-#[doc(hidden)]
-pub struct Function14<Func, A, B, C, D, E, F, G, H, I, J, K, L, M, N, Res> {
-    func: Func,
-    a: PhantomData<A>,
-    b: PhantomData<B>,
-    c: PhantomData<C>,
-    d: PhantomData<D>,
-    e: PhantomData<E>,
-    f: PhantomData<F>,
-    g: PhantomData<G>,
-    h: PhantomData<H>,
-    i: PhantomData<I>,
-    j: PhantomData<J>,
-    k: PhantomData<K>,
-    l: PhantomData<L>,
-    m: PhantomData<M>,
-    n: PhantomData<N>,
-    res: PhantomData<Res>,
-}
-
-#[allow(missing_docs)]
-impl<Func, A, B, C, D, E, F, G, H, I, J, K, L, M, N, Res>
-    Function14<Func, A, B, C, D, E, F, G, H, I, J, K, L, M, N, Res>
-where
-    Func: Fn(A, B, C, D, E, F, G, H, I, J, K, L, M, N) -> Res,
-    A: TryConvert,
-    B: TryConvert,
-    C: TryConvert,
-    D: TryConvert,
-    E: TryConvert,
-    F: TryConvert,
-    G: TryConvert,
-    H: TryConvert,
-    I: TryConvert,
-    J: TryConvert,
-    K: TryConvert,
-    L: TryConvert,
-    M: TryConvert,
-    N: TryConvert,
-    Res: ReturnValue,
-{
-    #[inline]
-    pub fn new(func: Func) -> Self {
-        Self {
-            func,
-            a: Default::default(),
-            b: Default::default(),
-            c: Default::default(),
-            d: Default::default(),
-            e: Default::default(),
-            f: Default::default(),
-            g: Default::default(),
-            h: Default::default(),
-            i: Default::default(),
-            j: Default::default(),
-            k: Default::default(),
-            l: Default::default(),
-            m: Default::default(),
-            n: Default::default(),
-            res: Default::default(),
-        }
-    }
-
-    #[inline]
-    unsafe fn call_convert_value(
-        self,
-        a: Value,
-        b: Value,
-        c: Value,
-        d: Value,
-        e: Value,
-        f: Value,
-        g: Value,
-        h: Value,
-        i: Value,
-        j: Value,
-        k: Value,
-        l: Value,
-        m: Value,
-        n: Value,
-    ) -> Result<Value, Error> {
-        (self.func)(
-            a.try_convert()?,
-            b.try_convert()?,
-            c.try_convert()?,
-            d.try_convert()?,
-            e.try_convert()?,
-            f.try_convert()?,
-            g.try_convert()?,
-            h.try_convert()?,
-            i.try_convert()?,
-            j.try_convert()?,
-            k.try_convert()?,
-            l.try_convert()?,
-            m.try_convert()?,
-            n.try_convert()?,
-        )
-        .into_return_value()
-    }
-
-    #[inline]
-    pub unsafe fn call_handle_error(
-        self,
-        a: Value,
-        b: Value,
-        c: Value,
-        d: Value,
-        e: Value,
-        f: Value,
-        g: Value,
-        h: Value,
-        i: Value,
-        j: Value,
-        k: Value,
-        l: Value,
-        m: Value,
-        n: Value,
-    ) -> Value {
-        let res = match std::panic::catch_unwind(AssertUnwindSafe(|| {
-            self.call_convert_value(a, b, c, d, e, f, g, h, i, j, k, l, m, n)
-        })) {
-            Ok(v) => v,
-            Err(e) => Err(Error::from_panic(e)),
-        };
-        match res {
-            Ok(v) => v,
-            Err(e) => raise(e),
-        }
-    }
-}
-
-/// Helper type for wrapping a function as a Ruby method ignoring self and
-/// taking 15 arguments, with type conversions and error handling.
-///
-/// See the [`function`] macro.
-#[doc(hidden)]
-pub struct Function15<Func, A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, Res> {
-    func: Func,
-    a: PhantomData<A>,
-    b: PhantomData<B>,
-    c: PhantomData<C>,
-    d: PhantomData<D>,
-    e: PhantomData<E>,
-    f: PhantomData<F>,
-    g: PhantomData<G>,
-    h: PhantomData<H>,
-    i: PhantomData<I>,
-    j: PhantomData<J>,
-    k: PhantomData<K>,
-    l: PhantomData<L>,
-    m: PhantomData<M>,
-    n: PhantomData<N>,
-    o: PhantomData<O>,
-    res: PhantomData<Res>,
-}
-
-#[allow(missing_docs)]
-impl<Func, A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, Res>
-    Function15<Func, A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, Res>
-where
-    Func: Fn(A, B, C, D, E, F, G, H, I, J, K, L, M, N, O) -> Res,
-    A: TryConvert,
-    B: TryConvert,
-    C: TryConvert,
-    D: TryConvert,
-    E: TryConvert,
-    F: TryConvert,
-    G: TryConvert,
-    H: TryConvert,
-    I: TryConvert,
-    J: TryConvert,
-    K: TryConvert,
-    L: TryConvert,
-    M: TryConvert,
-    N: TryConvert,
-    O: TryConvert,
-    Res: ReturnValue,
-{
-    #[inline]
-    pub fn new(func: Func) -> Self {
-        Self {
-            func,
-            a: Default::default(),
-            b: Default::default(),
-            c: Default::default(),
-            d: Default::default(),
-            e: Default::default(),
-            f: Default::default(),
-            g: Default::default(),
-            h: Default::default(),
-            i: Default::default(),
-            j: Default::default(),
-            k: Default::default(),
-            l: Default::default(),
-            m: Default::default(),
-            n: Default::default(),
-            o: Default::default(),
-            res: Default::default(),
-        }
-    }
-
-    #[inline]
-    unsafe fn call_convert_value(
-        self,
-        a: Value,
-        b: Value,
-        c: Value,
-        d: Value,
-        e: Value,
-        f: Value,
-        g: Value,
-        h: Value,
-        i: Value,
-        j: Value,
-        k: Value,
-        l: Value,
-        m: Value,
-        n: Value,
-        o: Value,
-    ) -> Result<Value, Error> {
-        (self.func)(
-            a.try_convert()?,
-            b.try_convert()?,
-            c.try_convert()?,
-            d.try_convert()?,
-            e.try_convert()?,
-            f.try_convert()?,
-            g.try_convert()?,
-            h.try_convert()?,
-            i.try_convert()?,
-            j.try_convert()?,
-            k.try_convert()?,
-            l.try_convert()?,
-            m.try_convert()?,
-            n.try_convert()?,
-            o.try_convert()?,
-        )
-        .into_return_value()
-    }
-
-    #[inline]
-    pub unsafe fn call_handle_error(
-        self,
-        a: Value,
-        b: Value,
-        c: Value,
-        d: Value,
-        e: Value,
-        f: Value,
-        g: Value,
-        h: Value,
-        i: Value,
-        j: Value,
-        k: Value,
-        l: Value,
-        m: Value,
-        n: Value,
-        o: Value,
-    ) -> Value {
-        let res = match std::panic::catch_unwind(AssertUnwindSafe(|| {
-            self.call_convert_value(a, b, c, d, e, f, g, h, i, j, k, l, m, n, o)
-        })) {
-            Ok(v) => v,
-            Err(e) => Err(Error::from_panic(e)),
-        };
-        match res {
-            Ok(v) => v,
-            Err(e) => raise(e),
-        }
-    }
-}
-
-/// Helper type for wrapping a function as a Ruby method ignoring self and
-/// taking 16 arguments, with type conversions and error handling.
-///
-/// See the [`function`] macro.
-#[doc(hidden)]
-pub struct Function16<Func, A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Res> {
-    func: Func,
-    a: PhantomData<A>,
-    b: PhantomData<B>,
-    c: PhantomData<C>,
-    d: PhantomData<D>,
-    e: PhantomData<E>,
-    f: PhantomData<F>,
-    g: PhantomData<G>,
-    h: PhantomData<H>,
-    i: PhantomData<I>,
-    j: PhantomData<J>,
-    k: PhantomData<K>,
-    l: PhantomData<L>,
-    m: PhantomData<M>,
-    n: PhantomData<N>,
-    o: PhantomData<O>,
-    p: PhantomData<P>,
-    res: PhantomData<Res>,
-}
-
-#[allow(missing_docs)]
-impl<Func, A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Res>
-    Function16<Func, A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Res>
-where
-    Func: Fn(A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P) -> Res,
-    A: TryConvert,
-    B: TryConvert,
-    C: TryConvert,
-    D: TryConvert,
-    E: TryConvert,
-    F: TryConvert,
-    G: TryConvert,
-    H: TryConvert,
-    I: TryConvert,
-    J: TryConvert,
-    K: TryConvert,
-    L: TryConvert,
-    M: TryConvert,
-    N: TryConvert,
-    O: TryConvert,
-    P: TryConvert,
-    Res: ReturnValue,
-{
-    #[inline]
-    pub fn new(func: Func) -> Self {
-        Self {
-            func,
-            a: Default::default(),
-            b: Default::default(),
-            c: Default::default(),
-            d: Default::default(),
-            e: Default::default(),
-            f: Default::default(),
-            g: Default::default(),
-            h: Default::default(),
-            i: Default::default(),
-            j: Default::default(),
-            k: Default::default(),
-            l: Default::default(),
-            m: Default::default(),
-            n: Default::default(),
-            o: Default::default(),
-            p: Default::default(),
-            res: Default::default(),
-        }
-    }
-
-    #[inline]
-    unsafe fn call_convert_value(
-        self,
-        a: Value,
-        b: Value,
-        c: Value,
-        d: Value,
-        e: Value,
-        f: Value,
-        g: Value,
-        h: Value,
-        i: Value,
-        j: Value,
-        k: Value,
-        l: Value,
-        m: Value,
-        n: Value,
-        o: Value,
-        p: Value,
-    ) -> Result<Value, Error> {
-        (self.func)(
-            a.try_convert()?,
-            b.try_convert()?,
-            c.try_convert()?,
-            d.try_convert()?,
-            e.try_convert()?,
-            f.try_convert()?,
-            g.try_convert()?,
-            h.try_convert()?,
-            i.try_convert()?,
-            j.try_convert()?,
-            k.try_convert()?,
-            l.try_convert()?,
-            m.try_convert()?,
-            n.try_convert()?,
-            o.try_convert()?,
-            p.try_convert()?,
-        )
-        .into_return_value()
-    }
-
-    #[inline]
-    pub unsafe fn call_handle_error(
-        self,
-        a: Value,
-        b: Value,
-        c: Value,
-        d: Value,
-        e: Value,
-        f: Value,
-        g: Value,
-        h: Value,
-        i: Value,
-        j: Value,
-        k: Value,
-        l: Value,
-        m: Value,
-        n: Value,
-        o: Value,
-        p: Value,
-    ) -> Value {
-        let res = match std::panic::catch_unwind(AssertUnwindSafe(|| {
-            self.call_convert_value(a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p)
-        })) {
-            Ok(v) => v,
-            Err(e) => Err(Error::from_panic(e)),
-        };
-        match res {
-            Ok(v) => v,
-            Err(e) => raise(e),
-        }
-    }
-}
-
-/// Wrap a Rust function item with Ruby type conversion and error handling,
-/// ignoring Ruby's `self` argument.
-///
-/// This macro wraps the given function and returns a function pointer
-/// implementing the [`Method`] trait, suitable for passing to functions that
-/// define Ruby methods such as
-/// [`define_method`](crate::module::Module::define_method).
-///
-/// Ruby code implicitly always has a `self` parameter available. In the
-/// extention API this is passed explicitly. The wrapper this macro generates
-/// ignores that argument, and does not pass it to the wrapped function.
-///
-/// The values `-2` and `-1` for `arity` have special meaning. Both indicate
-/// functions with any number of arguments, with `-2` the arguments are passed
-/// as a [`RArray`], with `-1` they are passed as a slice of [`Value`]s.
-/// Arity of `-1` can be used with [`scan_args`](crate::scan_args::scan_args)
-/// and [`get_kwargs`](crate::scan_args::get_kwargs) for more complex method
-/// signatures.
-///
-/// | Arity | Signature                                     |
-/// |-------|-----------------------------------------------|
-/// |    -2 | `fn(arguments: RArray) -> Result<R, Error>`   |
-/// |    -1 | `fn(arguments: &[Value]) -> Result<R, Error>` |
-/// |     0 | `fn()-> Result<R, Error>`                     |
-/// |     1 | `fn(arg1: T) -> Result<R, Error>`             |
-/// |     2 | `fn(arg1: T, arg2: U) -> Result<R, Error>`    |
-/// |   ... | ...                                           |
-/// |    16 | ...                                           |
-///
-/// Where `T`, `U`, and so on are any types that implement `TryConvert`,
-/// and `R` implements `Into<Value>`. It is also possible to return just `R`
-/// rather than a `Result` for functions that will never error, and omit the
-/// return value (i.e. return `()`) for a function that returns `nil` to Ruby.
-/// See [`ReturnValue`] for more details on what can be returned.
-///
-/// See the [`method`] macro for cases where the `self` argument is required.
-///
-/// # Examples
-///
-/// ```
-/// fn distance(a: (f64, f64), b: (f64, f64)) -> f64 {
-///     ((b.0 - a.0).powi(2) + (b.0 - a.0).powi(2)).sqrt()
-/// }
-///
-/// #[magnus::init]
-/// fn init() {
-///     magnus::define_global_function("distance", magnus::function!(distance, 2));
-/// }
-/// ```
-#[macro_export]
-macro_rules! function {
-    ($name:expr, -2) => {{
-        unsafe extern "C" fn anon(rb_self: $crate::Value, args: $crate::RArray) -> $crate::Value {
-            $crate::method::FunctionRbAry::new($name).call_handle_error(args)
-        }
-        anon as unsafe extern "C" fn($crate::Value, $crate::RArray) -> $crate::Value
-    }};
-    ($name:expr, -1) => {{
-        unsafe extern "C" fn anon(
-            argc: std::os::raw::c_int,
-            argv: *const $crate::Value,
-            rb_self: $crate::Value,
-        ) -> $crate::Value {
-            $crate::method::FunctionCAry::new($name).call_handle_error(argc, argv)
-        }
-        anon as unsafe extern "C" fn(
-            std::os::raw::c_int,
-            *const $crate::Value,
-            $crate::Value,
-        ) -> $crate::Value
-    }};
-    ($name:expr, 0) => {{
-        unsafe extern "C" fn anon(rb_self: $crate::Value) -> $crate::Value {
-            $crate::method::Function0::new($name).call_handle_error()
-        }
-        anon as unsafe extern "C" fn($crate::Value) -> $crate::Value
-    }};
-    ($name:expr, 1) => {{
-        unsafe extern "C" fn anon(rb_self: $crate::Value, a: $crate::Value) -> $crate::Value {
-            $crate::method::Function1::new($name).call_handle_error(a)
-        }
-        anon as unsafe extern "C" fn($crate::Value, $crate::Value) -> $crate::Value
-    }};
-    ($name:expr, 2) => {{
-        unsafe extern "C" fn anon(
-            rb_self: $crate::Value,
-            a: $crate::Value,
-            b: $crate::Value,
-        ) -> $crate::Value {
-            $crate::method::Function2::new($name).call_handle_error(a, b)
-        }
-        anon as unsafe extern "C" fn($crate::Value, $crate::Value, $crate::Value) -> $crate::Value
-    }};
-    ($name:expr, 3) => {{
-        unsafe extern "C" fn anon(
-            rb_self: $crate::Value,
-            a: $crate::Value,
-            b: $crate::Value,
-            c: $crate::Value,
-        ) -> $crate::Value {
-            $crate::method::Function3::new($name).call_handle_error(a, b, c)
-        }
-        anon as unsafe extern "C" fn(
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-        ) -> $crate::Value
-    }};
-    ($name:expr, 4) => {{
-        unsafe extern "C" fn anon(
-            rb_self: $crate::Value,
-            a: $crate::Value,
-            b: $crate::Value,
-            c: $crate::Value,
-            d: $crate::Value,
-        ) -> $crate::Value {
-            $crate::method::Function4::new($name).call_handle_error(a, b, c, d)
-        }
-        anon as unsafe extern "C" fn(
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-        ) -> $crate::Value
-    }};
-    ($name:expr, 5) => {{
-        unsafe extern "C" fn anon(
-            rb_self: $crate::Value,
-            a: $crate::Value,
-            b: $crate::Value,
-            c: $crate::Value,
-            d: $crate::Value,
-            e: $crate::Value,
-        ) -> $crate::Value {
-            $crate::method::Function5::new($name).call_handle_error(a, b, c, d, e)
-        }
-        anon as unsafe extern "C" fn(
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-        ) -> $crate::Value
-    }};
-    ($name:expr, 6) => {{
-        unsafe extern "C" fn anon(
-            rb_self: $crate::Value,
-            a: $crate::Value,
-            b: $crate::Value,
-            c: $crate::Value,
-            d: $crate::Value,
-            e: $crate::Value,
-            f: $crate::Value,
-        ) -> $crate::Value {
-            $crate::method::Function6::new($name).call_handle_error(a, b, c, d, e, f)
-        }
-        anon as unsafe extern "C" fn(
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-        ) -> $crate::Value
-    }};
-    ($name:expr, 7) => {{
-        unsafe extern "C" fn anon(
-            rb_self: $crate::Value,
-            a: $crate::Value,
-            b: $crate::Value,
-            c: $crate::Value,
-            d: $crate::Value,
-            e: $crate::Value,
-            f: $crate::Value,
-            g: $crate::Value,
-        ) -> $crate::Value {
-            $crate::method::Function7::new($name).call_handle_error(a, b, c, d, e, f, g)
-        }
-        anon as unsafe extern "C" fn(
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-        ) -> $crate::Value
-    }};
-    ($name:expr, 8) => {{
-        unsafe extern "C" fn anon(
-            rb_self: $crate::Value,
-            a: $crate::Value,
-            b: $crate::Value,
-            c: $crate::Value,
-            d: $crate::Value,
-            e: $crate::Value,
-            f: $crate::Value,
-            g: $crate::Value,
-            h: $crate::Value,
-        ) -> $crate::Value {
-            $crate::method::Function8::new($name).call_handle_error(a, b, c, d, e, f, g, h)
-        }
-        anon as unsafe extern "C" fn(
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-        ) -> $crate::Value
-    }};
-    ($name:expr, 9) => {{
-        unsafe extern "C" fn anon(
-            rb_self: $crate::Value,
-            a: $crate::Value,
-            b: $crate::Value,
-            c: $crate::Value,
-            d: $crate::Value,
-            e: $crate::Value,
-            f: $crate::Value,
-            g: $crate::Value,
-            h: $crate::Value,
-            i: $crate::Value,
-        ) -> $crate::Value {
-            $crate::method::Function9::new($name).call_handle_error(a, b, c, d, e, f, g, h, i)
-        }
-        anon as unsafe extern "C" fn(
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-        ) -> $crate::Value
-    }};
-    ($name:expr, 10) => {{
-        unsafe extern "C" fn anon(
-            rb_self: $crate::Value,
-            a: $crate::Value,
-            b: $crate::Value,
-            c: $crate::Value,
-            d: $crate::Value,
-            e: $crate::Value,
-            f: $crate::Value,
-            g: $crate::Value,
-            h: $crate::Value,
-            i: $crate::Value,
-            j: $crate::Value,
-        ) -> $crate::Value {
-            $crate::method::Function10::new($name).call_handle_error(a, b, c, d, e, f, g, h, i, j)
-        }
-        anon as unsafe extern "C" fn(
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-        ) -> $crate::Value
-    }};
-    ($name:expr, 11) => {{
-        unsafe extern "C" fn anon(
-            rb_self: $crate::Value,
-            a: $crate::Value,
-            b: $crate::Value,
-            c: $crate::Value,
-            d: $crate::Value,
-            e: $crate::Value,
-            f: $crate::Value,
-            g: $crate::Value,
-            h: $crate::Value,
-            i: $crate::Value,
-            j: $crate::Value,
-            k: $crate::Value,
-        ) -> $crate::Value {
-            $crate::method::Function11::new($name)
-                .call_handle_error(a, b, c, d, e, f, g, h, i, j, k)
-        }
-        anon as unsafe extern "C" fn(
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-        ) -> $crate::Value
-    }};
-    ($name:expr, 12) => {{
-        unsafe extern "C" fn anon(
-            rb_self: $crate::Value,
-            a: $crate::Value,
-            b: $crate::Value,
-            c: $crate::Value,
-            d: $crate::Value,
-            e: $crate::Value,
-            f: $crate::Value,
-            g: $crate::Value,
-            h: $crate::Value,
-            i: $crate::Value,
-            j: $crate::Value,
-            k: $crate::Value,
-            l: $crate::Value,
-        ) -> $crate::Value {
-            $crate::method::Function12::new($name)
-                .call_handle_error(a, b, c, d, e, f, g, h, i, j, k, l)
+/// This macro wraps the given function and returns a function pointer
+/// implementing the [`Method`] trait, suitable for passing to functions that
+/// define Ruby methods such as
+/// [`define_method`](crate::module::Module::define_method).
+///
+/// Ruby code implicitly always has a `self` parameter available. In the
+/// extention API this is passed explicitly. The wrapper this macro generates
+/// ignores that argument, and does not pass it to the wrapped function.
+///
+/// The values `-2` and `-1` for `arity` have special meaning. Both indicate
+/// functions with any number of arguments, with `-2` the arguments are passed
+/// as a [`RArray`], with `-1` they are passed as a slice of [`Value`]s.
+/// Arity of `-1` can be used with [`scan_args`](crate::scan_args::scan_args)
+/// and [`get_kwargs`](crate::scan_args::get_kwargs) for more complex method
+/// signatures.
+///
+/// | Arity | Signature                                     |
+/// |-------|-----------------------------------------------|
+/// |    -2 | `fn(arguments: RArray) -> Result<R, Error>`   |
+/// |    -1 | `fn(arguments: &[Value]) -> Result<R, Error>` |
+/// |     0 | `fn()-> Result<R, Error>`                     |
+/// |     1 | `fn(arg1: T) -> Result<R, Error>`             |
+/// |     2 | `fn(arg1: T, arg2: U) -> Result<R, Error>`    |
+/// |   ... | ...                                           |
+/// |    15 | ...                                           |
+///
+/// Ruby limits methods to an arity of 15. Functions with a greater arity, up
+/// to 32, are defined with an arity of `-1` and the number of arguments
+/// checked when called. As the arity can't be checked against the function's
+/// signature at compile time, evaluating the macro panics if they differ.
+///
+/// Where `T`, `U`, and so on are any types that implement `TryConvert`,
+/// and `R` implements `Into<Value>`. It is also possible to return just `R`
+/// rather than a `Result` for functions that will never error, and omit the
+/// return value (i.e. return `()`) for a function that returns `nil` to Ruby.
+/// See [`ReturnValue`] for more details on what can be returned.
+///
+/// In place of `arity` a string of Ruby-style parameters can be given, such
+/// as `"a, b = nil, *rest, **kwargs, &block"`. See the [`method`] macro for
+/// details.
+///
+/// See the [`method`] macro for cases where the `self` argument is required.
+///
+/// # Examples
+///
+/// ```
+/// fn distance(a: (f64, f64), b: (f64, f64)) -> f64 {
+///     ((b.0 - a.0).powi(2) + (b.0 - a.0).powi(2)).sqrt()
+/// }
+///
+/// #[magnus::init]
+/// fn init() {
+///     magnus::define_global_function("distance", magnus::function!(distance, 2));
+/// }
+/// ```
+///
+/// With a parameter list:
+///
+/// ```
+/// use magnus::{Error, RHash, Symbol};
+///
+/// fn greet(name: String, greeting: Option<String>, opts: RHash) -> Result<String, Error> {
+///     let greeting = greeting.unwrap_or_else(|| "Hello".to_owned());
+///     let punct: Option<String> = opts.lookup(Symbol::new("punct"))?;
+///     Ok(format!("{}, {}{}", greeting, name, punct.as_deref().unwrap_or("!")))
+/// }
+///
+/// #[magnus::init]
+/// fn init() {
+///     magnus::define_global_function(
+///         "greet",
+///         magnus::function!(greet, "name, greeting = nil, **opts"),
+///     );
+/// }
+/// ```
+#[macro_export]
+macro_rules! function {
+    ($name:expr, -2) => {{
+        unsafe extern "C" fn anon(rb_self: $crate::Value, args: $crate::RArray) -> $crate::Value {
+            $crate::method::FunctionRbAry::new($name).call_handle_error(args)
         }
-        anon as unsafe extern "C" fn(
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-        ) -> $crate::Value
+        anon as unsafe extern "C" fn($crate::Value, $crate::RArray) -> $crate::Value
     }};
-    ($name:expr, 13) => {{
+    ($name:expr, -1) => {{
         unsafe extern "C" fn anon(
+            argc: std::os::raw::c_int,
+            argv: *const $crate::Value,
             rb_self: $crate::Value,
-            a: $crate::Value,
-            b: $crate::Value,
-            c: $crate::Value,
-            d: $crate::Value,
-            e: $crate::Value,
-            f: $crate::Value,
-            g: $crate::Value,
-            h: $crate::Value,
-            i: $crate::Value,
-            j: $crate::Value,
-            k: $crate::Value,
-            l: $crate::Value,
-            m: $crate::Value,
         ) -> $crate::Value {
-            $crate::method::Function13::new($name)
-                .call_handle_error(a, b, c, d, e, f, g, h, i, j, k, l, m)
+            $crate::method::FunctionCAry::new($name).call_handle_error(argc, argv)
         }
         anon as unsafe extern "C" fn(
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
+            std::os::raw::c_int,
+            *const $crate::Value,
             $crate::Value,
         ) -> $crate::Value
     }};
-    ($name:expr, 14) => {{
+    ($name:expr, 0) => {
+        $crate::__fixed_arity!(@function $name)
+    };
+    ($name:expr, 1) => {
+        $crate::__fixed_arity!(@function $name, a)
+    };
+    ($name:expr, 2) => {
+        $crate::__fixed_arity!(@function $name, a, b)
+    };
+    ($name:expr, 3) => {
+        $crate::__fixed_arity!(@function $name, a, b, c)
+    };
+    ($name:expr, 4) => {
+        $crate::__fixed_arity!(@function $name, a, b, c, d)
+    };
+    ($name:expr, 5) => {
+        $crate::__fixed_arity!(@function $name, a, b, c, d, e)
+    };
+    ($name:expr, 6) => {
+        $crate::__fixed_arity!(@function $name, a, b, c, d, e, f)
+    };
+    ($name:expr, 7) => {
+        $crate::__fixed_arity!(@function $name, a, b, c, d, e, f, g)
+    };
+    ($name:expr, 8) => {
+        $crate::__fixed_arity!(@function $name, a, b, c, d, e, f, g, h)
+    };
+    ($name:expr, 9) => {
+        $crate::__fixed_arity!(@function $name, a, b, c, d, e, f, g, h, i)
+    };
+    ($name:expr, 10) => {
+        $crate::__fixed_arity!(@function $name, a, b, c, d, e, f, g, h, i, j)
+    };
+    ($name:expr, 11) => {
+        $crate::__fixed_arity!(@function $name, a, b, c, d, e, f, g, h, i, j, k)
+    };
+    ($name:expr, 12) => {
+        $crate::__fixed_arity!(@function $name, a, b, c, d, e, f, g, h, i, j, k, l)
+    };
+    ($name:expr, 13) => {
+        $crate::__fixed_arity!(@function $name, a, b, c, d, e, f, g, h, i, j, k, l, m)
+    };
+    ($name:expr, 14) => {
+        $crate::__fixed_arity!(@function $name, a, b, c, d, e, f, g, h, i, j, k, l, m, n)
+    };
+    ($name:expr, 15) => {
+        $crate::__fixed_arity!(@function $name, a, b, c, d, e, f, g, h, i, j, k, l, m, n, o)
+    };
+    ($name:expr, $params:literal) => {{
+        static PARAMS: $crate::method::ParamsCell = $crate::method::ParamsCell::new();
         unsafe extern "C" fn anon(
+            argc: std::os::raw::c_int,
+            argv: *const $crate::Value,
             rb_self: $crate::Value,
-            a: $crate::Value,
-            b: $crate::Value,
-            c: $crate::Value,
-            d: $crate::Value,
-            e: $crate::Value,
-            f: $crate::Value,
-            g: $crate::Value,
-            h: $crate::Value,
-            i: $crate::Value,
-            j: $crate::Value,
-            k: $crate::Value,
-            l: $crate::Value,
-            m: $crate::Value,
-            n: $crate::Value,
         ) -> $crate::Value {
-            $crate::method::Function14::new($name)
-                .call_handle_error(a, b, c, d, e, f, g, h, i, j, k, l, m, n)
+            $crate::method::FunctionParams::new($name, PARAMS.get()).call_handle_error(argc, argv)
         }
+        PARAMS.set($crate::method::FunctionParams::params(&$name, $params));
         anon as unsafe extern "C" fn(
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
+            std::os::raw::c_int,
+            *const $crate::Value,
             $crate::Value,
         ) -> $crate::Value
     }};
-    ($name:expr, 15) => {{
-        unsafe extern "C" fn anon(
-            rb_self: $crate::Value,
-            a: $crate::Value,
-            b: $crate::Value,
-            c: $crate::Value,
-            d: $crate::Value,
-            e: $crate::Value,
-            f: $crate::Value,
-            g: $crate::Value,
-            h: $crate::Value,
-            i: $crate::Value,
-            j: $crate::Value,
-            k: $crate::Value,
-            l: $crate::Value,
-            m: $crate::Value,
-            n: $crate::Value,
-            o: $crate::Value,
-        ) -> $crate::Value {
-            $crate::method::Function15::new($name)
-                .call_handle_error(a, b, c, d, e, f, g, h, i, j, k, l, m, n, o)
+    ($name:expr, $arity:expr) => {
+        compile_error!("arity must be an integer literal of -2 or greater, or a parameter list")
+    };
+}
+
+// Wraps `$name` as an `extern "C"` function taking self and a fixed number of
+// arguments. Used by the `method` and `function` macros, this is an
+// implementation detail and not part of the public API.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __fixed_arity {
+    (@value $arg:ident) => {
+        $crate::Value
+    };
+    (@method $name:expr $(, $arg:ident)*) => {{
+        unsafe extern "C" fn anon(rb_self: $crate::Value $(, $arg: $crate::Value)*) -> $crate::Value {
+            $crate::method::MethodN::new($name).call_handle_error([rb_self $(, $arg)*])
         }
-        anon as unsafe extern "C" fn(
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-        ) -> $crate::Value
+        anon as unsafe extern "C" fn($crate::Value $(, $crate::__fixed_arity!(@value $arg))*) -> $crate::Value
     }};
-    ($name:expr, 16) => {{
-        unsafe extern "C" fn anon(
-            rb_self: $crate::Value,
-            a: $crate::Value,
-            b: $crate::Value,
-            c: $crate::Value,
-            d: $crate::Value,
-            e: $crate::Value,
-            f: $crate::Value,
-            g: $crate::Value,
-            h: $crate::Value,
-            i: $crate::Value,
-            j: $crate::Value,
-            k: $crate::Value,
-            l: $crate::Value,
-            m: $crate::Value,
-            n: $crate::Value,
-            o: $crate::Value,
-            p: $crate::Value,
-        ) -> $crate::Value {
-            $crate::method::Function16::new($name)
-                .call_handle_error(a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p)
+    (@function $name:expr $(, $arg:ident)*) => {{
+        unsafe extern "C" fn anon(rb_self: $crate::Value $(, $arg: $crate::Value)*) -> $crate::Value {
+            $crate::method::FunctionN::new($name).call_handle_error([$($arg),*])
         }
-        anon as unsafe extern "C" fn(
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-            $crate::Value,
-        ) -> $crate::Value
+        anon as unsafe extern "C" fn($crate::Value $(, $crate::__fixed_arity!(@value $arg))*) -> $crate::Value
     }};
-    ($name:expr, $arity:expr) => {
-        compile_error!("arity must be an integer literal between -2..=16")
-    };
 }
@@ -1,6 +1,6 @@
 //! Types and functions for working with Ruby modules.
 
-use std::{ffi::CString, fmt, mem::transmute, ops::Deref};
+use std::{ffi::CString, fmt, mem::transmute, ops::Deref, os::raw::c_int};
 
 use crate::{
     class::RClass,
@@ -9,15 +9,21 @@ use crate::{
     exception,
     method::Method,
     object::Object,
+    r_array::RArray,
+    r_string::RString,
     ruby_sys::{
-        rb_class_inherited_p, rb_const_get, rb_define_class_id_under, rb_define_method_id,
-        rb_define_module_function, rb_define_module_id_under, rb_define_private_method,
-        rb_define_protected_method, rb_mComparable, rb_mEnumerable, rb_mErrno, rb_mFileTest,
-        rb_mGC, rb_mKernel, rb_mMath, rb_mProcess, rb_mWaitReadable, rb_mWaitWritable,
-        rb_module_new, ruby_value_type, VALUE,
+        rb_alias, rb_attr, rb_class_inherited_p, rb_class_instance_methods, rb_const_defined,
+        rb_const_get, rb_const_set, rb_cvar_defined, rb_cvar_get, rb_cvar_set,
+        rb_define_class_id_under, rb_define_method_id, rb_define_module_function,
+        rb_define_module_id_under, rb_define_private_method, rb_define_protected_method,
+        rb_include_module, rb_mComparable, rb_mEnumerable, rb_mErrno, rb_mFileTest, rb_mGC,
+        rb_mKernel, rb_mMath, rb_mProcess, rb_mWaitReadable, rb_mWaitWritable, rb_mod_ancestors,
+        rb_mod_name, rb_module_new, rb_prepend_module, rb_remove_method_id, rb_undef,
+        ruby_value_type, VALUE,
     },
+    symbol::Symbol,
     try_convert::TryConvert,
-    value::{Id, NonZeroValue, Value},
+    value::{Id, NonZeroValue, Value, QNIL},
 };
 
 /// A Value pointer to a RModule struct, Ruby's internal representation of
@@ -115,6 +121,41 @@ impl RModule {
             );
         }
     }
+
+    /// Make the existing method `name` a 'module function'.
+    ///
+    /// This is equivalent to `module_function :name` in Ruby. The method
+    /// will be copied to a public 'class' method on the module, and the
+    /// instance method made private.
+    ///
+    /// Errors if the method `name` is not defined.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RModule};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let module = eval::<RModule>(r#"
+    ///     module Example
+    ///       def self_name
+    ///         "example"
+    ///       end
+    ///       self
+    ///     end
+    /// "#).unwrap();
+    /// module.module_function("self_name").unwrap();
+    ///
+    /// assert_eq!(eval::<String>("Example.self_name").unwrap(), "example");
+    /// assert!(module.module_function("missing").is_err());
+    /// ```
+    pub fn module_function<T>(self, name: T) -> Result<(), Error>
+    where
+        T: Into<Id>,
+    {
+        self.funcall::<_, _, Value>("module_function", (Symbol::from(name.into()),))
+            .map(|_| ())
+    }
 }
 
 impl Deref for RModule {
@@ -242,6 +283,167 @@ pub trait Module: Object + Deref<Target = Value> + Copy {
         res.and_then(|v| v.try_convert())
     }
 
+    /// Set the value for the constant `name` within `self`'s scope.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, Module, RClass};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let class = RClass::new(Default::default()).unwrap();
+    /// class.const_set("EXAMPLE", 42).unwrap();
+    /// assert_eq!(class.const_get::<_, i64>("EXAMPLE").unwrap(), 42);
+    /// ```
+    fn const_set<T, U>(self, name: T, value: U) -> Result<(), Error>
+    where
+        T: Into<Id>,
+        U: Into<Value>,
+    {
+        debug_assert_value!(self);
+        let id = name.into();
+        let value = value.into();
+        debug_assert_value!(value);
+        unsafe {
+            protect(|| {
+                rb_const_set(self.as_rb_value(), id.as_rb_id(), value.as_rb_value());
+                *QNIL
+            })
+        }?;
+        Ok(())
+    }
+
+    /// Returns whether the constant `name` is defined within `self`'s scope,
+    /// including constants inherited from ancestors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{class, Module};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// assert!(class::object().const_defined("String"));
+    /// assert!(!class::object().const_defined("Unknown"));
+    /// ```
+    fn const_defined<T>(self, name: T) -> bool
+    where
+        T: Into<Id>,
+    {
+        debug_assert_value!(self);
+        let id = name.into();
+        unsafe { rb_const_defined(self.as_rb_value(), id.as_rb_id()) != 0 }
+    }
+
+    /// Make the constant `name` within `self`'s scope private.
+    ///
+    /// This is equivalent to `private_constant :name` in Ruby.
+    ///
+    /// Errors if the constant `name` is not defined.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, exception, Module, RClass, Value};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let class = eval::<RClass>("class Example; self; end").unwrap();
+    /// class.const_set("SECRET", 42).unwrap();
+    /// class.private_constant("SECRET").unwrap();
+    ///
+    /// let err = eval::<Value>("Example::SECRET").unwrap_err();
+    /// assert!(err.is_kind_of(exception::name_error()));
+    /// ```
+    fn private_constant<T>(self, name: T) -> Result<(), Error>
+    where
+        T: Into<Id>,
+    {
+        self.funcall::<_, _, Value>("private_constant", (Symbol::from(name.into()),))
+            .map(|_| ())
+    }
+
+    /// Get the value for the class variable `name` within `self`'s scope.
+    ///
+    /// Note, the `@@` is part of the name. Errors if the class variable is
+    /// not defined.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, Module, RClass};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let class = eval::<RClass>("class Example; @@count = 1; self; end").unwrap();
+    /// assert_eq!(class.cvar_get::<_, i64>("@@count").unwrap(), 1);
+    /// assert!(class.cvar_get::<_, i64>("@@missing").is_err());
+    /// ```
+    fn cvar_get<T, U>(self, name: T) -> Result<U, Error>
+    where
+        T: Into<Id>,
+        U: TryConvert,
+    {
+        debug_assert_value!(self);
+        let id = name.into();
+        let res = unsafe { protect(|| Value::new(rb_cvar_get(self.as_rb_value(), id.as_rb_id()))) };
+        res.and_then(|v| v.try_convert())
+    }
+
+    /// Set the value for the class variable `name` within `self`'s scope.
+    ///
+    /// Note, the `@@` is part of the name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Module, RClass};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let class = RClass::new(Default::default()).unwrap();
+    /// class.cvar_set("@@count", 2).unwrap();
+    /// assert_eq!(class.cvar_get::<_, i64>("@@count").unwrap(), 2);
+    /// ```
+    fn cvar_set<T, U>(self, name: T, value: U) -> Result<(), Error>
+    where
+        T: Into<Id>,
+        U: Into<Value>,
+    {
+        debug_assert_value!(self);
+        let id = name.into();
+        let value = value.into();
+        debug_assert_value!(value);
+        unsafe {
+            protect(|| {
+                rb_cvar_set(self.as_rb_value(), id.as_rb_id(), value.as_rb_value());
+                *QNIL
+            })
+        }?;
+        Ok(())
+    }
+
+    /// Returns whether the class variable `name` is defined within `self`'s
+    /// scope.
+    ///
+    /// Note, the `@@` is part of the name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Module, RClass};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let class = RClass::new(Default::default()).unwrap();
+    /// assert!(!class.cvar_defined("@@count"));
+    /// class.cvar_set("@@count", 0).unwrap();
+    /// assert!(class.cvar_defined("@@count"));
+    /// ```
+    fn cvar_defined<T>(self, name: T) -> bool
+    where
+        T: Into<Id>,
+    {
+        debug_assert_value!(self);
+        let id = name.into();
+        unsafe { Value::new(rb_cvar_defined(self.as_rb_value(), id.as_rb_id())).to_bool() }
+    }
+
     /// Returns whether or not `self` inherits from `other`.
     ///
     /// Classes including a module are considered to inherit from that module.
@@ -270,6 +472,131 @@ pub trait Module: Object + Deref<Target = Value> + Copy {
         }
     }
 
+    /// Return the name of `self`, or `None` if `self` is anonymous.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{class, module, Module, RClass};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// assert_eq!(class::string().name().unwrap(), "String");
+    /// assert_eq!(module::comparable().name().unwrap(), "Comparable");
+    /// assert!(RClass::new(Default::default()).unwrap().name().is_none());
+    /// ```
+    fn name(self) -> Option<String> {
+        unsafe {
+            RString::from_value(Value::new(rb_mod_name(self.as_rb_value())))
+                .map(|s| s.to_string_lossy().into_owned())
+        }
+    }
+
+    /// Return an array of the modules included in `self`, and, for classes,
+    /// its superclasses, starting with `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{class, eval, Module};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let ancestors = class::integer().ancestors();
+    /// let res: bool = magnus::eval!(
+    ///     "ancestors.first(3) == [Integer, Numeric, Comparable]",
+    ///     ancestors
+    /// )
+    /// .unwrap();
+    /// assert!(res);
+    /// ```
+    fn ancestors(self) -> RArray {
+        unsafe { RArray::from_rb_value_unchecked(rb_mod_ancestors(self.as_rb_value())) }
+    }
+
+    /// Return an array of the names of the public and protected instance
+    /// methods of `self`.
+    ///
+    /// If `include_inherited` is `false` only the methods defined directly
+    /// in `self` are returned, otherwise methods from ancestors are included.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, Module, RClass};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let class: RClass = eval("class Example; def hello; end; self; end").unwrap();
+    /// let methods = class.instance_methods(false);
+    /// let res: bool = magnus::eval!("methods == [:hello]", methods).unwrap();
+    /// assert!(res);
+    /// assert!(class.instance_methods(true).len() > 1);
+    /// ```
+    fn instance_methods(self, include_inherited: bool) -> RArray {
+        let args = [Value::from(include_inherited).as_rb_value()];
+        unsafe {
+            RArray::from_rb_value_unchecked(rb_class_instance_methods(
+                args.len() as c_int,
+                args.as_ptr(),
+                self.as_rb_value(),
+            ))
+        }
+    }
+
+    /// Include `module` in `self`, adding its methods as instance methods.
+    ///
+    /// This is equivalent to `include module` in Ruby, although the module's
+    /// `included` hook is not called.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{module, Module, RClass};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let class = RClass::new(Default::default()).unwrap();
+    /// class.include_module(module::comparable()).unwrap();
+    /// assert!(class.is_inherited(module::comparable()));
+    /// ```
+    fn include_module(self, module: RModule) -> Result<(), Error> {
+        debug_assert_value!(self);
+        debug_assert_value!(module);
+        unsafe {
+            protect(|| {
+                rb_include_module(self.as_rb_value(), module.as_rb_value());
+                *QNIL
+            })
+        }?;
+        Ok(())
+    }
+
+    /// Prepend `module` to `self`, so its methods take precedence over
+    /// `self`'s own instance methods.
+    ///
+    /// This is equivalent to `prepend module` in Ruby, although the module's
+    /// `prepended` hook is not called.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, Module, RClass, RModule};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let class = eval::<RClass>(r#"class Example; def name; "example"; end; self; end"#).unwrap();
+    /// let module = eval::<RModule>("module Loud; def name; super.upcase; end; self; end").unwrap();
+    /// class.prepend_module(module).unwrap();
+    /// assert_eq!(eval::<String>("Example.new.name").unwrap(), "EXAMPLE");
+    /// ```
+    fn prepend_module(self, module: RModule) -> Result<(), Error> {
+        debug_assert_value!(self);
+        debug_assert_value!(module);
+        unsafe {
+            protect(|| {
+                rb_prepend_module(self.as_rb_value(), module.as_rb_value());
+                *QNIL
+            })
+        }?;
+        Ok(())
+    }
+
     /// Define a method in `self`'s scope.
     ///
     /// # Examples
@@ -400,6 +727,162 @@ pub trait Module: Object + Deref<Target = Value> + Copy {
             );
         }
     }
+
+    /// Define `new_name` as an alias of the existing method `old_name`.
+    ///
+    /// This is equivalent to `alias_method :new_name, :old_name` in Ruby.
+    ///
+    /// Errors if the method `old_name` is not defined.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{class, eval, Module};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// class::string().define_alias("shout", "upcase").unwrap();
+    /// assert_eq!(eval::<String>(r#""hello".shout"#).unwrap(), "HELLO");
+    /// assert!(class::string().define_alias("whisper", "missing").is_err());
+    /// ```
+    fn define_alias<T, U>(self, new_name: T, old_name: U) -> Result<(), Error>
+    where
+        T: Into<Id>,
+        U: Into<Id>,
+    {
+        debug_assert_value!(self);
+        let new_id = new_name.into();
+        let old_id = old_name.into();
+        unsafe {
+            protect(|| {
+                rb_alias(self.as_rb_value(), new_id.as_rb_id(), old_id.as_rb_id());
+                *QNIL
+            })
+        }?;
+        Ok(())
+    }
+
+    /// Define public accessor methods for the instance variable `name`.
+    ///
+    /// The `@` is *not* part of the name. `rw` specifies whether a reader,
+    /// writer, or both are defined. This is equivalent to `attr_reader
+    /// :name`, `attr_writer :name`, or `attr_accessor :name` in Ruby.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, module::Attr, Module, RClass};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let class = eval::<RClass>("class Example; self; end").unwrap();
+    /// class.define_attr("value", Attr::ReadWrite).unwrap();
+    /// assert_eq!(eval::<i64>("e = Example.new; e.value = 42; e.value").unwrap(), 42);
+    /// ```
+    fn define_attr<T>(self, name: T, rw: Attr) -> Result<(), Error>
+    where
+        T: Into<Id>,
+    {
+        debug_assert_value!(self);
+        let id = name.into();
+        unsafe {
+            protect(|| {
+                rb_attr(
+                    self.as_rb_value(),
+                    id.as_rb_id(),
+                    rw.is_read() as c_int,
+                    rw.is_write() as c_int,
+                    0,
+                );
+                *QNIL
+            })
+        }?;
+        Ok(())
+    }
+
+    /// Undefine the method `name` for `self`, so instances of `self` will
+    /// not respond to it, even if defined by an ancestor.
+    ///
+    /// This is equivalent to `undef_method :name` in Ruby.
+    ///
+    /// Errors if the method `name` is not defined.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, Module, RClass, Value};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let class = eval::<RClass>("class Example; self; end").unwrap();
+    /// class.undef_method("to_s").unwrap();
+    /// assert!(eval::<Value>("Example.new.to_s").is_err());
+    /// ```
+    fn undef_method<T>(self, name: T) -> Result<(), Error>
+    where
+        T: Into<Id>,
+    {
+        debug_assert_value!(self);
+        let id = name.into();
+        unsafe {
+            protect(|| {
+                rb_undef(self.as_rb_value(), id.as_rb_id());
+                *QNIL
+            })
+        }?;
+        Ok(())
+    }
+
+    /// Remove the method `name` from `self`, so calls will be resolved by
+    /// `self`'s ancestors.
+    ///
+    /// This is equivalent to `remove_method :name` in Ruby.
+    ///
+    /// Errors if the method `name` is not defined on `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, Module, RClass};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let class = eval::<RClass>(r#"class Example; def to_s; "example"; end; self; end"#).unwrap();
+    /// class.remove_method("to_s").unwrap();
+    /// assert!(eval::<String>("Example.new.to_s").unwrap().starts_with("#<Example"));
+    /// assert!(class.remove_method("to_s").is_err());
+    /// ```
+    fn remove_method<T>(self, name: T) -> Result<(), Error>
+    where
+        T: Into<Id>,
+    {
+        debug_assert_value!(self);
+        let id = name.into();
+        unsafe {
+            protect(|| {
+                rb_remove_method_id(self.as_rb_value(), id.as_rb_id());
+                *QNIL
+            })
+        }?;
+        Ok(())
+    }
+}
+
+/// Argument for [`define_attr`](Module::define_attr).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Attr {
+    /// Define a reader method, like `attr_reader`.
+    Read,
+    /// Define a writer method, like `attr_writer`.
+    Write,
+    /// Define both reader and writer methods, like `attr_accessor`.
+    ReadWrite,
+}
+
+impl Attr {
+    fn is_read(self) -> bool {
+        matches!(self, Attr::Read | Attr::ReadWrite)
+    }
+
+    fn is_write(self) -> bool {
+        matches!(self, Attr::Write | Attr::ReadWrite)
+    }
 }
 
 /// Return Ruby's `Comparable` module.
@@ -4,9 +4,10 @@ use crate::{
     debug_assert_value,
     error::{protect, Error},
     method::Method,
-    ruby_sys::{rb_define_singleton_method, rb_ivar_get, rb_ivar_set},
+    module::RModule,
+    ruby_sys::{rb_define_singleton_method, rb_extend_object, rb_ivar_get, rb_ivar_set},
     try_convert::TryConvert,
-    value::{Id, Value},
+    value::{Id, Value, QNIL},
 };
 
 /// Functions available all non-immediate values.
@@ -67,4 +68,32 @@ pub trait Object: Deref<Target = Value> + Copy {
         }?;
         Ok(())
     }
+
+    /// Extend `self` with `module`, adding its methods as singleton methods.
+    ///
+    /// This is equivalent to `extend module` in Ruby, although the module's
+    /// `extended` hook is not called.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, prelude::*, RModule, RObject};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let module = eval::<RModule>(r#"module Greet; def greet; "hello"; end; self; end"#).unwrap();
+    /// let obj = eval::<RObject>("Object.new").unwrap();
+    /// obj.extend_object(module).unwrap();
+    /// assert_eq!(obj.funcall::<_, _, String>("greet", ()).unwrap(), "hello");
+    /// ```
+    fn extend_object(self, module: RModule) -> Result<(), Error> {
+        debug_assert_value!(self);
+        debug_assert_value!(module);
+        unsafe {
+            protect(|| {
+                rb_extend_object(self.as_rb_value(), module.as_rb_value());
+                *QNIL
+            })
+        }?;
+        Ok(())
+    }
 }
@@ -1,19 +1,31 @@
+//! Types and functions for working with Ruby's Array class.
+
 use std::{
-    convert::TryInto, fmt, iter::FromIterator, ops::Deref, os::raw::c_long, ptr::NonNull, slice,
+    cmp::Ordering,
+    convert::TryInto,
+    fmt,
+    iter::{self, FromIterator},
+    marker::PhantomData,
+    ops::Deref,
+    os::raw::c_long,
+    ptr::NonNull,
+    slice,
 };
 
 use crate::{
     debug_assert_value,
     enumerator::Enumerator,
     error::{protect, Error},
-    exception,
+    exception::{self, Exception},
     object::Object,
     r_string::RString,
     ruby_sys::{
-        self, rb_ary_cat, rb_ary_entry, rb_ary_includes, rb_ary_join, rb_ary_new, rb_ary_new_capa,
-        rb_ary_new_from_values, rb_ary_pop, rb_ary_push, rb_ary_replace, rb_ary_shared_with_p,
-        rb_ary_shift, rb_ary_store, rb_ary_subseq, rb_ary_to_ary, rb_ary_unshift,
-        ruby_rarray_flags, ruby_value_type, VALUE,
+        self, rb_ary_assoc, rb_ary_cat, rb_ary_clear, rb_ary_concat, rb_ary_delete,
+        rb_ary_delete_at, rb_ary_dup, rb_ary_entry, rb_ary_includes, rb_ary_join, rb_ary_new,
+        rb_ary_new_capa, rb_ary_new_from_values, rb_ary_pop, rb_ary_push, rb_ary_replace,
+        rb_ary_resize, rb_ary_reverse, rb_ary_rotate, rb_ary_shared_with_p, rb_ary_shift,
+        rb_ary_sort_bang, rb_ary_store, rb_ary_subseq, rb_ary_to_ary, rb_ary_unshift,
+        rb_check_array_type, ruby_rarray_flags, ruby_value_type, VALUE,
     },
     try_convert::{TryConvert, TryConvertOwned},
     value::{NonZeroValue, Value, QNIL},
@@ -238,6 +250,36 @@ impl RArray {
         }
     }
 
+    /// Add the items of `iter` to the end of `self`.
+    ///
+    /// Returns `Err` if `self` is frozen. This is the fallible version of
+    /// [`Extend::extend`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RArray};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let ary = eval::<RArray>("[1, 2]").unwrap();
+    /// ary.extend_from(vec![3, 4]).unwrap();
+    /// assert_eq!(ary.to_vec::<i64>().unwrap(), vec![1, 2, 3, 4]);
+    ///
+    /// let ary = eval::<RArray>("[1, 2].freeze").unwrap();
+    /// assert!(ary.extend_from(vec![3, 4]).is_err());
+    /// ```
+    pub fn extend_from<I>(self, iter: I) -> Result<(), Error>
+    where
+        I: IntoIterator,
+        I::Item: Into<Value>,
+    {
+        self.check_frozen()?;
+        for item in iter {
+            self.push(item)?;
+        }
+        Ok(())
+    }
+
     /// Remove and return the last element of `self`, converting it to a `T`.
     ///
     /// Errors if `self` is frozen or if the conversion fails.
@@ -710,6 +752,372 @@ impl RArray {
             (!val.is_nil()).then(|| Self::from_rb_value_unchecked(val.as_rb_value()))
         }
     }
+
+    /// Attempt to convert `val` to a `RArray` with its `to_ary` method.
+    ///
+    /// Returns `Ok(None)` if `val` does not respond to `to_ary`, and `Err` if
+    /// `to_ary` raises or does not return an Array.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RArray, Value};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let val: Value = eval("Struct.new(:to_ary).new([1, 2])").unwrap();
+    /// let ary = RArray::to_ary(val).unwrap().unwrap();
+    /// assert_eq!(ary.to_vec::<i64>().unwrap(), vec![1, 2]);
+    /// assert!(RArray::to_ary(1).unwrap().is_none());
+    /// ```
+    pub fn to_ary<T>(val: T) -> Result<Option<Self>, Error>
+    where
+        T: Into<Value>,
+    {
+        let val = val.into();
+        unsafe {
+            protect(|| Value::new(rb_check_array_type(val.as_rb_value()))).map(|res| {
+                (!res.is_nil()).then(|| Self::from_rb_value_unchecked(res.as_rb_value()))
+            })
+        }
+    }
+
+    /// Create a new `RArray` with the same elements as `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RArray};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let ary = eval::<RArray>("[1, 2, 3]").unwrap();
+    /// let dup = ary.dup();
+    /// dup.push(4).unwrap();
+    /// assert_eq!(ary.len(), 3);
+    /// assert_eq!(dup.len(), 4);
+    /// ```
+    pub fn dup(self) -> Self {
+        unsafe { Self::from_rb_value_unchecked(rb_ary_dup(self.as_rb_value())) }
+    }
+
+    /// Insert `item` before the element at `offset`.
+    ///
+    /// A negative `offset` counts backwards from the end of the array, with
+    /// `-1` inserting after the last element. If `offset` is beyond the end of
+    /// the array the array will be padded with `nil`.
+    ///
+    /// Returns `Err` if `self` is frozen or `offset` is too far before the
+    /// start of the array.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RArray};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let ary = eval::<RArray>("[1, 3]").unwrap();
+    /// ary.insert(1, 2).unwrap();
+    /// ary.insert(-1, 4).unwrap();
+    /// assert_eq!(ary.to_vec::<i64>().unwrap(), vec![1, 2, 3, 4]);
+    /// ```
+    pub fn insert<T>(self, offset: isize, item: T) -> Result<(), Error>
+    where
+        T: Into<Value>,
+    {
+        // no C api for Array#insert
+        self.funcall::<_, _, Value>("insert", (offset, item.into()))?;
+        Ok(())
+    }
+
+    /// Remove all elements equal (by `==`) to `item` from `self`.
+    ///
+    /// Returns `Err` if `self` is frozen, or if `==` raises.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RArray};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let ary = eval::<RArray>("[1, 2, 1, 3]").unwrap();
+    /// ary.delete(1).unwrap();
+    /// assert_eq!(ary.to_vec::<i64>().unwrap(), vec![2, 3]);
+    /// ```
+    pub fn delete<T>(self, item: T) -> Result<(), Error>
+    where
+        T: Into<Value>,
+    {
+        self.check_frozen()?;
+        let item = item.into();
+        unsafe {
+            protect(|| Value::new(rb_ary_delete(self.as_rb_value(), item.as_rb_value())))?;
+        }
+        Ok(())
+    }
+
+    /// Remove and return the element at `offset`, converting it to a `T`.
+    ///
+    /// Returns `nil` if `offset` is out of bounds, so `T` should generally be
+    /// an `Option`. Returns `Err` if `self` is frozen, even when `offset` is
+    /// out of bounds, or if the conversion fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RArray};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let ary = eval::<RArray>("[1, 2, 3]").unwrap();
+    /// assert_eq!(ary.delete_at::<i64>(1).unwrap(), 2);
+    /// assert_eq!(ary.delete_at::<Option<i64>>(5).unwrap(), None);
+    /// assert_eq!(ary.to_vec::<i64>().unwrap(), vec![1, 3]);
+    /// ```
+    pub fn delete_at<T>(self, offset: isize) -> Result<T, Error>
+    where
+        T: TryConvert,
+    {
+        self.check_frozen()?;
+        unsafe {
+            protect(|| Value::new(rb_ary_delete_at(self.as_rb_value(), offset as c_long)))
+                .and_then(|val| val.try_convert())
+        }
+    }
+
+    /// Add the elements of `other` to the end of `self`.
+    ///
+    /// Returns `Err` if `self` is frozen.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RArray};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let ary = eval::<RArray>("[1, 2]").unwrap();
+    /// ary.concat(eval::<RArray>("[3, 4]").unwrap()).unwrap();
+    /// assert_eq!(ary.to_vec::<i64>().unwrap(), vec![1, 2, 3, 4]);
+    /// ```
+    pub fn concat(self, other: Self) -> Result<(), Error> {
+        unsafe {
+            protect(|| Value::new(rb_ary_concat(self.as_rb_value(), other.as_rb_value())))?;
+        }
+        Ok(())
+    }
+
+    /// Reverse the order of the elements of `self`, in place.
+    ///
+    /// Returns `Err` if `self` is frozen.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RArray};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let ary = eval::<RArray>("[1, 2, 3]").unwrap();
+    /// ary.reverse().unwrap();
+    /// assert_eq!(ary.to_vec::<i64>().unwrap(), vec![3, 2, 1]);
+    /// ```
+    pub fn reverse(self) -> Result<(), Error> {
+        unsafe {
+            protect(|| Value::new(rb_ary_reverse(self.as_rb_value())))?;
+        }
+        Ok(())
+    }
+
+    /// Rotate the elements of `self` in place, so the element at `rot` is
+    /// first.
+    ///
+    /// A negative `rot` rotates in the opposite direction.
+    ///
+    /// Returns `Err` if `self` is frozen.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RArray};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let ary = eval::<RArray>("[1, 2, 3, 4]").unwrap();
+    /// ary.rotate(1).unwrap();
+    /// assert_eq!(ary.to_vec::<i64>().unwrap(), vec![2, 3, 4, 1]);
+    /// ary.rotate(-2).unwrap();
+    /// assert_eq!(ary.to_vec::<i64>().unwrap(), vec![4, 1, 2, 3]);
+    /// ```
+    pub fn rotate(self, rot: isize) -> Result<(), Error> {
+        unsafe {
+            protect(|| Value::new(rb_ary_rotate(self.as_rb_value(), rot as c_long)))?;
+        }
+        Ok(())
+    }
+
+    /// Sort the elements of `self` in place, comparing elements with `<=>`.
+    ///
+    /// Returns `Err` if `self` is frozen or any elements can't be compared.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RArray};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let ary = eval::<RArray>("[3, 1, 2]").unwrap();
+    /// ary.sort().unwrap();
+    /// assert_eq!(ary.to_vec::<i64>().unwrap(), vec![1, 2, 3]);
+    ///
+    /// let ary = eval::<RArray>(r#"[3, "1", 2]"#).unwrap();
+    /// assert!(ary.sort().is_err());
+    /// ```
+    pub fn sort(self) -> Result<(), Error> {
+        unsafe {
+            protect(|| Value::new(rb_ary_sort_bang(self.as_rb_value())))?;
+        }
+        Ok(())
+    }
+
+    /// Sort the elements of `self` in place, using `cmp` to compare elements.
+    ///
+    /// The sort is stable. Returns `Err` if `self` is frozen.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RArray};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let ary = eval::<RArray>(r#"["ccc", "a", "bb"]"#).unwrap();
+    /// ary.sort_by(|a, b| {
+    ///     let a = a.try_convert::<String>().unwrap();
+    ///     let b = b.try_convert::<String>().unwrap();
+    ///     b.len().cmp(&a.len())
+    /// })
+    /// .unwrap();
+    /// assert_eq!(ary.to_vec::<String>().unwrap(), vec!["ccc", "bb", "a"]);
+    /// ```
+    pub fn sort_by<F>(self, mut cmp: F) -> Result<(), Error>
+    where
+        F: FnMut(Value, Value) -> Ordering,
+    {
+        self.check_frozen()?;
+        // the elements are read from dup (on the stack, so visible to the GC)
+        // rather than copied to the heap, as they may be removed from self if
+        // cmp calls Ruby
+        let dup = self.dup();
+        let entry = |i: c_long| unsafe { Value::new(rb_ary_entry(dup.as_rb_value(), i)) };
+        let mut indices = (0..dup.len() as c_long).collect::<Vec<_>>();
+        indices.sort_by(|a, b| cmp(entry(*a), entry(*b)));
+        self.replace(indices.into_iter().map(entry).collect())
+    }
+
+    /// Remove all elements from `self`.
+    ///
+    /// Returns `Err` if `self` is frozen.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RArray};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let ary = eval::<RArray>("[1, 2, 3]").unwrap();
+    /// ary.clear().unwrap();
+    /// assert!(ary.is_empty());
+    /// ```
+    pub fn clear(self) -> Result<(), Error> {
+        unsafe {
+            protect(|| Value::new(rb_ary_clear(self.as_rb_value())))?;
+        }
+        Ok(())
+    }
+
+    /// Set the length of `self` to `len`, truncating it or padding it with
+    /// `nil`.
+    ///
+    /// Returns `Err` if `self` is frozen.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RArray};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let ary = eval::<RArray>("[1, 2, 3]").unwrap();
+    /// ary.resize(2).unwrap();
+    /// assert_eq!(ary.to_vec::<i64>().unwrap(), vec![1, 2]);
+    /// ary.resize(4).unwrap();
+    /// assert_eq!(ary.to_vec::<Option<i64>>().unwrap(), vec![Some(1), Some(2), None, None]);
+    /// ```
+    pub fn resize(self, len: usize) -> Result<(), Error> {
+        unsafe {
+            protect(|| Value::new(rb_ary_resize(self.as_rb_value(), len as c_long)))?;
+        }
+        Ok(())
+    }
+
+    /// Search `self`, an array of arrays, for the first inner array with a
+    /// first element equal (by `==`) to `key`, and return it converted to `T`.
+    ///
+    /// Returns `nil` if there is no match, so `T` should generally be an
+    /// `Option`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RArray};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let ary = eval::<RArray>(r#"[["a", 1], ["b", 2]]"#).unwrap();
+    /// assert_eq!(ary.assoc::<_, (String, i64)>("b").unwrap(), (String::from("b"), 2));
+    /// assert_eq!(ary.assoc::<_, Option<(String, i64)>>("c").unwrap(), None);
+    /// ```
+    pub fn assoc<K, T>(self, key: K) -> Result<T, Error>
+    where
+        K: Into<Value>,
+        T: TryConvert,
+    {
+        let key = key.into();
+        unsafe {
+            protect(|| Value::new(rb_ary_assoc(self.as_rb_value(), key.as_rb_value())))
+                .and_then(|val| val.try_convert())
+        }
+    }
+
+    /// Return a [`TypedArray`] view of `self`, after checking every element
+    /// can be converted to `T`.
+    ///
+    /// Returns `Err` naming the index of the first element that can not be
+    /// converted.
+    ///
+    /// As with [`to_vec`](RArray::to_vec), `T` must be an 'owned' Rust native
+    /// type. Elements are checked with
+    /// [`TryConvertOwned::check_convert`], so are not copied into Rust until
+    /// accessed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RArray};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let ary = eval::<RArray>("[1.5, 2.5, 3]").unwrap();
+    /// let floats = ary.typed::<f64>().unwrap();
+    /// assert_eq!(floats.iter().sum::<Result<f64, _>>().unwrap(), 7.0);
+    ///
+    /// let ary = eval::<RArray>("[1.5, :two, 3]").unwrap();
+    /// let err = ary.typed::<f64>().unwrap_err();
+    /// assert!(err.to_string().contains("at index 1"));
+    /// ```
+    pub fn typed<T>(self) -> Result<TypedArray<T>, Error>
+    where
+        T: TryConvertOwned,
+    {
+        let mut i = 0;
+        // length is re-checked each time as checks may call Ruby
+        while i < self.len() {
+            T::check_convert(self.entry(i as isize)?).map_err(|e| error_at_index(e, i))?;
+            i += 1;
+        }
+        Ok(TypedArray(self, PhantomData))
+    }
 }
 
 impl Deref for RArray {
@@ -738,304 +1146,330 @@ impl From<RArray> for Value {
     }
 }
 
-impl<T0> From<(T0,)> for Value
+macro_rules! impl_from_tuple {
+    () => {};
+    ($($t:ident $i:tt)+) => {
+        impl<$($t),+> From<($($t,)+)> for Value
+        where
+            $($t: Into<Value>,)+
+        {
+            fn from(val: ($($t,)+)) -> Self {
+                let ary = [$(val.$i.into()),+];
+                RArray::from_slice(&ary).into()
+            }
+        }
+    };
+}
+
+for_each_tuple!(impl_from_tuple);
+
+impl<T> From<Vec<T>> for Value
 where
-    T0: Into<Value>,
+    T: Into<Value>,
 {
-    fn from(val: (T0,)) -> Self {
-        let ary = [val.0.into()];
-        RArray::from_slice(&ary).into()
+    fn from(val: Vec<T>) -> Self {
+        RArray::from_vec(val).into()
     }
 }
 
-impl<T0, T1> From<(T0, T1)> for Value
+impl<T> FromIterator<T> for RArray
 where
-    T0: Into<Value>,
-    T1: Into<Value>,
+    T: Into<Value>,
 {
-    fn from(val: (T0, T1)) -> Self {
-        let ary = [val.0.into(), val.1.into()];
-        RArray::from_slice(&ary).into()
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let iter = iter.into_iter();
+        let (lower, _) = iter.size_hint();
+        let array = if lower > 0 {
+            RArray::with_capacity(lower)
+        } else {
+            RArray::new()
+        };
+        for i in iter {
+            array.push(i).unwrap();
+        }
+        array
     }
 }
 
-impl<T0, T1, T2> From<(T0, T1, T2)> for Value
+/// # Panics
+///
+/// Panics if `self` is frozen. See [`RArray::extend_from`] for a fallible
+/// alternative.
+impl<T> Extend<T> for RArray
 where
-    T0: Into<Value>,
-    T1: Into<Value>,
-    T2: Into<Value>,
+    T: Into<Value>,
 {
-    fn from(val: (T0, T1, T2)) -> Self {
-        let ary = [val.0.into(), val.1.into(), val.2.into()];
-        RArray::from_slice(&ary).into()
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        self.extend_from(iter).unwrap_or_else(|e| panic!("{}", e))
     }
 }
 
-impl<T0, T1, T2, T3> From<(T0, T1, T2, T3)> for Value
-where
-    T0: Into<Value>,
-    T1: Into<Value>,
-    T2: Into<Value>,
-    T3: Into<Value>,
-{
-    fn from(val: (T0, T1, T2, T3)) -> Self {
-        let ary = [val.0.into(), val.1.into(), val.2.into(), val.3.into()];
-        RArray::from_slice(&ary).into()
+impl IntoIterator for RArray {
+    type Item = Value;
+    type IntoIter = Iter;
+
+    /// Returns an iterator over the elements of `self`.
+    ///
+    /// The iterator works over a copy of `self`, sharing the same storage,
+    /// so changes made to `self` during iteration are not reflected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RArray};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let ary = eval::<RArray>("[1, 2, 3]").unwrap();
+    /// let res = ary
+    ///     .into_iter()
+    ///     .map(|v| v.try_convert::<i64>().unwrap() * 2)
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(res, vec![2, 4, 6]);
+    /// ```
+    fn into_iter(self) -> Self::IntoIter {
+        Iter {
+            ary: self.subseq(0, self.len()).unwrap_or_else(RArray::new),
+            i: 0,
+        }
     }
 }
 
-impl<T0, T1, T2, T3, T4> From<(T0, T1, T2, T3, T4)> for Value
-where
-    T0: Into<Value>,
-    T1: Into<Value>,
-    T2: Into<Value>,
-    T3: Into<Value>,
-    T4: Into<Value>,
-{
-    fn from(val: (T0, T1, T2, T3, T4)) -> Self {
-        let ary = [
-            val.0.into(),
-            val.1.into(),
-            val.2.into(),
-            val.3.into(),
-            val.4.into(),
-        ];
-        RArray::from_slice(&ary).into()
+/// An iterator over the elements of a [`RArray`], see
+/// [`RArray::into_iter`](RArray#method.into_iter).
+///
+/// As with [`RArray`], this type is only safe to use on the stack.
+pub struct Iter {
+    ary: RArray,
+    i: usize,
+}
+
+impl Iterator for Iter {
+    type Item = Value;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.i >= self.ary.len() {
+            return None;
+        }
+        let val = unsafe { Value::new(rb_ary_entry(self.ary.as_rb_value(), self.i as c_long)) };
+        self.i += 1;
+        Some(val)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.ary.len().saturating_sub(self.i);
+        (remaining, Some(remaining))
     }
 }
 
-impl<T0, T1, T2, T3, T4, T5> From<(T0, T1, T2, T3, T4, T5)> for Value
-where
-    T0: Into<Value>,
-    T1: Into<Value>,
-    T2: Into<Value>,
-    T3: Into<Value>,
-    T4: Into<Value>,
-    T5: Into<Value>,
-{
-    fn from(val: (T0, T1, T2, T3, T4, T5)) -> Self {
-        let ary = [
-            val.0.into(),
-            val.1.into(),
-            val.2.into(),
-            val.3.into(),
-            val.4.into(),
-            val.5.into(),
-        ];
-        RArray::from_slice(&ary).into()
+impl Object for RArray {}
+
+// Add the index of a bad element to the message of `e`. A Ruby exception is
+// copied with `Exception#exception`, keeping its class and backtrace.
+fn error_at_index(e: Error, i: usize) -> Error {
+    match e {
+        Error::Jump(_) => e,
+        Error::Error(class, msg) => Error::new(class, format!("{} at index {}", msg, i)),
+        Error::Exception(ex) => ex
+            .funcall("exception", (format!("{} at index {}", ex, i),))
+            .ok()
+            .and_then(Exception::from_value)
+            .unwrap_or(ex)
+            .into(),
     }
 }
 
-impl<T0, T1, T2, T3, T4, T5, T6> From<(T0, T1, T2, T3, T4, T5, T6)> for Value
+/// A Ruby Array known to hold elements of type `T`.
+///
+/// Created with [`RArray::typed`], or by [`TryConvert`], e.g. when used as the
+/// argument of a method, in which case a `TypeError` naming the index of any
+/// bad element is raised.
+///
+/// Every element is checked when the `TypedArray` is created, and converted
+/// once each time it is accessed. As Ruby code may store any value in the
+/// underlying array at any time, access to elements is still fallible.
+///
+/// As with [`RArray`], this type is only safe to use on the stack.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{define_global_function, eval, function, Error, TypedArray};
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// fn sum(nums: TypedArray<f64>) -> Result<f64, Error> {
+///     nums.iter().sum()
+/// }
+///
+/// define_global_function("sum", function!(sum, 1));
+///
+/// assert_eq!(eval::<f64>("sum([1.5, 2, 3.5])").unwrap(), 7.0);
+/// let err = eval::<f64>("sum([1.5, nil])").unwrap_err();
+/// assert!(err.to_string().contains("at index 1"));
+/// ```
+#[repr(transparent)]
+pub struct TypedArray<T>(RArray, PhantomData<T>);
+
+impl<T> TypedArray<T>
 where
-    T0: Into<Value>,
-    T1: Into<Value>,
-    T2: Into<Value>,
-    T3: Into<Value>,
-    T4: Into<Value>,
-    T5: Into<Value>,
-    T6: Into<Value>,
+    T: TryConvertOwned,
 {
-    fn from(val: (T0, T1, T2, T3, T4, T5, T6)) -> Self {
-        let ary = [
-            val.0.into(),
-            val.1.into(),
-            val.2.into(),
-            val.3.into(),
-            val.4.into(),
-            val.5.into(),
-            val.6.into(),
-        ];
-        RArray::from_slice(&ary).into()
+    /// Return the number of elements in `self`.
+    pub fn len(self) -> usize {
+        self.0.len()
+    }
+
+    /// Return whether `self` contains any elements or not.
+    pub fn is_empty(self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Return the element at index `i`, converted to `T`.
+    ///
+    /// Returns `Err` if `i` is out of bounds, or the element was replaced by
+    /// Ruby code with one that can't be converted to `T`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RArray};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let ary = eval::<RArray>(r#"["a", "b"]"#).unwrap().typed::<String>().unwrap();
+    /// assert_eq!(ary.get(1).unwrap(), "b");
+    /// assert!(ary.get(2).is_err());
+    /// ```
+    pub fn get(self, i: usize) -> Result<T, Error> {
+        let len = self.len();
+        if i >= len {
+            return Err(Error::new(
+                exception::index_error(),
+                format!(
+                    "index {} outside of array bounds: {}...{}",
+                    i,
+                    -(len as i64),
+                    len
+                ),
+            ));
+        }
+        let val = unsafe { Value::new(rb_ary_entry(self.0.as_rb_value(), i as c_long)) };
+        T::try_convert_owned(val).map_err(|e| error_at_index(e, i))
+    }
+
+    /// Add `item` to the end of `self`.
+    ///
+    /// Returns `Err` if `self` is frozen.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RArray};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let ary = RArray::new().typed::<i64>().unwrap();
+    /// ary.push(1).unwrap();
+    /// ary.push(2).unwrap();
+    /// assert_eq!(ary.iter().collect::<Result<Vec<_>, _>>().unwrap(), vec![1, 2]);
+    /// ```
+    pub fn push(self, item: T) -> Result<(), Error>
+    where
+        T: Into<Value>,
+    {
+        self.0.push(item)
+    }
+
+    /// Returns an iterator over the elements of `self`, converted to `T`.
+    ///
+    /// The iterator works over a copy of `self`, sharing the same storage,
+    /// so changes made to `self` during iteration are not reflected.
+    ///
+    /// Each item is `Err` if, since `self` was created, Ruby code has
+    /// replaced that element with one that can't be converted to `T`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RArray};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let ary = eval::<RArray>("[1, 2, 3]").unwrap().typed::<i64>().unwrap();
+    /// let mut res = Vec::new();
+    /// for i in ary.iter() {
+    ///     res.push(i.unwrap() * 2);
+    /// }
+    /// assert_eq!(res, vec![2, 4, 6]);
+    /// ```
+    pub fn iter(self) -> impl Iterator<Item = Result<T, Error>> {
+        let snapshot = TypedArray::<T>(
+            self.0.subseq(0, self.len()).unwrap_or_else(RArray::new),
+            PhantomData,
+        );
+        let mut i = 0;
+        iter::from_fn(move || {
+            if i >= snapshot.len() {
+                return None;
+            }
+            let res = snapshot.get(i);
+            i += 1;
+            Some(res)
+        })
     }
 }
 
-impl<T0, T1, T2, T3, T4, T5, T6, T7> From<(T0, T1, T2, T3, T4, T5, T6, T7)> for Value
-where
-    T0: Into<Value>,
-    T1: Into<Value>,
-    T2: Into<Value>,
-    T3: Into<Value>,
-    T4: Into<Value>,
-    T5: Into<Value>,
-    T6: Into<Value>,
-    T7: Into<Value>,
-{
-    fn from(val: (T0, T1, T2, T3, T4, T5, T6, T7)) -> Self {
-        let ary = [
-            val.0.into(),
-            val.1.into(),
-            val.2.into(),
-            val.3.into(),
-            val.4.into(),
-            val.5.into(),
-            val.6.into(),
-            val.7.into(),
-        ];
-        RArray::from_slice(&ary).into()
+impl<T> Clone for TypedArray<T> {
+    fn clone(&self) -> Self {
+        *self
     }
 }
 
-impl<T0, T1, T2, T3, T4, T5, T6, T7, T8> From<(T0, T1, T2, T3, T4, T5, T6, T7, T8)> for Value
-where
-    T0: Into<Value>,
-    T1: Into<Value>,
-    T2: Into<Value>,
-    T3: Into<Value>,
-    T4: Into<Value>,
-    T5: Into<Value>,
-    T6: Into<Value>,
-    T7: Into<Value>,
-    T8: Into<Value>,
-{
-    fn from(val: (T0, T1, T2, T3, T4, T5, T6, T7, T8)) -> Self {
-        let ary = [
-            val.0.into(),
-            val.1.into(),
-            val.2.into(),
-            val.3.into(),
-            val.4.into(),
-            val.5.into(),
-            val.6.into(),
-            val.7.into(),
-            val.8.into(),
-        ];
-        RArray::from_slice(&ary).into()
+impl<T> Copy for TypedArray<T> {}
+
+impl<T> Deref for TypedArray<T> {
+    type Target = Value;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
     }
 }
 
-impl<T0, T1, T2, T3, T4, T5, T6, T7, T8, T9> From<(T0, T1, T2, T3, T4, T5, T6, T7, T8, T9)>
-    for Value
-where
-    T0: Into<Value>,
-    T1: Into<Value>,
-    T2: Into<Value>,
-    T3: Into<Value>,
-    T4: Into<Value>,
-    T5: Into<Value>,
-    T6: Into<Value>,
-    T7: Into<Value>,
-    T8: Into<Value>,
-    T9: Into<Value>,
-{
-    fn from(val: (T0, T1, T2, T3, T4, T5, T6, T7, T8, T9)) -> Self {
-        let ary = [
-            val.0.into(),
-            val.1.into(),
-            val.2.into(),
-            val.3.into(),
-            val.4.into(),
-            val.5.into(),
-            val.6.into(),
-            val.7.into(),
-            val.8.into(),
-            val.9.into(),
-        ];
-        RArray::from_slice(&ary).into()
+impl<T> fmt::Display for TypedArray<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
     }
 }
 
-impl<T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10>
-    From<(T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10)> for Value
-where
-    T0: Into<Value>,
-    T1: Into<Value>,
-    T2: Into<Value>,
-    T3: Into<Value>,
-    T4: Into<Value>,
-    T5: Into<Value>,
-    T6: Into<Value>,
-    T7: Into<Value>,
-    T8: Into<Value>,
-    T9: Into<Value>,
-    T10: Into<Value>,
-{
-    fn from(val: (T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10)) -> Self {
-        let ary = [
-            val.0.into(),
-            val.1.into(),
-            val.2.into(),
-            val.3.into(),
-            val.4.into(),
-            val.5.into(),
-            val.6.into(),
-            val.7.into(),
-            val.8.into(),
-            val.9.into(),
-            val.10.into(),
-        ];
-        RArray::from_slice(&ary).into()
+impl<T> fmt::Debug for TypedArray<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
     }
 }
 
-impl<T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11>
-    From<(T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11)> for Value
-where
-    T0: Into<Value>,
-    T1: Into<Value>,
-    T2: Into<Value>,
-    T3: Into<Value>,
-    T4: Into<Value>,
-    T5: Into<Value>,
-    T6: Into<Value>,
-    T7: Into<Value>,
-    T8: Into<Value>,
-    T9: Into<Value>,
-    T10: Into<Value>,
-    T11: Into<Value>,
-{
-    fn from(val: (T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11)) -> Self {
-        let ary = [
-            val.0.into(),
-            val.1.into(),
-            val.2.into(),
-            val.3.into(),
-            val.4.into(),
-            val.5.into(),
-            val.6.into(),
-            val.7.into(),
-            val.8.into(),
-            val.9.into(),
-            val.10.into(),
-            val.11.into(),
-        ];
-        RArray::from_slice(&ary).into()
+impl<T> From<TypedArray<T>> for RArray {
+    fn from(val: TypedArray<T>) -> Self {
+        val.0
     }
 }
 
-impl<T> From<Vec<T>> for Value
-where
-    T: Into<Value>,
-{
-    fn from(val: Vec<T>) -> Self {
-        RArray::from_vec(val).into()
+impl<T> From<TypedArray<T>> for Value {
+    fn from(val: TypedArray<T>) -> Self {
+        *val
     }
 }
 
-impl<T> FromIterator<T> for RArray
+impl<T> TryConvert for TypedArray<T>
 where
-    T: Into<Value>,
+    T: TryConvertOwned,
 {
-    fn from_iter<I>(iter: I) -> Self
-    where
-        I: IntoIterator<Item = T>,
-    {
-        let iter = iter.into_iter();
-        let (lower, _) = iter.size_hint();
-        let array = if lower > 0 {
-            RArray::with_capacity(lower)
-        } else {
-            RArray::new()
-        };
-        for i in iter {
-            array.push(i).unwrap();
-        }
-        array
+    fn try_convert(val: &Value) -> Result<Self, Error> {
+        RArray::try_convert(val)?.typed()
     }
 }
 
-impl Object for RArray {}
-
 impl TryConvert for RArray {
     #[inline]
     fn try_convert(val: &Value) -> Result<Self, Error> {
@@ -1,7 +1,8 @@
 use std::{
     fmt,
     ops::Deref,
-    os::raw::{c_long, c_longlong, c_ulong, c_ulonglong},
+    os::raw::{c_int, c_long, c_longlong, c_ulong, c_ulonglong, c_void},
+    ptr,
 };
 
 use crate::{
@@ -10,8 +11,8 @@ use crate::{
     exception,
     integer::{Integer, IntegerType},
     ruby_sys::{
-        rb_ll2inum, rb_num2ll, rb_num2long, rb_num2ull, rb_num2ulong, rb_ull2inum, ruby_fl_type,
-        ruby_value_type, VALUE,
+        rb_absint_size, rb_integer_pack, rb_integer_unpack, rb_ll2inum, rb_num2ll, rb_num2long,
+        rb_num2ull, rb_num2ulong, rb_ull2inum, ruby_fl_type, ruby_value_type, size_t, VALUE,
     },
     try_convert::TryConvert,
     value::{Fixnum, NonZeroValue, Value, QNIL},
@@ -103,7 +104,7 @@ impl RBignum {
         }
     }
 
-    fn is_negative(self) -> bool {
+    pub(crate) fn is_negative(self) -> bool {
         debug_assert_value!(self);
         unsafe {
             let r_basic = self.r_basic_unchecked();
@@ -272,6 +273,193 @@ impl RBignum {
         }
         Ok(res as usize)
     }
+
+    /// Create a new `RBignum` from an `i128.`
+    ///
+    /// Returns `Ok(RBignum)` if `n` is large enough to require a bignum,
+    /// otherwise returns `Err(Fixnum)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RBignum};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let big = RBignum::from_i128(-170141183460469231731687303715884105728).unwrap();
+    /// let res: bool = eval!("big == -(2 ** 127)", big).unwrap();
+    /// assert!(res);
+    /// // too small
+    /// assert!(RBignum::from_i128(0).is_err());
+    /// ```
+    pub fn from_i128(n: i128) -> Result<Self, Fixnum> {
+        Self::from_integer(unpack_le(&n.to_le_bytes(), INTEGER_PACK_2COMP))
+    }
+
+    /// Create a new `RBignum` from an `u128.`
+    ///
+    /// Returns `Ok(RBignum)` if `n` is large enough to require a bignum,
+    /// otherwise returns `Err(Fixnum)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RBignum};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let big = RBignum::from_u128(340282366920938463463374607431768211455).unwrap();
+    /// let res: bool = eval!("big == 2 ** 128 - 1", big).unwrap();
+    /// assert!(res);
+    /// // too small
+    /// assert!(RBignum::from_u128(0).is_err());
+    /// ```
+    pub fn from_u128(n: u128) -> Result<Self, Fixnum> {
+        Self::from_integer(unpack_le(&n.to_le_bytes(), 0))
+    }
+
+    /// Create a new `RBignum` from the little-endian bytes of an unsigned
+    /// integer.
+    ///
+    /// Returns `Ok(RBignum)` if the value is large enough to require a
+    /// bignum, otherwise returns `Err(Fixnum)`. The result is never
+    /// negative, see [`RBignum::to_bytes_le`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RBignum};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let big = RBignum::from_bytes_le(&[0, 0, 0, 0, 0, 0, 0, 0, 1]).unwrap();
+    /// let res: bool = eval!("big == 2 ** 64", big).unwrap();
+    /// assert!(res);
+    /// // too small
+    /// assert!(RBignum::from_bytes_le(&[1]).is_err());
+    /// ```
+    pub fn from_bytes_le(bytes: &[u8]) -> Result<Self, Fixnum> {
+        Self::from_integer(unpack_le(bytes, 0))
+    }
+
+    fn from_integer(val: Integer) -> Result<Self, Fixnum> {
+        match val.integer_type() {
+            IntegerType::Fixnum(fix) => Err(fix),
+            IntegerType::Bignum(big) => Ok(big),
+        }
+    }
+
+    /// Convert `self` to an `i128`. Returns `Err` if `self` is out of range
+    /// for `i128`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RBignum};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// assert_eq!(eval::<RBignum>("-(2 ** 127)").unwrap().to_i128().unwrap(), i128::MIN);
+    /// assert!(eval::<RBignum>("2 ** 127").unwrap().to_i128().is_err());
+    /// ```
+    pub fn to_i128(self) -> Result<i128, Error> {
+        debug_assert_value!(self);
+        let mut bytes = [0; 16];
+        let sign = self.pack_le(&mut bytes, INTEGER_PACK_2COMP);
+        let res = i128::from_le_bytes(bytes);
+        if sign.abs() > 1 || (sign < 0) != (res < 0) {
+            return Err(Error::new(
+                exception::range_error(),
+                "bignum too big to convert into `i128`",
+            ));
+        }
+        Ok(res)
+    }
+
+    /// Convert `self` to a `u128`. Returns `Err` if `self` is negative or out
+    /// of range for `u128`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RBignum};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// assert_eq!(eval::<RBignum>("2 ** 128 - 1").unwrap().to_u128().unwrap(), u128::MAX);
+    /// assert!(eval::<RBignum>("2 ** 128").unwrap().to_u128().is_err());
+    /// assert!(eval::<RBignum>("-(2 ** 64)").unwrap().to_u128().is_err());
+    /// ```
+    pub fn to_u128(self) -> Result<u128, Error> {
+        debug_assert_value!(self);
+        if self.is_negative() {
+            return Err(Error::new(
+                exception::range_error(),
+                "can't convert negative integer to unsigned",
+            ));
+        }
+        let mut bytes = [0; 16];
+        if self.pack_le(&mut bytes, 0) > 1 {
+            return Err(Error::new(
+                exception::range_error(),
+                "bignum too big to convert into `u128`",
+            ));
+        }
+        Ok(u128::from_le_bytes(bytes))
+    }
+
+    /// Return the absolute value of `self` as little-endian bytes.
+    ///
+    /// The sign of `self` is not included, compare `self` with `0` to
+    /// determine it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RBignum};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let big = eval::<RBignum>("2 ** 64 + 2").unwrap();
+    /// assert_eq!(big.to_bytes_le(), vec![2, 0, 0, 0, 0, 0, 0, 0, 1]);
+    /// let big = eval::<RBignum>("-(2 ** 64 + 2)").unwrap();
+    /// assert_eq!(big.to_bytes_le(), vec![2, 0, 0, 0, 0, 0, 0, 0, 1]);
+    /// ```
+    pub fn to_bytes_le(self) -> Vec<u8> {
+        debug_assert_value!(self);
+        let len = unsafe { rb_absint_size(self.as_rb_value(), ptr::null_mut()) } as usize;
+        let mut bytes = vec![0; len];
+        self.pack_le(&mut bytes, 0);
+        bytes
+    }
+
+    fn pack_le(self, bytes: &mut [u8], flags: c_int) -> c_int {
+        unsafe {
+            rb_integer_pack(
+                self.as_rb_value(),
+                bytes.as_mut_ptr() as *mut c_void,
+                bytes.len() as size_t,
+                1,
+                0,
+                INTEGER_PACK_LITTLE_ENDIAN | flags,
+            )
+        }
+    }
+}
+
+// from ruby/internal/intern/bignum.h
+const INTEGER_PACK_LSWORD_FIRST: c_int = 0x02;
+const INTEGER_PACK_LSBYTE_FIRST: c_int = 0x20;
+const INTEGER_PACK_LITTLE_ENDIAN: c_int = INTEGER_PACK_LSWORD_FIRST | INTEGER_PACK_LSBYTE_FIRST;
+pub(crate) const INTEGER_PACK_2COMP: c_int = 0x80;
+pub(crate) const INTEGER_PACK_NEGATIVE: c_int = 0x200;
+
+/// Create an `Integer` from little-endian bytes, interpreted according to
+/// `flags`.
+pub(crate) fn unpack_le(bytes: &[u8], flags: c_int) -> Integer {
+    unsafe {
+        Integer::from_rb_value_unchecked(rb_integer_unpack(
+            bytes.as_ptr() as *const c_void,
+            bytes.len() as size_t,
+            1,
+            0,
+            INTEGER_PACK_LITTLE_ENDIAN | flags,
+        ))
+    }
 }
 
 impl Deref for RBignum {
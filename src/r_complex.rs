@@ -1,13 +1,20 @@
 use std::{fmt, ops::Deref};
 
 use crate::{
-    error::Error,
+    class, debug_assert_value,
+    error::{protect, Error},
     exception,
-    ruby_sys::ruby_value_type,
-    try_convert::TryConvert,
+    ruby_sys::{
+        rb_complex_abs, rb_complex_arg, rb_complex_imag, rb_complex_new, rb_complex_real,
+        rb_num2dbl, ruby_value_type, VALUE,
+    },
+    try_convert::{TryConvert, TryConvertOwned},
     value::{NonZeroValue, Value},
 };
 
+#[cfg(feature = "num-complex")]
+use std::convert::TryFrom;
+
 /// A Value pointer to a RComplex struct, Ruby's internal representation of
 /// complex numbers.
 ///
@@ -19,6 +26,16 @@ pub struct RComplex(NonZeroValue);
 
 impl RComplex {
     /// Return `Some(RComplex)` if `val` is a `RComplex`, `None` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RComplex};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// assert!(RComplex::from_value(eval("1+2i").unwrap()).is_some());
+    /// assert!(RComplex::from_value(eval("1").unwrap()).is_none());
+    /// ```
     #[inline]
     pub fn from_value(val: Value) -> Option<Self> {
         unsafe {
@@ -26,6 +43,148 @@ impl RComplex {
                 .then(|| Self(NonZeroValue::new_unchecked(val)))
         }
     }
+
+    #[inline]
+    pub(crate) unsafe fn from_rb_value_unchecked(val: VALUE) -> Self {
+        Self(NonZeroValue::new_unchecked(Value::new(val)))
+    }
+
+    /// Create a new `RComplex` from its real and imaginary parts.
+    ///
+    /// Returns `Err` if either part is not numeric.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RComplex};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let complex = RComplex::new(1, 2.5).unwrap();
+    /// let res: bool = eval!("complex == Complex(1, 2.5)", complex).unwrap();
+    /// assert!(res);
+    /// assert!(RComplex::new("1", 2).is_err());
+    /// ```
+    pub fn new<T, U>(real: T, imag: U) -> Result<Self, Error>
+    where
+        T: Into<Value>,
+        U: Into<Value>,
+    {
+        let real = real.into();
+        let imag = imag.into();
+        debug_assert_value!(real);
+        debug_assert_value!(imag);
+        for part in &[real, imag] {
+            if !part.is_kind_of(class::numeric()) {
+                return Err(Error::new(
+                    exception::type_error(),
+                    format!("{} is not a number", part.inspect()),
+                ));
+            }
+        }
+        unsafe {
+            protect(|| Value::new(rb_complex_new(real.as_rb_value(), imag.as_rb_value())))
+                .map(|v| Self::from_rb_value_unchecked(v.as_rb_value()))
+        }
+    }
+
+    /// Return the real part of `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RComplex};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let complex = eval::<RComplex>("Complex(9, 8)").unwrap();
+    /// assert_eq!(complex.real::<i64>().unwrap(), 9);
+    /// ```
+    pub fn real<T>(self) -> Result<T, Error>
+    where
+        T: TryConvert,
+    {
+        unsafe { Value::new(rb_complex_real(self.as_rb_value())) }.try_convert()
+    }
+
+    /// Return the imaginary part of `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RComplex};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let complex = eval::<RComplex>("Complex(9, 8)").unwrap();
+    /// assert_eq!(complex.imag::<i64>().unwrap(), 8);
+    /// ```
+    pub fn imag<T>(self) -> Result<T, Error>
+    where
+        T: TryConvert,
+    {
+        unsafe { Value::new(rb_complex_imag(self.as_rb_value())) }.try_convert()
+    }
+
+    /// Return the real and imaginary parts of `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RComplex};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let complex = eval::<RComplex>("Complex(1.5, -2)").unwrap();
+    /// assert_eq!(complex.rect::<f64>().unwrap(), (1.5, -2.0));
+    /// ```
+    pub fn rect<T>(self) -> Result<(T, T), Error>
+    where
+        T: TryConvert,
+    {
+        Ok((self.real()?, self.imag()?))
+    }
+
+    /// Return the absolute value (or magnitude) of `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RComplex};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let complex = eval::<RComplex>("Complex(3, 4)").unwrap();
+    /// assert_eq!(complex.abs(), 5.0);
+    /// ```
+    pub fn abs(self) -> f64 {
+        unsafe { rb_num2dbl(rb_complex_abs(self.as_rb_value())) }
+    }
+
+    /// Return the argument (or angle) of `self`, in radians.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RComplex};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let complex = eval::<RComplex>("Complex(0, 1)").unwrap();
+    /// assert_eq!(complex.arg(), std::f64::consts::FRAC_PI_2);
+    /// ```
+    pub fn arg(self) -> f64 {
+        unsafe { rb_num2dbl(rb_complex_arg(self.as_rb_value())) }
+    }
+
+    /// Return the absolute value and argument of `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RComplex};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let complex = eval::<RComplex>("Complex(-2, 0)").unwrap();
+    /// assert_eq!(complex.polar(), (2.0, std::f64::consts::PI));
+    /// ```
+    pub fn polar(self) -> (f64, f64) {
+        (self.abs(), self.arg())
+    }
 }
 
 impl Deref for RComplex {
@@ -67,3 +226,32 @@ impl TryConvert for RComplex {
         })
     }
 }
+
+#[cfg(feature = "num-complex")]
+impl<T> TryFrom<num_complex::Complex<T>> for RComplex
+where
+    T: Into<Value>,
+{
+    type Error = Error;
+
+    /// Create a new `RComplex` from a `Complex`.
+    ///
+    /// Returns `Err` if the real or imaginary part do not convert to a Ruby
+    /// number.
+    fn try_from(val: num_complex::Complex<T>) -> Result<Self, Self::Error> {
+        Self::new(val.re, val.im)
+    }
+}
+
+#[cfg(feature = "num-complex")]
+impl<T> TryConvert for num_complex::Complex<T>
+where
+    T: TryConvert,
+{
+    fn try_convert(val: &Value) -> Result<Self, Error> {
+        let complex = RComplex::try_convert(val)?;
+        Ok(Self::new(complex.real()?, complex.imag()?))
+    }
+}
+#[cfg(feature = "num-complex")]
+impl<T> TryConvertOwned for num_complex::Complex<T> where T: TryConvertOwned {}
@@ -1,9 +1,19 @@
-use std::{fmt, ops::Deref};
+use std::{
+    fmt,
+    io::{self, Read, Seek, SeekFrom, Write},
+    ops::Deref,
+    os::raw::c_int,
+};
+
+#[cfg(unix)]
+use std::os::unix::io::{AsRawFd, RawFd};
 
 use crate::{
+    class,
     error::Error,
     exception,
     object::Object,
+    r_string::RString,
     ruby_sys::ruby_value_type,
     try_convert::TryConvert,
     value::{NonZeroValue, Value},
@@ -11,6 +21,14 @@ use crate::{
 
 /// A Value pointer to a RFile struct, Ruby's internal representation of files.
 ///
+/// This is the representation of all of Ruby's `IO` objects, not only
+/// instances of `File`.
+///
+/// Implements [`Read`], [`Write`], and [`Seek`] by calling Ruby's
+/// `readpartial`, `write`, and `seek` methods, so Ruby-level buffering is
+/// respected and reads and writes can be interleaved with those made from
+/// Ruby.
+///
 /// All [`Value`] methods should be available on this type through [`Deref`],
 /// but some may be missed by this documentation.
 #[derive(Clone, Copy)]
@@ -19,6 +37,16 @@ pub struct RFile(NonZeroValue);
 
 impl RFile {
     /// Return `Some(RFile)` if `val` is a `RFile`, `None` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RFile};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// assert!(RFile::from_value(eval("STDOUT").unwrap()).is_some());
+    /// assert!(RFile::from_value(eval("nil").unwrap()).is_none());
+    /// ```
     #[inline]
     pub fn from_value(val: Value) -> Option<Self> {
         unsafe {
@@ -26,6 +54,146 @@ impl RFile {
                 .then(|| Self(NonZeroValue::new_unchecked(val)))
         }
     }
+
+    /// Create a new Ruby `IO` object for the file descriptor `fd`, opened
+    /// with `mode` (e.g. `"r"`, `"w"`, or `"r+"`).
+    ///
+    /// Returns `Err` if `fd` is not a valid file descriptor or `mode` is
+    /// invalid or incompatible with how `fd` was opened.
+    ///
+    /// # Safety
+    ///
+    /// The returned `IO` object takes ownership of `fd`, and will close it
+    /// when it is closed or garbage collected. `fd` must be an open file
+    /// descriptor not owned by anything else.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(unix)]
+    /// # {
+    /// use std::{os::unix::io::IntoRawFd, os::unix::net::UnixStream};
+    ///
+    /// use magnus::{eval, RFile};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let (a, b) = UnixStream::pair().unwrap();
+    /// let a = unsafe { RFile::from_raw_fd(a.into_raw_fd(), "r+").unwrap() };
+    /// let b = unsafe { RFile::from_raw_fd(b.into_raw_fd(), "r+").unwrap() };
+    /// let res: String = eval!(r#"a.write("hello"); a.close; b.read"#, a, b).unwrap();
+    /// assert_eq!(res, "hello");
+    /// # }
+    /// ```
+    pub unsafe fn from_raw_fd(fd: c_int, mode: &str) -> Result<Self, Error> {
+        class::io()
+            .new_instance((fd, RString::new(mode)))
+            .and_then(|v| Self::try_convert(&v))
+    }
+
+    /// Return the file descriptor of `self`.
+    ///
+    /// Ruby's buffers are flushed first, so the file descriptor can be used
+    /// directly. Data Ruby has buffered for reading is given back by seeking
+    /// backwards, which isn't possible for pipes and sockets, so for those
+    /// any buffered data will be missed by reads from the file descriptor.
+    ///
+    /// Returns `Err` if `self` is closed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RFile};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let stdout: RFile = eval("STDOUT").unwrap();
+    /// assert_eq!(stdout.fileno().unwrap(), 1);
+    /// ```
+    pub fn fileno(self) -> Result<c_int, Error> {
+        self.funcall::<_, _, Value>("flush", ())?;
+        self.funcall("fileno", ())
+    }
+}
+
+/// Convert a Ruby exception to an `io::Error`, keeping the OS error code for
+/// `SystemCallError`s.
+fn to_io_error(e: Error) -> io::Error {
+    if let Error::Exception(ex) = &e {
+        if ex.is_kind_of(exception::system_call_error()) {
+            if let Ok(errno) = ex.funcall("errno", ()) {
+                return io::Error::from_raw_os_error(errno);
+            }
+        }
+    }
+    let kind = if e.is_kind_of(exception::eof_error()) {
+        io::ErrorKind::UnexpectedEof
+    } else {
+        io::ErrorKind::Other
+    };
+    io::Error::new(kind, e.to_string())
+}
+
+impl Read for RFile {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        // IO#read with a length blocks until that many bytes are read, where
+        // readpartial returns whatever is available, raising at end of file
+        let s: RString = match self.funcall("readpartial", (buf.len(),)) {
+            Ok(s) => s,
+            Err(e) if e.is_kind_of(exception::eof_error()) => return Ok(0),
+            Err(e) => return Err(to_io_error(e)),
+        };
+        let slice = unsafe { s.as_slice() };
+        if slice.len() > buf.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "readpartial returned {} bytes, expected at most {}",
+                    slice.len(),
+                    buf.len()
+                ),
+            ));
+        }
+        buf[..slice.len()].copy_from_slice(slice);
+        Ok(slice.len())
+    }
+}
+
+impl Write for RFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.funcall("write", (RString::from_slice(buf),))
+            .map_err(to_io_error)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.funcall::<_, _, Value>("flush", ())
+            .map(|_| ())
+            .map_err(to_io_error)
+    }
+}
+
+impl Seek for RFile {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        // whence values from IO::SEEK_SET, IO::SEEK_CUR, and IO::SEEK_END
+        let (offset, whence) = match pos {
+            SeekFrom::Start(n) => (Value::from(n), 0),
+            SeekFrom::Current(n) => (Value::from(n), 1),
+            SeekFrom::End(n) => (Value::from(n), 2),
+        };
+        self.funcall::<_, _, Value>("seek", (offset, whence))
+            .and_then(|_| self.funcall("pos", ()))
+            .map_err(to_io_error)
+    }
+}
+
+/// Panics if `self` is closed, see [`RFile::fileno`] for a non-panicking
+/// version.
+#[cfg(unix)]
+impl AsRawFd for RFile {
+    fn as_raw_fd(&self) -> RawFd {
+        self.fileno().unwrap_or_else(|e| panic!("{}", e))
+    }
 }
 
 impl Deref for RFile {
@@ -1,20 +1,40 @@
 //! Types and functions for working with Ruby’s Hash class.
 
-use std::{collections::HashMap, fmt, hash::Hash, iter::FromIterator, ops::Deref, os::raw::c_int};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    convert::TryFrom,
+    fmt,
+    hash::{BuildHasher, Hash},
+    iter::FromIterator,
+    marker::PhantomData,
+    ops::Deref,
+    os::raw::{c_char, c_int},
+};
+
+#[cfg(feature = "indexmap")]
+use indexmap::IndexMap;
 
 use crate::{
+    block::Proc,
+    class::{self, RClass},
     debug_assert_value,
     error::{protect, Error},
     exception,
+    module::Module,
     object::Object,
+    r_array::RArray,
     ruby_sys::{
-        rb_check_hash_type, rb_hash_aref, rb_hash_aset, rb_hash_fetch, rb_hash_foreach,
-        rb_hash_lookup, rb_hash_lookup2, rb_hash_new, rb_hash_size, ruby_value_type, VALUE,
+        rb_check_hash_type, rb_hash_aref, rb_hash_aset, rb_hash_clear, rb_hash_delete, rb_hash_dup,
+        rb_hash_fetch, rb_hash_foreach, rb_hash_lookup, rb_hash_lookup2, rb_hash_new, rb_hash_size,
+        rb_hash_update_by, rb_require, ruby_value_type, VALUE,
     },
     try_convert::{TryConvert, TryConvertOwned},
     value::{Fixnum, NonZeroValue, Value, QNIL, QUNDEF},
 };
 
+#[cfg(ruby_gte_3_2)]
+use crate::ruby_sys::rb_hash_new_capa;
+
 /// Iteration state for [`RHash::foreach`].
 #[repr(u32)]
 pub enum ForEach {
@@ -76,6 +96,30 @@ impl RHash {
         unsafe { Self::from_rb_value_unchecked(rb_hash_new()) }
     }
 
+    /// Create a new empty `RHash` with capacity for `n` entries.
+    ///
+    /// The capacity is only a hint, and is ignored before Ruby 3.2.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::RHash;
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let hash = RHash::with_capacity(16);
+    /// assert!(hash.is_empty());
+    /// ```
+    pub fn with_capacity(n: usize) -> RHash {
+        #[cfg(ruby_gte_3_2)]
+        let val = unsafe { rb_hash_new_capa(n as _) };
+        #[cfg(ruby_lt_3_2)]
+        let val = {
+            let _ = n;
+            unsafe { rb_hash_new() }
+        };
+        unsafe { Self::from_rb_value_unchecked(val) }
+    }
+
     /// Set the value `val` for the key `key`.
     ///
     /// Errors if `self` is frozen or `key` does not respond to `hash`.
@@ -110,6 +154,38 @@ impl RHash {
         Ok(())
     }
 
+    /// Set the key/value pairs of `iter` in `self`.
+    ///
+    /// Errors if `self` is frozen or a key does not respond to `hash`. This
+    /// is the fallible version of [`Extend::extend`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RHash};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let hash = RHash::new();
+    /// hash.extend_from(vec![("a", 1), ("b", 2)]).unwrap();
+    /// let res: bool = eval!(r#"hash == {"a" => 1, "b" => 2}"#, hash).unwrap();
+    /// assert!(res);
+    ///
+    /// let hash = eval::<RHash>("{}.freeze").unwrap();
+    /// assert!(hash.extend_from(vec![("a", 1)]).is_err());
+    /// ```
+    pub fn extend_from<I, K, V>(self, iter: I) -> Result<(), Error>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<Value>,
+        V: Into<Value>,
+    {
+        self.check_frozen()?;
+        for (k, v) in iter {
+            self.aset(k, v)?;
+        }
+        Ok(())
+    }
+
     /// Return the value for `key`, converting it to `U`.
     ///
     /// Returns hash's default if `key` is missing. See also
@@ -259,6 +335,200 @@ impl RHash {
         }
     }
 
+    /// Remove the entry for `key` from `self`, returning its value converted
+    /// to `U`.
+    ///
+    /// Returns `nil` if `key` is missing, so `U` should generally be an
+    /// `Option`.
+    ///
+    /// Errors if `self` is frozen.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RHash};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let hash = eval::<RHash>(r#"{"foo" => 1, "bar" => 2}"#).unwrap();
+    /// assert_eq!(hash.delete::<_, Option<i64>>("foo").unwrap(), Some(1));
+    /// assert_eq!(hash.delete::<_, Option<i64>>("foo").unwrap(), None);
+    /// assert_eq!(hash.len(), 1);
+    /// ```
+    pub fn delete<T, U>(self, key: T) -> Result<U, Error>
+    where
+        T: Into<Value>,
+        U: TryConvert,
+    {
+        // unlike Hash#delete, rb_hash_delete doesn't check for frozen
+        self.check_frozen()?;
+        let key = key.into();
+        unsafe {
+            protect(|| Value::new(rb_hash_delete(self.as_rb_value(), key.as_rb_value())))
+                .and_then(|v| v.try_convert())
+        }
+    }
+
+    /// Remove all entries from `self`.
+    ///
+    /// Errors if `self` is frozen.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RHash};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let hash = eval::<RHash>(r#"{"foo" => 1, "bar" => 2}"#).unwrap();
+    /// hash.clear().unwrap();
+    /// assert!(hash.is_empty());
+    /// ```
+    pub fn clear(self) -> Result<(), Error> {
+        unsafe {
+            protect(|| Value::new(rb_hash_clear(self.as_rb_value())))?;
+        }
+        Ok(())
+    }
+
+    /// Add the entries of `other` to `self`, overwriting the values of any
+    /// keys already present.
+    ///
+    /// Errors if `self` is frozen.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RHash};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let hash = eval::<RHash>(r#"{"foo" => 1, "bar" => 2}"#).unwrap();
+    /// let other = eval::<RHash>(r#"{"bar" => 3, "baz" => 4}"#).unwrap();
+    /// hash.update(other).unwrap();
+    /// let res: bool = eval!(r#"hash == {"foo" => 1, "bar" => 3, "baz" => 4}"#, hash).unwrap();
+    /// assert!(res);
+    /// ```
+    pub fn update(self, other: RHash) -> Result<(), Error> {
+        unsafe {
+            protect(|| {
+                Value::new(rb_hash_update_by(
+                    self.as_rb_value(),
+                    other.as_rb_value(),
+                    None,
+                ))
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Return a new hash with the entries of `self` and `other`, with the
+    /// values from `other` taking precedence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RHash};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let hash = eval::<RHash>(r#"{"foo" => 1, "bar" => 2}"#).unwrap();
+    /// let other = eval::<RHash>(r#"{"bar" => 3}"#).unwrap();
+    /// let merged = hash.merge(other).unwrap();
+    /// let res: bool = eval!(r#"merged == {"foo" => 1, "bar" => 3}"#, merged).unwrap();
+    /// assert!(res);
+    /// assert_eq!(hash.len(), 2);
+    /// ```
+    pub fn merge(self, other: RHash) -> Result<RHash, Error> {
+        let hash = unsafe { Self::from_rb_value_unchecked(rb_hash_dup(self.as_rb_value())) };
+        hash.update(other)?;
+        Ok(hash)
+    }
+
+    /// Set `self` to compare keys by identity (rather than with `eql?`).
+    ///
+    /// Errors if `self` is frozen.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{RHash, RString};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let hash = RHash::new();
+    /// hash.compare_by_identity().unwrap();
+    /// assert!(hash.is_compare_by_identity());
+    /// let key = RString::new("foo");
+    /// hash.aset(key, 1).unwrap();
+    /// assert!(hash.get(key).is_some());
+    /// assert!(hash.get("foo").is_none());
+    /// ```
+    pub fn compare_by_identity(self) -> Result<(), Error> {
+        self.funcall::<_, _, Value>("compare_by_identity", ())?;
+        Ok(())
+    }
+
+    /// Return whether `self` compares keys by identity.
+    ///
+    /// See [`compare_by_identity`](RHash::compare_by_identity).
+    pub fn is_compare_by_identity(self) -> bool {
+        self.funcall("compare_by_identity?", ()).unwrap_or(false)
+    }
+
+    /// Return the default value of `self`, converted to `U`.
+    ///
+    /// This is the value returned by [`aref`](RHash::aref) for a missing key.
+    /// Returns `nil` if `self` has a default proc.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RHash};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let hash = eval::<RHash>("Hash.new(0)").unwrap();
+    /// assert_eq!(hash.default_value::<i64>().unwrap(), 0);
+    /// hash.set_default_value(()).unwrap();
+    /// assert_eq!(hash.default_value::<Option<i64>>().unwrap(), None);
+    /// ```
+    pub fn default_value<U>(self) -> Result<U, Error>
+    where
+        U: TryConvert,
+    {
+        self.funcall("default", ())
+    }
+
+    /// Set the default value of `self`, returned by [`aref`](RHash::aref)
+    /// for a missing key.
+    ///
+    /// This replaces any default proc. Errors if `self` is frozen.
+    pub fn set_default_value<T>(self, val: T) -> Result<(), Error>
+    where
+        T: Into<Value>,
+    {
+        self.funcall::<_, _, Value>("default=", (val.into(),))?;
+        Ok(())
+    }
+
+    /// Set the default proc of `self`, called by [`aref`](RHash::aref) with
+    /// `self` and the key to generate a value for a missing key.
+    ///
+    /// This replaces any default value. Errors if `self` is frozen or `proc`
+    /// is a lambda taking a number of arguments other than 2.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, block::Proc, RHash};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let hash = RHash::new();
+    /// let proc: Proc = eval("proc { |hash, key| hash[key] = key * 2 }").unwrap();
+    /// hash.set_default_proc(proc).unwrap();
+    /// assert_eq!(hash.aref::<_, i64>(21).unwrap(), 42);
+    /// assert_eq!(hash.len(), 1);
+    /// ```
+    pub fn set_default_proc(self, proc: Proc) -> Result<(), Error> {
+        self.funcall::<_, _, Value>("default_proc=", (proc,))?;
+        Ok(())
+    }
+
     fn base_foreach<F>(self, mut func: F) -> Result<(), Error>
     where
         F: FnMut(Value, Value) -> ForEach,
@@ -326,6 +596,88 @@ impl RHash {
         res
     }
 
+    /// Returns an iterator over the key/value pairs of `self`, in insertion
+    /// order, converting keys to `K` and values to `V`.
+    ///
+    /// The iterator works over a snapshot of `self` taken when it was
+    /// created, held in a hidden Ruby array, so changes to `self` during
+    /// iteration are not reflected. See [`Iter`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RHash};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let hash = eval::<RHash>(r#"{"foo" => 1, "bar" => 2}"#).unwrap();
+    /// let mut res = Vec::new();
+    /// for pair in hash.iter::<String, i64>() {
+    ///     res.push(pair.unwrap());
+    /// }
+    /// assert_eq!(res, vec![(String::from("foo"), 1), (String::from("bar"), 2)]);
+    /// ```
+    pub fn iter<K, V>(self) -> Iter<K, V>
+    where
+        K: TryConvert,
+        V: TryConvert,
+    {
+        // flat array of [key, value, key, value, ...]
+        let entries = RArray::with_capacity(self.len() * 2);
+        self.base_foreach(|key, value| {
+            entries.push(key).unwrap();
+            entries.push(value).unwrap();
+            ForEach::Continue
+        })
+        .unwrap();
+        Iter {
+            entries,
+            i: 0,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Return the keys of `self` as a new Ruby array, in insertion order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RHash};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let hash = eval::<RHash>(r#"{"foo" => 1, "bar" => 2}"#).unwrap();
+    /// assert_eq!(hash.keys().to_vec::<String>().unwrap(), vec!["foo", "bar"]);
+    /// ```
+    pub fn keys(self) -> RArray {
+        let keys = RArray::with_capacity(self.len());
+        self.base_foreach(|key, _| {
+            keys.push(key).unwrap();
+            ForEach::Continue
+        })
+        .unwrap();
+        keys
+    }
+
+    /// Return the values of `self` as a new Ruby array, in insertion order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RHash};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let hash = eval::<RHash>(r#"{"foo" => 1, "bar" => 2}"#).unwrap();
+    /// assert_eq!(hash.values().to_vec::<i64>().unwrap(), vec![1, 2]);
+    /// ```
+    pub fn values(self) -> RArray {
+        let values = RArray::with_capacity(self.len());
+        self.base_foreach(|_, value| {
+            values.push(value).unwrap();
+            ForEach::Continue
+        })
+        .unwrap();
+        values
+    }
+
     /// Return `self` converted to a Rust [`HashMap`].
     ///
     /// This will only convert to a map of 'owned' Rust native types. The types
@@ -334,6 +686,10 @@ impl RHash {
     /// of Ruby's garbage collector, and thus may be prematurely garbage
     /// collected in the following sweep phase.
     ///
+    /// A [`HashMap`] does not keep the insertion order of the Ruby hash, see
+    /// [`to_vec`](RHash::to_vec), or the `indexmap` feature for conversion to
+    /// an `IndexMap`, to keep the order.
+    ///
     /// Errors if the conversion of any key or value fails.
     ///
     /// # Examples
@@ -478,6 +834,27 @@ where
     }
 }
 
+impl<K, V> From<BTreeMap<K, V>> for Value
+where
+    K: Into<Value>,
+    V: Into<Value>,
+{
+    fn from(map: BTreeMap<K, V>) -> Self {
+        map.into_iter().collect::<RHash>().into()
+    }
+}
+
+#[cfg(feature = "indexmap")]
+impl<K, V, S> From<IndexMap<K, V, S>> for Value
+where
+    K: Into<Value>,
+    V: Into<Value>,
+{
+    fn from(map: IndexMap<K, V, S>) -> Self {
+        map.into_iter().collect::<RHash>().into()
+    }
+}
+
 impl<K, V> FromIterator<(K, V)> for RHash
 where
     K: Into<Value>,
@@ -487,14 +864,69 @@ where
     where
         I: IntoIterator<Item = (K, V)>,
     {
-        let hash = RHash::new();
-        for (k, v) in iter {
-            let _ = hash.aset(k, v);
-        }
+        let iter = iter.into_iter();
+        let (lower, _) = iter.size_hint();
+        let mut hash = RHash::with_capacity(lower);
+        hash.extend(iter);
         hash
     }
 }
 
+/// # Panics
+///
+/// Panics if `self` is frozen or a key does not respond to `hash`. See
+/// [`RHash::extend_from`] for a fallible alternative.
+impl<K, V> Extend<(K, V)> for RHash
+where
+    K: Into<Value>,
+    V: Into<Value>,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = (K, V)>,
+    {
+        self.extend_from(iter).unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+/// An iterator over the key/value pairs of a [`RHash`], see [`RHash::iter`].
+///
+/// The pairs are copied to a Ruby array when the iterator is created, so
+/// changes to the hash during iteration are not reflected.
+///
+/// As with [`RHash`], this type is only safe to use on the stack.
+pub struct Iter<K, V> {
+    entries: RArray,
+    i: usize,
+    phantom: PhantomData<(K, V)>,
+}
+
+impl<K, V> Iterator for Iter<K, V>
+where
+    K: TryConvert,
+    V: TryConvert,
+{
+    type Item = Result<(K, V), Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.i >= self.entries.len() {
+            return None;
+        }
+        let i = self.i as isize;
+        self.i += 2;
+        Some(
+            self.entries
+                .entry(i)
+                .and_then(|k| Ok((k, self.entries.entry(i + 1)?))),
+        )
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.entries.len().saturating_sub(self.i) / 2;
+        (remaining, Some(remaining))
+    }
+}
+
 impl Object for RHash {}
 
 impl TryConvert for RHash {
@@ -516,3 +948,70 @@ impl TryConvert for RHash {
         }
     }
 }
+
+#[cfg(feature = "indexmap")]
+impl<K, V, S> TryConvert for IndexMap<K, V, S>
+where
+    K: TryConvertOwned + Eq + Hash,
+    V: TryConvertOwned,
+    S: BuildHasher + Default,
+{
+    fn try_convert(val: &Value) -> Result<Self, Error> {
+        debug_assert_value!(val);
+        RHash::try_convert(val)?.iter().collect()
+    }
+}
+#[cfg(feature = "indexmap")]
+impl<K, V, S> TryConvertOwned for IndexMap<K, V, S>
+where
+    K: TryConvertOwned + Eq + Hash,
+    V: TryConvertOwned,
+    S: BuildHasher + Default,
+{
+}
+
+// Ruby's Set is built on Hash. Before Ruby 3.2 it must be required.
+fn set_class() -> Result<RClass, Error> {
+    unsafe {
+        protect(|| Value::new(rb_require("set\0".as_ptr() as *const c_char)))?;
+    }
+    class::object().const_get("Set")
+}
+
+impl<T, S> TryFrom<HashSet<T, S>> for Value
+where
+    T: Into<Value>,
+{
+    type Error = Error;
+
+    /// Converts to a Ruby `Set`.
+    ///
+    /// Returns `Err` if the `set` library could not be loaded.
+    fn try_from(set: HashSet<T, S>) -> Result<Self, Self::Error> {
+        let ary = set.into_iter().collect::<RArray>();
+        set_class()?.new_instance((ary,))
+    }
+}
+
+/// Converts from a Ruby `Set` or `Array`.
+impl<T, S> TryConvert for HashSet<T, S>
+where
+    T: TryConvertOwned + Eq + Hash,
+    S: BuildHasher + Default,
+{
+    fn try_convert(val: &Value) -> Result<Self, Error> {
+        debug_assert_value!(val);
+        let ary = match RArray::from_value(*val) {
+            Some(ary) => ary,
+            None if val.is_kind_of(set_class()?) => val.funcall("to_a", ())?,
+            None => RArray::try_convert(val)?,
+        };
+        Ok(ary.to_vec::<T>()?.into_iter().collect())
+    }
+}
+impl<T, S> TryConvertOwned for HashSet<T, S>
+where
+    T: TryConvertOwned + Eq + Hash,
+    S: BuildHasher + Default,
+{
+}
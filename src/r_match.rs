@@ -1,12 +1,21 @@
-use std::{fmt, ops::Deref};
+use std::{
+    convert::TryFrom,
+    fmt, iter,
+    ops::Deref,
+    os::raw::{c_int, c_long},
+};
 
 use crate::{
-    error::Error,
+    error::{protect, Error},
     exception,
     object::Object,
-    ruby_sys::ruby_value_type,
+    r_string::RString,
+    ruby_sys::{
+        rb_reg_backref_number, rb_reg_match_post, rb_reg_match_pre, rb_reg_nth_defined,
+        rb_reg_nth_match, rb_str_offset, ruby_value_type,
+    },
     try_convert::TryConvert,
-    value::{NonZeroValue, Value},
+    value::{NonZeroValue, Value, QNIL},
 };
 
 /// A Value pointer to a RMatch struct, Ruby's internal representation of the
@@ -20,6 +29,16 @@ pub struct RMatch(NonZeroValue);
 
 impl RMatch {
     /// Return `Some(RMatch)` if `val` is a `RMatch`, `None` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RMatch};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// assert!(RMatch::from_value(eval(r#"/(.)(.)(.)/.match("foo")"#).unwrap()).is_some());
+    /// assert!(RMatch::from_value(eval(r#""foo""#).unwrap()).is_none());
+    /// ```
     #[inline]
     pub fn from_value(val: Value) -> Option<Self> {
         unsafe {
@@ -27,6 +46,165 @@ impl RMatch {
                 .then(|| Self(NonZeroValue::new_unchecked(val)))
         }
     }
+
+    fn nth_defined(self, i: c_int) -> Value {
+        unsafe { Value::new(rb_reg_nth_defined(i, self.as_rb_value())) }
+    }
+
+    /// Return the text of the `i`th group of the match, or `None` if the
+    /// group did not participate in the match or `i` is out of range.
+    ///
+    /// Index 0 is the whole match. Negative indices count backwards from
+    /// the last group.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RMatch};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let m = eval::<RMatch>(r#"/(.)(x)?(.)/.match("foo")"#).unwrap();
+    /// assert_eq!(m.nth(0).unwrap().to_string().unwrap(), "fo");
+    /// assert_eq!(m.nth(1).unwrap().to_string().unwrap(), "f");
+    /// assert!(m.nth(2).is_none());
+    /// assert_eq!(m.nth(-1).unwrap().to_string().unwrap(), "o");
+    /// assert!(m.nth(4).is_none());
+    /// assert!(m.nth(isize::MAX).is_none());
+    /// ```
+    pub fn nth(self, i: isize) -> Option<RString> {
+        let i = c_int::try_from(i).ok()?;
+        unsafe { RString::from_value(Value::new(rb_reg_nth_match(i, self.as_rb_value()))) }
+    }
+
+    /// Return the text of the group named `name`, or `None` if the group did
+    /// not participate in the match.
+    ///
+    /// Returns `Err` if there is no group named `name`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RMatch};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let m = eval::<RMatch>(r#"/(?<first>.)(?<second>x)?/.match("foo")"#).unwrap();
+    /// assert_eq!(m.named("first").unwrap().unwrap().to_string().unwrap(), "f");
+    /// assert!(m.named("second").unwrap().is_none());
+    /// assert!(m.named("third").is_err());
+    /// ```
+    pub fn named(self, name: &str) -> Result<Option<RString>, Error> {
+        let name = RString::new(name);
+        let mut i = 0;
+        unsafe {
+            protect(|| {
+                i = rb_reg_backref_number(self.as_rb_value(), name.as_rb_value());
+                *QNIL
+            })?;
+        }
+        Ok(self.nth(i as isize))
+    }
+
+    /// Return the byte offset of the start of the `i`th group of the match,
+    /// or `None` if the group did not participate in the match.
+    ///
+    /// Returns `Err` if `i` is out of range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RMatch};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let m = eval::<RMatch>(r#"/(ö)(x)?/.match("föo")"#).unwrap();
+    /// assert_eq!(m.begin(1).unwrap(), Some(1));
+    /// assert_eq!(m.begin(2).unwrap(), None);
+    /// assert!(m.begin(3).is_err());
+    /// ```
+    pub fn begin(self, i: usize) -> Result<Option<usize>, Error> {
+        self.offset("begin", i)
+    }
+
+    /// Return the byte offset of the end of the `i`th group of the match, or
+    /// `None` if the group did not participate in the match.
+    ///
+    /// Returns `Err` if `i` is out of range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RMatch};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let m = eval::<RMatch>(r#"/(ö)(x)?/.match("föo")"#).unwrap();
+    /// assert_eq!(m.end(1).unwrap(), Some(3));
+    /// assert_eq!(m.end(2).unwrap(), None);
+    /// assert!(m.end(3).is_err());
+    /// ```
+    pub fn end(self, i: usize) -> Result<Option<usize>, Error> {
+        self.offset("end", i)
+    }
+
+    fn offset(self, method: &str, i: usize) -> Result<Option<usize>, Error> {
+        // MatchData#begin/#end give character offsets, convert to bytes
+        let pos: Option<usize> = self.funcall(method, (i,))?;
+        let s: RString = self.funcall("string", ())?;
+        Ok(pos.map(|pos| unsafe { rb_str_offset(s.as_rb_value(), pos as c_long) as usize }))
+    }
+
+    /// Return the part of the string before the match.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RMatch};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let m = eval::<RMatch>(r#"/b/.match("abc")"#).unwrap();
+    /// assert_eq!(m.pre_match().to_string().unwrap(), "a");
+    /// ```
+    pub fn pre_match(self) -> RString {
+        unsafe { RString::from_rb_value_unchecked(rb_reg_match_pre(self.as_rb_value())) }
+    }
+
+    /// Return the part of the string after the match.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RMatch};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let m = eval::<RMatch>(r#"/b/.match("abc")"#).unwrap();
+    /// assert_eq!(m.post_match().to_string().unwrap(), "c");
+    /// ```
+    pub fn post_match(self) -> RString {
+        unsafe { RString::from_rb_value_unchecked(rb_reg_match_post(self.as_rb_value())) }
+    }
+
+    /// Returns an iterator over the captured groups of the match, not
+    /// including the whole match. Groups that did not participate in the
+    /// match are `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RMatch};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let m = eval::<RMatch>(r#"/(.)(x)?(.)/.match("foo")"#).unwrap();
+    /// let captures = m
+    ///     .captures()
+    ///     .map(|c| c.map(|s| s.to_string().unwrap()))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(captures, vec![Some(String::from("f")), None, Some(String::from("o"))]);
+    /// ```
+    pub fn captures(self) -> impl Iterator<Item = Option<RString>> {
+        let mut i = 0;
+        iter::from_fn(move || {
+            i += 1;
+            (!self.nth_defined(i).is_nil()).then(|| self.nth(i as isize))
+        })
+    }
 }
 
 impl Deref for RMatch {
@@ -1,10 +1,14 @@
-use std::{fmt, ops::Deref};
+use std::{convert::TryFrom, fmt, ops::Deref};
 
 use crate::{
-    error::Error,
+    debug_assert_value,
+    error::{protect, Error},
     exception,
-    ruby_sys::ruby_value_type,
-    try_convert::TryConvert,
+    integer::Integer,
+    ruby_sys::{
+        rb_num2dbl, rb_rational_den, rb_rational_new, rb_rational_num, ruby_value_type, VALUE,
+    },
+    try_convert::{TryConvert, TryConvertOwned},
     value::{NonZeroValue, Value},
 };
 
@@ -19,6 +23,16 @@ pub struct RRational(NonZeroValue);
 
 impl RRational {
     /// Return `Some(RRational)` if `val` is a `RRational`, `None` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RRational};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// assert!(RRational::from_value(eval("1/2r").unwrap()).is_some());
+    /// assert!(RRational::from_value(eval("0.5").unwrap()).is_none());
+    /// ```
     #[inline]
     pub fn from_value(val: Value) -> Option<Self> {
         unsafe {
@@ -26,6 +40,132 @@ impl RRational {
                 .then(|| Self(NonZeroValue::new_unchecked(val)))
         }
     }
+
+    #[inline]
+    pub(crate) unsafe fn from_rb_value_unchecked(val: VALUE) -> Self {
+        Self(NonZeroValue::new_unchecked(Value::new(val)))
+    }
+
+    /// Create a new `RRational` from a numerator and denominator.
+    ///
+    /// The result is reduced to its simplest form, with a positive
+    /// denominator. Returns `Err` if `den` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, Integer, RRational};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let rational = RRational::new(Integer::from_i64(2), Integer::from_i64(-4)).unwrap();
+    /// let res: bool = eval!("rational == -1/2r", rational).unwrap();
+    /// assert!(res);
+    /// assert!(RRational::new(Integer::from_i64(1), Integer::from_i64(0)).is_err());
+    /// ```
+    pub fn new(num: Integer, den: Integer) -> Result<Self, Error> {
+        debug_assert_value!(num);
+        debug_assert_value!(den);
+        unsafe {
+            protect(|| Value::new(rb_rational_new(num.as_rb_value(), den.as_rb_value())))
+                .map(|v| Self::from_rb_value_unchecked(v.as_rb_value()))
+        }
+    }
+
+    /// Return the numerator of `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RRational};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let rational = eval::<RRational>("6/4r").unwrap();
+    /// assert_eq!(rational.numerator().to_i64().unwrap(), 3);
+    /// ```
+    pub fn numerator(self) -> Integer {
+        unsafe { Integer::from_rb_value_unchecked(rb_rational_num(self.as_rb_value())) }
+    }
+
+    /// Return the denominator of `self`.
+    ///
+    /// This is always positive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RRational};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let rational = eval::<RRational>("6/-4r").unwrap();
+    /// assert_eq!(rational.denominator().to_i64().unwrap(), 2);
+    /// ```
+    pub fn denominator(self) -> Integer {
+        unsafe { Integer::from_rb_value_unchecked(rb_rational_den(self.as_rb_value())) }
+    }
+
+    /// Convert `self` to a `f64`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RRational};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// assert_eq!(eval::<RRational>("1/4r").unwrap().to_f64(), 0.25);
+    /// ```
+    pub fn to_f64(self) -> f64 {
+        unsafe { rb_num2dbl(self.as_rb_value()) }
+    }
+}
+
+impl TryFrom<(i64, i64)> for RRational {
+    type Error = Error;
+
+    /// Create a new `RRational` from a `(numerator, denominator)` pair.
+    ///
+    /// Returns `Err` if the denominator is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use magnus::{eval, RRational};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let rational = RRational::try_from((3, 6)).unwrap();
+    /// let res: bool = eval!("rational == 1/2r", rational).unwrap();
+    /// assert!(res);
+    /// assert!(RRational::try_from((1, 0)).is_err());
+    /// ```
+    fn try_from((num, den): (i64, i64)) -> Result<Self, Self::Error> {
+        Self::new(Integer::from_i64(num), Integer::from_i64(den))
+    }
+}
+
+impl TryFrom<RRational> for (i64, i64) {
+    type Error = Error;
+
+    /// Convert a `RRational` to a `(numerator, denominator)` pair.
+    ///
+    /// Returns `Err` if the numerator or denominator is out of range for
+    /// `i64`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use magnus::{eval, RRational};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let pair = <(i64, i64)>::try_from(eval::<RRational>("-3/6r").unwrap()).unwrap();
+    /// assert_eq!(pair, (-1, 2));
+    /// assert!(<(i64, i64)>::try_from(eval::<RRational>("2 ** 64 / 3r").unwrap()).is_err());
+    /// ```
+    fn try_from(val: RRational) -> Result<Self, Self::Error> {
+        Ok((val.numerator().to_i64()?, val.denominator().to_i64()?))
+    }
 }
 
 impl Deref for RRational {
@@ -67,3 +207,49 @@ impl TryConvert for RRational {
         })
     }
 }
+
+#[cfg(feature = "num-rational")]
+impl<T> TryFrom<num_rational::Ratio<T>> for RRational
+where
+    T: Into<Value>,
+{
+    type Error = Error;
+
+    /// Create a new `RRational` from a `Ratio`.
+    ///
+    /// Returns `Err` if the denominator is zero, or the numerator or
+    /// denominator do not convert to a Ruby `Integer`.
+    fn try_from(val: num_rational::Ratio<T>) -> Result<Self, Self::Error> {
+        // no implicit conversion with `to_int`, which would truncate floats
+        fn integer(val: Value) -> Result<Integer, Error> {
+            Integer::from_value(val).ok_or_else(|| {
+                Error::new(
+                    exception::type_error(),
+                    format!("no implicit conversion of {} into Integer", unsafe {
+                        val.classname()
+                    },),
+                )
+            })
+        }
+        let (num, den) = val.into_raw();
+        Self::new(integer(num.into())?, integer(den.into())?)
+    }
+}
+
+#[cfg(feature = "num-rational")]
+impl<T> TryConvert for num_rational::Ratio<T>
+where
+    T: TryConvert,
+{
+    fn try_convert(val: &Value) -> Result<Self, Error> {
+        let rational = RRational::try_convert(val)?;
+        // Ruby rationals are always in their simplest form, with a positive
+        // denominator, so there's no need to normalise
+        Ok(Self::new_raw(
+            rational.numerator().try_convert()?,
+            rational.denominator().try_convert()?,
+        ))
+    }
+}
+#[cfg(feature = "num-rational")]
+impl<T> TryConvertOwned for num_rational::Ratio<T> where T: TryConvertOwned {}
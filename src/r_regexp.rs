@@ -1,11 +1,19 @@
-use std::{fmt, ops::Deref};
+use std::{
+    fmt,
+    ops::Deref,
+    os::raw::{c_int, c_long},
+};
 
 use crate::{
-    error::Error,
+    error::{protect, Error},
     exception,
-    ruby_sys::ruby_value_type,
+    r_match::RMatch,
+    r_string::RString,
+    ruby_sys::{
+        rb_backref_get, rb_reg_new_str, rb_reg_options, rb_reg_search, ruby_value_type, VALUE,
+    },
     try_convert::TryConvert,
-    value::{NonZeroValue, Value},
+    value::{NonZeroValue, Value, QNIL},
 };
 
 /// A Value pointer to a RRegexp struct, Ruby's internal representation of
@@ -18,7 +26,28 @@ use crate::{
 pub struct RRegexp(NonZeroValue);
 
 impl RRegexp {
+    /// Ignore case when matching.
+    pub const IGNORECASE: i32 = 1;
+    /// Ignore whitespace and comments in the pattern.
+    pub const EXTENDED: i32 = 2;
+    /// Allow `.` to match a newline.
+    pub const MULTILINE: i32 = 4;
+    /// Match using the encoding of the pattern, rather than the string.
+    pub const FIXEDENCODING: i32 = 16;
+    /// Treat the pattern as having no encoding.
+    pub const NOENCODING: i32 = 32;
+
     /// Return `Some(RRegexp)` if `val` is a `RRegexp`, `None` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RRegexp};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// assert!(RRegexp::from_value(eval("/f(.)o/").unwrap()).is_some());
+    /// assert!(RRegexp::from_value(eval(r#""f(.)o""#).unwrap()).is_none());
+    /// ```
     #[inline]
     pub fn from_value(val: Value) -> Option<Self> {
         unsafe {
@@ -26,6 +55,108 @@ impl RRegexp {
                 .then(|| Self(NonZeroValue::new_unchecked(val)))
         }
     }
+
+    #[inline]
+    pub(crate) unsafe fn from_rb_value_unchecked(val: VALUE) -> Self {
+        Self(NonZeroValue::new_unchecked(Value::new(val)))
+    }
+
+    /// Create a new `Regexp` from the Ruby regular expression source
+    /// `pattern`, with `opts` a combination of [`RRegexp::IGNORECASE`],
+    /// [`RRegexp::EXTENDED`], etc.
+    ///
+    /// Returns `Err` if `pattern` is not a valid regular expression.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RRegexp};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let regexp = RRegexp::new("foo", RRegexp::IGNORECASE).unwrap();
+    /// let res: bool = eval!(r#"regexp.match?("FOO")"#, regexp).unwrap();
+    /// assert!(res);
+    /// assert!(RRegexp::new("(", 0).is_err());
+    /// ```
+    pub fn new(pattern: &str, opts: i32) -> Result<Self, Error> {
+        let pattern = RString::new(pattern);
+        unsafe {
+            protect(|| Value::new(rb_reg_new_str(pattern.as_rb_value(), opts as c_int)))
+                .map(|v| Self::from_rb_value_unchecked(v.as_rb_value()))
+        }
+    }
+
+    /// Search for `self` in `s`, starting at byte offset `pos`.
+    ///
+    /// Returns `None` if there is no match. Like Ruby's own matching methods,
+    /// this sets `$~` (see [`backref_get`](crate::backref_get)) in the calling
+    /// Ruby frame.
+    ///
+    /// Returns `Err` if `s` is not compatible with the encoding of `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{RRegexp, RString};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let regexp = RRegexp::new("o+", 0).unwrap();
+    /// let s = RString::new("foo boo");
+    /// let m = regexp.match_at(s, 3).unwrap().unwrap();
+    /// assert_eq!(m.begin(0).unwrap(), Some(5));
+    /// assert!(regexp.match_at(s, 7).unwrap().is_none());
+    /// ```
+    pub fn match_at(self, s: RString, pos: usize) -> Result<Option<RMatch>, Error> {
+        let mut res = 0;
+        unsafe {
+            protect(|| {
+                res = rb_reg_search(self.as_rb_value(), s.as_rb_value(), pos as c_long, 0);
+                *QNIL
+            })?;
+            if res < 0 {
+                return Ok(None);
+            }
+            Ok(RMatch::from_value(Value::new(rb_backref_get())))
+        }
+    }
+
+    /// Return the source of `self`, the pattern it was created from.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RRegexp};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let regexp = eval::<RRegexp>("/f(.)o/").unwrap();
+    /// assert_eq!(regexp.source().unwrap().to_string().unwrap(), "f(.)o");
+    /// ```
+    pub fn source(self) -> Result<RString, Error> {
+        self.funcall("source", ())
+    }
+
+    /// Return the options of `self`, a combination of
+    /// [`RRegexp::IGNORECASE`], [`RRegexp::EXTENDED`], etc.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RRegexp};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let regexp = eval::<RRegexp>("/foo/ix").unwrap();
+    /// assert_eq!(regexp.options().unwrap(), RRegexp::IGNORECASE | RRegexp::EXTENDED);
+    /// ```
+    pub fn options(self) -> Result<i32, Error> {
+        let mut res = 0;
+        unsafe {
+            protect(|| {
+                res = rb_reg_options(self.as_rb_value());
+                *QNIL
+            })?;
+        }
+        Ok(res)
+    }
 }
 
 impl Deref for RRegexp {
@@ -5,7 +5,7 @@ use std::{
     ffi::CStr,
     fmt, io,
     ops::Deref,
-    os::raw::{c_char, c_long},
+    os::raw::{c_char, c_int, c_long},
     path::{Path, PathBuf},
     ptr::{self, NonNull},
     slice, str,
@@ -13,17 +13,19 @@ use std::{
 
 use crate::{
     debug_assert_value,
+    encoding::{Encoding, Index, RbEncoding},
     error::{protect, Error},
     exception,
     object::Object,
     ruby_sys::{
-        self, rb_enc_associate_index, rb_enc_get, rb_enc_get_index, rb_str_buf_append,
-        rb_str_buf_new, rb_str_cat, rb_str_conv_enc, rb_str_new, rb_str_to_str,
+        self, rb_enc_associate_index, rb_enc_get, rb_enc_get_index, rb_enc_str_coderange,
+        rb_enc_str_new, rb_str_buf_append, rb_str_buf_new, rb_str_cat, rb_str_conv_enc,
+        rb_str_encode, rb_str_locktmp, rb_str_new, rb_str_to_str, rb_str_unlocktmp,
         rb_usascii_encindex, rb_utf8_encindex, rb_utf8_encoding, rb_utf8_str_new,
-        rb_utf8_str_new_static, ruby_rstring_flags, ruby_value_type, VALUE,
+        rb_utf8_str_new_static, ruby_coderange_type, ruby_rstring_flags, ruby_value_type, VALUE,
     },
     try_convert::TryConvert,
-    value::{NonZeroValue, Value},
+    value::{NonZeroValue, Value, QNIL},
 };
 
 #[cfg(ruby_gte_3_0)]
@@ -171,6 +173,40 @@ impl RString {
         unsafe { Self::from_rb_value_unchecked(rb_str_new(ptr as *const c_char, len as c_long)) }
     }
 
+    /// Create a new Ruby string from the Rust slice `s` with the encoding
+    /// `enc`.
+    ///
+    /// The bytes are not checked to be valid in `enc`, see
+    /// [`is_valid_encoding`](RString::is_valid_encoding).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{encoding::RbEncoding, eval, RString};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let sjis = RbEncoding::find("Shift_JIS").unwrap();
+    /// let s = RString::enc_new(&[130, 160], sjis);
+    /// let res: bool = eval!(r#"s == "あ".encode("Shift_JIS")"#, s).unwrap();
+    /// assert!(res);
+    /// ```
+    pub fn enc_new<T, E>(s: T, enc: E) -> Self
+    where
+        T: AsRef<[u8]>,
+        E: Into<RbEncoding>,
+    {
+        let s = s.as_ref();
+        let len = s.len();
+        let ptr = s.as_ptr();
+        unsafe {
+            Self::from_rb_value_unchecked(rb_enc_str_new(
+                ptr as *const c_char,
+                len as c_long,
+                enc.into().as_ptr(),
+            ))
+        }
+    }
+
     /// Create a new Ruby string from the Rust char `c`.
     ///
     /// The encoding of the Ruby string will be UTF-8.
@@ -225,6 +261,80 @@ impl RString {
         self.as_slice_unconstrained()
     }
 
+    /// Call `func` with a slice of the bytes of `self`.
+    ///
+    /// `self` is locked for the duration of `func`, and any attempt to
+    /// modify it from Ruby will raise an error, so unlike
+    /// [`as_slice`](RString::as_slice) this is safe to use even if `func`
+    /// calls Ruby.
+    ///
+    /// Errors if `self` is already locked.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::RString;
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let s = RString::new("example");
+    /// let count = s.with_bytes(|b| b.iter().filter(|&&c| c == b'e').count()).unwrap();
+    /// assert_eq!(count, 2);
+    /// ```
+    ///
+    /// ```
+    /// use magnus::{eval, RString};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let s = RString::new("example");
+    /// s.with_bytes(|_| {
+    ///     let res: Result<bool, _> = eval!(r#"s << "!""#, s);
+    ///     assert!(res.is_err());
+    /// })
+    /// .unwrap();
+    /// ```
+    pub fn with_bytes<F, R>(self, func: F) -> Result<R, Error>
+    where
+        F: FnOnce(&[u8]) -> R,
+    {
+        struct Unlock(RString);
+
+        impl Drop for Unlock {
+            fn drop(&mut self) {
+                unsafe { rb_str_unlocktmp(self.0.as_rb_value()) };
+            }
+        }
+
+        unsafe {
+            protect(|| Value::new(rb_str_locktmp(self.as_rb_value())))?;
+            let _unlock = Unlock(self);
+            Ok(func(self.as_slice()))
+        }
+    }
+
+    /// Call `func` with `self` as a Rust `&str`.
+    ///
+    /// `self` is locked for the duration of `func`, see
+    /// [`with_bytes`](RString::with_bytes).
+    ///
+    /// Errors if `self`'s encoding is not UTF-8 (or US-ASCII), if the string
+    /// is not valid UTF-8, or if `self` is already locked.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::RString;
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let s = RString::new("example");
+    /// assert_eq!(s.with_str(|s| s.to_uppercase()).unwrap(), "EXAMPLE");
+    /// ```
+    pub fn with_str<F, R>(self, func: F) -> Result<R, Error>
+    where
+        F: FnOnce(&str) -> R,
+    {
+        self.with_bytes(|_| unsafe { self.as_str().map(func) })?
+    }
+
     unsafe fn as_slice_unconstrained<'a>(self) -> &'a [u8] {
         #[cfg(ruby_gte_3_1)]
         unsafe fn embedded_ary_ptr(rstring: RString) -> *const u8 {
@@ -309,6 +419,108 @@ impl RString {
         }
     }
 
+    /// Returns the encoding of `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{encoding::RbEncoding, RString};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// assert_eq!(RString::new("example").encoding(), RbEncoding::utf8());
+    /// assert_eq!(RString::from_slice(b"example").encoding(), RbEncoding::ascii8bit());
+    /// ```
+    pub fn encoding(self) -> RbEncoding {
+        RbEncoding::from(Index::from_int(unsafe {
+            rb_enc_get_index(self.as_rb_value())
+        }))
+    }
+
+    /// Changes the encoding of `self` to `enc`, without changing the
+    /// underlying bytes.
+    ///
+    /// Errors if `self` is frozen.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{encoding::RbEncoding, eval, RString};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let s = RString::from_slice("café".as_bytes());
+    /// s.force_encoding(RbEncoding::utf8()).unwrap();
+    /// let res: bool = eval!(r#"s == "café""#, s).unwrap();
+    /// assert!(res);
+    /// ```
+    pub fn force_encoding<T>(self, enc: T) -> Result<(), Error>
+    where
+        T: Into<Index>,
+    {
+        let index = enc.into();
+        unsafe {
+            protect(|| Value::new(rb_enc_associate_index(self.as_rb_value(), index.to_int())))?;
+        }
+        Ok(())
+    }
+
+    /// Returns a new string by transcoding `self` from its current encoding
+    /// to `enc`.
+    ///
+    /// Errors if `self` contains characters that are invalid in its current
+    /// encoding, or that can not be represented in `enc`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{encoding::RbEncoding, RString};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let sjis = RbEncoding::find("Shift_JIS").unwrap();
+    /// let s = RString::new("あ").conv(sjis).unwrap();
+    /// assert_eq!(s.encoding(), sjis);
+    /// // safe as we don't give Ruby the chance to mess with the string while
+    /// // we hold a refrence to the slice.
+    /// unsafe { assert_eq!(s.as_slice(), &[130, 160]) };
+    ///
+    /// assert!(RString::new("🦀").conv(sjis).is_err());
+    /// ```
+    pub fn conv<T>(self, enc: T) -> Result<Self, Error>
+    where
+        T: Into<Encoding>,
+    {
+        let enc = enc.into();
+        unsafe {
+            protect(|| {
+                Value::new(rb_str_encode(
+                    self.as_rb_value(),
+                    enc.as_rb_value(),
+                    0,
+                    QNIL.as_rb_value(),
+                ))
+            })
+            .map(|v| Self::from_rb_value_unchecked(v.as_rb_value()))
+        }
+    }
+
+    /// Returns whether `self` is valid in its encoding.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RString};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// assert!(RString::new("café").is_valid_encoding());
+    /// let s: RString = eval(r#""caf\xE9""#).unwrap();
+    /// assert!(!s.is_valid_encoding());
+    /// ```
+    pub fn is_valid_encoding(self) -> bool {
+        unsafe {
+            rb_enc_str_coderange(self.as_rb_value())
+                != ruby_coderange_type::RUBY_ENC_CODERANGE_BROKEN as c_int
+        }
+    }
+
     /// Returns a Rust `&str` reference to the value of `self`.
     ///
     /// Errors if `self`'s encoding is not UTF-8 (or US-ASCII), or if the
@@ -404,6 +616,18 @@ impl RString {
             .map_err(|e| Error::new(exception::encoding_error(), format!("{}", e)))
     }
 
+    // Check `self` can be converted with `to_string`, without copying it.
+    pub(crate) fn check_utf8(self) -> Result<(), Error> {
+        let utf8 = if self.is_utf8_compatible_encoding() {
+            self
+        } else {
+            self.encode_utf8()?
+        };
+        str::from_utf8(unsafe { utf8.as_slice() })
+            .map(|_| ())
+            .map_err(|e| Error::new(exception::encoding_error(), format!("{}", e)))
+    }
+
     /// Converts `self` to a [`char`]. Errors if the string is more than one
     /// character or can not be encoded as UTF-8.
     ///
@@ -0,0 +1,311 @@
+use std::{
+    fmt,
+    ops::Deref,
+    os::raw::c_void,
+    panic::{catch_unwind, AssertUnwindSafe},
+};
+
+use crate::{
+    class,
+    error::{protect, raise, Error},
+    exception,
+    method::ReturnValue,
+    object::Object,
+    r_typed_data::BorrowScope,
+    ruby_sys::{
+        rb_thread_create, rb_thread_current, rb_thread_kill, rb_thread_local_aref,
+        rb_thread_local_aset, rb_thread_main, rb_thread_run, rb_thread_wakeup, VALUE,
+    },
+    try_convert::TryConvert,
+    value::{Id, NonZeroValue, Value},
+};
+
+/// Wrapper type for a Value known to be an instance of Ruby's Thread class.
+///
+/// All [`Value`] methods should be available on this type through [`Deref`],
+/// but some may be missed by this documentation.
+#[derive(Clone, Copy)]
+#[repr(transparent)]
+pub struct RThread(NonZeroValue);
+
+impl RThread {
+    /// Return `Some(RThread)` if `val` is a `Thread`, `None` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RThread};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// assert!(RThread::from_value(eval("Thread.current").unwrap()).is_some());
+    /// assert!(RThread::from_value(eval("nil").unwrap()).is_none());
+    /// ```
+    #[inline]
+    pub fn from_value(val: Value) -> Option<Self> {
+        unsafe {
+            val.is_kind_of(class::thread())
+                .then(|| Self(NonZeroValue::new_unchecked(val)))
+        }
+    }
+
+    #[inline]
+    pub(crate) unsafe fn from_rb_value_unchecked(val: VALUE) -> Self {
+        Self(NonZeroValue::new_unchecked(Value::new(val)))
+    }
+
+    /// Create a new Ruby thread running `func`.
+    ///
+    /// The thread holds the Global VM Lock while running, as with any other
+    /// Ruby thread, so may call Ruby. The return value of `func` becomes the
+    /// thread's value, and if `func` returns an `Err` or panics the error is
+    /// raised in the thread, to be re-raised by [`join`](RThread::join) or
+    /// [`value`](RThread::value).
+    ///
+    /// As [`Value`] is not [`Send`], Ruby objects can not be captured by
+    /// `func`. Thread local variables (see [`local_aset`](RThread::local_aset))
+    /// can be used to pass data to the thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::RThread;
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let t = RThread::spawn(|| (1..=10).sum::<i64>()).unwrap();
+    /// assert_eq!(t.value::<i64>().unwrap(), 55);
+    /// ```
+    pub fn spawn<F, R>(func: F) -> Result<Self, Error>
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: ReturnValue,
+    {
+        unsafe extern "C" fn call<F, R>(arg: *mut c_void) -> VALUE
+        where
+            F: FnOnce() -> R,
+            R: ReturnValue,
+        {
+            let func = Box::from_raw(arg as *mut F);
+            let res = match catch_unwind(AssertUnwindSafe(|| {
+                // thread locals are per native thread, so there's no outer
+                // scope on a new Ruby thread
+                let _scope = BorrowScope::new();
+                func().into_return_value()
+            })) {
+                Ok(v) => v,
+                Err(e) => Err(Error::from_panic(e)),
+            };
+            match res {
+                Ok(v) => v.as_rb_value(),
+                Err(e) => raise(e),
+            }
+        }
+
+        let arg = Box::into_raw(Box::new(func));
+        unsafe {
+            let fptr = call::<F, R> as unsafe extern "C" fn(*mut c_void) -> VALUE;
+            #[cfg(ruby_lt_2_7)]
+            let fptr: unsafe extern "C" fn() -> VALUE = std::mem::transmute(fptr);
+            match protect(|| Value::new(rb_thread_create(Some(fptr), arg as *mut c_void))) {
+                Ok(v) => Ok(Self::from_rb_value_unchecked(v.as_rb_value())),
+                Err(e) => {
+                    // thread was not started, so func won't be called
+                    drop(Box::from_raw(arg));
+                    Err(e)
+                }
+            }
+        }
+    }
+
+    /// Return the currently running thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::RThread;
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// assert!(RThread::current().equal(RThread::main()).unwrap());
+    /// ```
+    pub fn current() -> Self {
+        unsafe { Self::from_rb_value_unchecked(rb_thread_current()) }
+    }
+
+    /// Return the main thread.
+    pub fn main() -> Self {
+        unsafe { Self::from_rb_value_unchecked(rb_thread_main()) }
+    }
+
+    /// Wait for `self` to finish.
+    ///
+    /// Returns `Err` if the thread raised an exception.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{exception, Error, RThread, Value};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let t = RThread::spawn(|| -> Result<(), Error> {
+    ///     Err(Error::new(exception::arg_error(), "bad input"))
+    /// })
+    /// .unwrap();
+    /// # t.funcall::<_, _, Value>("report_on_exception=", (false,)).unwrap();
+    /// assert!(t.join().unwrap_err().is_kind_of(exception::arg_error()));
+    /// ```
+    pub fn join(self) -> Result<(), Error> {
+        self.funcall::<_, _, Value>("join", ()).map(|_| ())
+    }
+
+    /// Wait for `self` to finish and return its value.
+    ///
+    /// Returns `Err` if the thread raised an exception, or the value fails to
+    /// convert to `T`.
+    pub fn value<T>(self) -> Result<T, Error>
+    where
+        T: TryConvert,
+    {
+        self.funcall("value", ())
+    }
+
+    /// Returns whether `self` is running or sleeping.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::RThread;
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// assert!(RThread::current().is_alive());
+    /// let t = RThread::spawn(|| ()).unwrap();
+    /// t.join().unwrap();
+    /// assert!(!t.is_alive());
+    /// ```
+    pub fn is_alive(self) -> bool {
+        self.funcall("alive?", ()).unwrap_or(false)
+    }
+
+    /// Mark `self` as eligible for scheduling, if it is sleeping.
+    ///
+    /// Errors if `self` is dead.
+    pub fn wakeup(self) -> Result<(), Error> {
+        unsafe { protect(|| Value::new(rb_thread_wakeup(self.as_rb_value()))).map(|_| ()) }
+    }
+
+    /// Wake up `self` and immediately schedule it to run.
+    ///
+    /// Errors if `self` is dead.
+    pub fn run(self) -> Result<(), Error> {
+        unsafe { protect(|| Value::new(rb_thread_run(self.as_rb_value()))).map(|_| ()) }
+    }
+
+    /// Terminate `self`.
+    ///
+    /// If `self` is the current thread this will return an `Err` that must be
+    /// propagated back to Ruby for the thread to exit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::RThread;
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let t = RThread::spawn(|| magnus::eval::<magnus::Value>("sleep")).unwrap();
+    /// t.kill().unwrap();
+    /// t.join().unwrap();
+    /// assert!(!t.is_alive());
+    /// ```
+    pub fn kill(self) -> Result<(), Error> {
+        unsafe { protect(|| Value::new(rb_thread_kill(self.as_rb_value()))).map(|_| ()) }
+    }
+
+    /// Get the value of the fiber-local variable `key` of `self`.
+    ///
+    /// This is equivalent to `thread[key]` in Ruby. Returns `nil`, or the
+    /// result of converting `nil` to `T`, if the variable is not set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::RThread;
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let t = RThread::current();
+    /// t.local_aset("answer", 42).unwrap();
+    /// assert_eq!(t.local_aref::<_, i64>("answer").unwrap(), 42);
+    /// assert_eq!(t.local_aref::<_, Option<i64>>("unset").unwrap(), None);
+    /// ```
+    pub fn local_aref<K, T>(self, key: K) -> Result<T, Error>
+    where
+        K: Into<Id>,
+        T: TryConvert,
+    {
+        let id = key.into();
+        unsafe { Value::new(rb_thread_local_aref(self.as_rb_value(), id.as_rb_id())) }.try_convert()
+    }
+
+    /// Set the value of the fiber-local variable `key` of `self` to `val`.
+    ///
+    /// This is equivalent to `thread[key] = val` in Ruby.
+    ///
+    /// Errors if `self` is frozen.
+    pub fn local_aset<K, T>(self, key: K, val: T) -> Result<(), Error>
+    where
+        K: Into<Id>,
+        T: Into<Value>,
+    {
+        let id = key.into();
+        let val = val.into();
+        unsafe {
+            protect(|| {
+                Value::new(rb_thread_local_aset(
+                    self.as_rb_value(),
+                    id.as_rb_id(),
+                    val.as_rb_value(),
+                ))
+            })
+        }
+        .map(|_| ())
+    }
+}
+
+impl Deref for RThread {
+    type Target = Value;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.get_ref()
+    }
+}
+
+impl fmt::Display for RThread {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", unsafe { self.to_s_infallible() })
+    }
+}
+
+impl fmt::Debug for RThread {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.inspect())
+    }
+}
+
+impl From<RThread> for Value {
+    fn from(val: RThread) -> Self {
+        *val
+    }
+}
+
+impl Object for RThread {}
+
+impl TryConvert for RThread {
+    #[inline]
+    fn try_convert(val: &Value) -> Result<Self, Error> {
+        Self::from_value(*val).ok_or_else(|| {
+            Error::new(
+                exception::type_error(),
+                format!("no implicit conversion of {} into Thread", unsafe {
+                    val.classname()
+                },),
+            )
+        })
+    }
+}
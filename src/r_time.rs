@@ -0,0 +1,418 @@
+use std::{
+    fmt,
+    ops::Deref,
+    os::raw::{c_int, c_long},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, FixedOffset, Local, Offset, TimeZone, Utc};
+#[cfg(feature = "time")]
+use std::convert::TryFrom;
+#[cfg(feature = "time")]
+use time::{OffsetDateTime, UtcOffset};
+
+use crate::{
+    class,
+    error::{protect, Error},
+    exception,
+    integer::Integer,
+    object::Object,
+    r_rational::RRational,
+    ruby_sys::{
+        rb_time_nano_new, rb_time_timespec, rb_time_timespec_new, rb_time_utc_offset, timespec,
+        VALUE,
+    },
+    try_convert::{TryConvert, TryConvertOwned},
+    value::{NonZeroValue, Value, QNIL},
+};
+
+// special values for the offset argument of rb_time_timespec_new
+const OFFSET_UTC: c_int = c_int::MAX - 1;
+
+const NANOS_PER_SEC: u32 = 1_000_000_000;
+
+/// Wrapper type for a Value known to be an instance of Ruby's Time class.
+///
+/// All [`Value`] methods should be available on this type through [`Deref`],
+/// but some may be missed by this documentation.
+#[derive(Clone, Copy)]
+#[repr(transparent)]
+pub struct RTime(NonZeroValue);
+
+impl RTime {
+    /// Return `Some(RTime)` if `val` is a `Time`, `None` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RTime};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// assert!(RTime::from_value(eval("Time.now").unwrap()).is_some());
+    /// assert!(RTime::from_value(eval("0").unwrap()).is_none());
+    /// ```
+    #[inline]
+    pub fn from_value(val: Value) -> Option<Self> {
+        unsafe {
+            val.is_kind_of(class::time())
+                .then(|| Self(NonZeroValue::new_unchecked(val)))
+        }
+    }
+
+    #[inline]
+    pub(crate) unsafe fn from_rb_value_unchecked(val: VALUE) -> Self {
+        Self(NonZeroValue::new_unchecked(Value::new(val)))
+    }
+
+    /// Create a new `Time` in the local timezone from `secs` seconds and
+    /// `nsecs` nanoseconds since the Unix epoch.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RTime};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let t = RTime::new(1_000_000_000, 5);
+    /// let res: bool = eval!("t == Time.at(1_000_000_000, 5, :nsec)", t).unwrap();
+    /// assert!(res);
+    /// ```
+    pub fn new(secs: i64, nsecs: u32) -> Self {
+        unsafe { Self::from_rb_value_unchecked(rb_time_nano_new(secs as _, nsecs as c_long)) }
+    }
+
+    /// Create a new `Time` in UTC from `secs` seconds and `nsecs` nanoseconds
+    /// since the Unix epoch.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::RTime;
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let t = RTime::new_utc(0, 0);
+    /// assert!(t.is_utc());
+    /// assert_eq!(t.to_string(), "1970-01-01 00:00:00 UTC");
+    /// ```
+    pub fn new_utc(secs: i64, nsecs: u32) -> Self {
+        unsafe { Self::from_rb_value_unchecked(rb_time_timespec_new(&ts(secs, nsecs), OFFSET_UTC)) }
+    }
+
+    /// Create a new `Time` with a fixed offset of `offset` seconds from UTC,
+    /// from `secs` seconds and `nsecs` nanoseconds since the Unix epoch.
+    ///
+    /// Returns `Err` if `offset` is not less than a day either side of UTC.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::RTime;
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let t = RTime::new_with_offset(0, 0, 3600).unwrap();
+    /// assert_eq!(t.utc_offset().unwrap(), 3600);
+    /// assert_eq!(t.to_string(), "1970-01-01 01:00:00 +0100");
+    /// assert!(RTime::new_with_offset(0, 0, 86400).is_err());
+    /// ```
+    pub fn new_with_offset(secs: i64, nsecs: u32, offset: i32) -> Result<Self, Error> {
+        if offset <= -86400 || offset >= 86400 {
+            return Err(Error::new(
+                exception::arg_error(),
+                "utc_offset out of range",
+            ));
+        }
+        unsafe {
+            Ok(Self::from_rb_value_unchecked(rb_time_timespec_new(
+                &ts(secs, nsecs),
+                offset as c_int,
+            )))
+        }
+    }
+
+    /// Return the offset of `self` from UTC, in seconds.
+    ///
+    /// Returns `Err` if `self` is uninitialised, e.g. from `Time.allocate`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RTime};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let t: RTime = eval(r#"Time.new(2000, 1, 1, 0, 0, 0, "-05:00")"#).unwrap();
+    /// assert_eq!(t.utc_offset().unwrap(), -18000);
+    /// assert!(eval::<RTime>("Time.allocate").unwrap().utc_offset().is_err());
+    /// ```
+    pub fn utc_offset(self) -> Result<i32, Error> {
+        unsafe {
+            protect(|| Value::new(rb_time_utc_offset(self.as_rb_value())))
+                .and_then(|v| v.try_convert())
+        }
+    }
+
+    /// Return whether `self` is in UTC.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RTime};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// assert!(eval::<RTime>("Time.now.utc").unwrap().is_utc());
+    /// assert!(!eval::<RTime>(r#"Time.new(2000, 1, 1, 0, 0, 0, "+00:00")"#).unwrap().is_utc());
+    /// ```
+    pub fn is_utc(self) -> bool {
+        self.funcall("utc?", ()).unwrap_or(false)
+    }
+
+    /// Return `self` as seconds and nanoseconds since the Unix epoch.
+    ///
+    /// Returns `Err` if `self` is out of range of the platform's `time_t`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RTime};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let t: RTime = eval("Time.at(-1, 250, :millisecond)").unwrap();
+    /// assert_eq!(t.to_timespec().unwrap(), (-1, 250_000_000));
+    /// ```
+    // time_t isn't i64 on all platforms
+    #[allow(clippy::useless_conversion)]
+    pub fn to_timespec(self) -> Result<(i64, u32), Error> {
+        let mut res = ts(0, 0);
+        unsafe {
+            protect(|| {
+                res = rb_time_timespec(self.as_rb_value());
+                *QNIL
+            })?;
+        }
+        Ok((i64::from(res.tv_sec), res.tv_nsec as u32))
+    }
+}
+
+fn ts(secs: i64, nsecs: u32) -> timespec {
+    timespec {
+        tv_sec: secs as _,
+        tv_nsec: nsecs as _,
+    }
+}
+
+fn out_of_range() -> Error {
+    Error::new(exception::range_error(), "time out of range")
+}
+
+impl Deref for RTime {
+    type Target = Value;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.get_ref()
+    }
+}
+
+impl fmt::Display for RTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", unsafe { self.to_s_infallible() })
+    }
+}
+
+impl fmt::Debug for RTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.inspect())
+    }
+}
+
+impl From<RTime> for Value {
+    fn from(val: RTime) -> Self {
+        *val
+    }
+}
+
+impl Object for RTime {}
+
+impl TryConvert for RTime {
+    #[inline]
+    fn try_convert(val: &Value) -> Result<Self, Error> {
+        Self::from_value(*val).ok_or_else(|| {
+            Error::new(
+                exception::type_error(),
+                format!("no implicit conversion of {} into Time", unsafe {
+                    val.classname()
+                },),
+            )
+        })
+    }
+}
+
+impl From<SystemTime> for Value {
+    fn from(val: SystemTime) -> Self {
+        let (secs, nsecs) = match val.duration_since(UNIX_EPOCH) {
+            Ok(d) => (d.as_secs() as i64, d.subsec_nanos()),
+            Err(e) => {
+                let d = e.duration();
+                match d.subsec_nanos() {
+                    0 => (-(d.as_secs() as i64), 0),
+                    n => (-(d.as_secs() as i64) - 1, NANOS_PER_SEC - n),
+                }
+            }
+        };
+        RTime::new(secs, nsecs).into()
+    }
+}
+
+impl TryConvert for SystemTime {
+    fn try_convert(val: &Value) -> Result<Self, Error> {
+        let (secs, nsecs) = RTime::try_convert(val)?.to_timespec()?;
+        let res = if secs >= 0 {
+            UNIX_EPOCH.checked_add(Duration::new(secs as u64, nsecs))
+        } else {
+            UNIX_EPOCH
+                .checked_sub(Duration::from_secs(secs.unsigned_abs()))
+                .and_then(|t| t.checked_add(Duration::from_nanos(nsecs as u64)))
+        };
+        res.ok_or_else(out_of_range)
+    }
+}
+impl TryConvertOwned for SystemTime {}
+
+/// Converts to a number of seconds, an `Integer` if `val` is a whole number of
+/// seconds, otherwise a `Rational`.
+impl From<Duration> for Value {
+    fn from(val: Duration) -> Self {
+        if val.subsec_nanos() == 0 {
+            return val.as_secs().into();
+        }
+        RRational::new(
+            Integer::from_u128(val.as_nanos()),
+            Integer::from_u64(NANOS_PER_SEC as u64),
+        )
+        .unwrap_or_else(|e| panic!("{}", e))
+        .into()
+    }
+}
+
+/// Converts from any non-negative `Numeric` number of seconds.
+impl TryConvert for Duration {
+    fn try_convert(val: &Value) -> Result<Self, Error> {
+        if !val.is_kind_of(class::numeric()) {
+            return Err(Error::new(
+                exception::type_error(),
+                format!("can't convert {} into time interval", unsafe {
+                    val.classname()
+                }),
+            ));
+        }
+        let (secs, frac): (Value, Value) = val.funcall("divmod", (1,))?;
+        let secs = Integer::try_convert(&secs)?;
+        if secs < 0 {
+            return Err(Error::new(
+                exception::arg_error(),
+                "time interval must not be negative",
+            ));
+        }
+        let nanos: u64 = frac
+            .funcall::<_, _, Value>("*", (NANOS_PER_SEC,))?
+            .funcall("round", ())?;
+        let secs = secs.to_u64()?;
+        // rounding may give a whole second, which Duration::new carries
+        secs.checked_add(nanos / NANOS_PER_SEC as u64)
+            .map(|s| Duration::new(s, (nanos % NANOS_PER_SEC as u64) as u32))
+            .ok_or_else(|| Error::new(exception::range_error(), "time interval out of range"))
+    }
+}
+impl TryConvertOwned for Duration {}
+
+#[cfg(feature = "chrono")]
+impl<Tz> From<DateTime<Tz>> for Value
+where
+    Tz: TimeZone,
+{
+    fn from(val: DateTime<Tz>) -> Self {
+        let offset = val.offset().fix().local_minus_utc();
+        RTime::new_with_offset(val.timestamp(), val.timestamp_subsec_nanos(), offset)
+            .unwrap_or_else(|e| panic!("{}", e))
+            .into()
+    }
+}
+
+#[cfg(feature = "chrono")]
+fn try_convert_date_time<Tz, F>(val: &Value, tz: F) -> Result<DateTime<Tz>, Error>
+where
+    Tz: TimeZone,
+    F: FnOnce(RTime) -> Result<Tz, Error>,
+{
+    let time = RTime::try_convert(val)?;
+    let (secs, nsecs) = time.to_timespec()?;
+    tz(time)?
+        .timestamp_opt(secs, nsecs)
+        .single()
+        .ok_or_else(out_of_range)
+}
+
+#[cfg(feature = "chrono")]
+impl TryConvert for DateTime<Utc> {
+    fn try_convert(val: &Value) -> Result<Self, Error> {
+        try_convert_date_time(val, |_| Ok(Utc))
+    }
+}
+#[cfg(feature = "chrono")]
+impl TryConvertOwned for DateTime<Utc> {}
+
+/// Keeps the UTC offset of the `Time`.
+#[cfg(feature = "chrono")]
+impl TryConvert for DateTime<FixedOffset> {
+    fn try_convert(val: &Value) -> Result<Self, Error> {
+        try_convert_date_time(val, |time| {
+            FixedOffset::east_opt(time.utc_offset()?).ok_or_else(out_of_range)
+        })
+    }
+}
+#[cfg(feature = "chrono")]
+impl TryConvertOwned for DateTime<FixedOffset> {}
+
+#[cfg(feature = "chrono")]
+impl TryConvert for DateTime<Local> {
+    fn try_convert(val: &Value) -> Result<Self, Error> {
+        try_convert_date_time(val, |_| Ok(Local))
+    }
+}
+#[cfg(feature = "chrono")]
+impl TryConvertOwned for DateTime<Local> {}
+
+#[cfg(feature = "time")]
+impl TryFrom<OffsetDateTime> for RTime {
+    type Error = Error;
+
+    /// Create a new `RTime` from an `OffsetDateTime`, keeping its offset.
+    ///
+    /// Returns `Err` if the offset is not less than a day either side of UTC,
+    /// which `OffsetDateTime` allows but Ruby does not.
+    fn try_from(val: OffsetDateTime) -> Result<Self, Self::Error> {
+        RTime::new_with_offset(
+            val.unix_timestamp(),
+            val.nanosecond(),
+            val.offset().whole_seconds(),
+        )
+    }
+}
+
+/// Keeps the UTC offset of the `Time`.
+#[cfg(feature = "time")]
+impl TryConvert for OffsetDateTime {
+    fn try_convert(val: &Value) -> Result<Self, Error> {
+        let time = RTime::try_convert(val)?;
+        let (secs, nsecs) = time.to_timespec()?;
+        let offset =
+            UtcOffset::from_whole_seconds(time.utc_offset()?).map_err(|_| out_of_range())?;
+        OffsetDateTime::from_unix_timestamp_nanos(
+            secs as i128 * NANOS_PER_SEC as i128 + nsecs as i128,
+        )
+        .ok()
+        .and_then(|t| t.checked_to_offset(offset))
+        .ok_or_else(out_of_range)
+    }
+}
+#[cfg(feature = "time")]
+impl TryConvertOwned for OffsetDateTime {}
@@ -4,12 +4,14 @@
 //! Ruby's C API.
 
 use std::{
+    cell::{Cell, RefCell},
     ffi::{c_void, CString},
     fmt,
     marker::PhantomData,
     mem::size_of_val,
-    ops::Deref,
+    ops::{Deref, DerefMut},
     ptr::{self, NonNull},
+    sync::atomic::{AtomicIsize, Ordering},
 };
 
 use crate::{
@@ -59,6 +61,89 @@ impl RTypedData {
                 })
         }
     }
+
+    /// Get a shared reference to the Rust value of type `T` wrapped by
+    /// `self`.
+    ///
+    /// Borrows are tracked at runtime, returns `Err` if `self` does not wrap
+    /// a `T`, or the wrapped value is currently mutably borrowed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{define_class, RTypedData, Value};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// #[magnus::wrap(class = "Point")]
+    /// struct Point {
+    ///     x: isize,
+    ///     y: isize,
+    /// }
+    ///
+    /// define_class("Point", Default::default()).unwrap();
+    /// let val = RTypedData::from_value(Value::from(Point { x: 1, y: 2 })).unwrap();
+    ///
+    /// let a = val.get::<Point>().unwrap();
+    /// let b = val.get::<Point>().unwrap();
+    /// assert_eq!(a.x, 1);
+    /// assert_eq!(b.y, 2);
+    /// assert!(val.get_mut::<Point>().is_err());
+    /// ```
+    pub fn get<T>(&self) -> Result<Ref<'_, T>, Error>
+    where
+        T: TypedData,
+    {
+        let ptr = typed_data_ptr::<T>(self)?.as_ptr();
+        let borrows = unsafe { &(*ptr).borrows };
+        acquire(borrows, Borrow::Shared).map_err(|msg| borrow_error(self, msg))?;
+        Ok(Ref {
+            borrows,
+            data: unsafe { &(*ptr).data },
+        })
+    }
+
+    /// Get a mutable reference to the Rust value of type `T` wrapped by
+    /// `self`.
+    ///
+    /// Borrows are tracked at runtime, returns `Err` if `self` does not wrap
+    /// a `T`, or the wrapped value is currently borrowed. A `&T` obtained with
+    /// [`TryConvert`] outside of a method call or block is not tied to any
+    /// scope, so is not tracked, use [`get`](RTypedData::get) instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{define_class, RTypedData, Value};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// #[magnus::wrap(class = "Point")]
+    /// struct Point {
+    ///     x: isize,
+    ///     y: isize,
+    /// }
+    ///
+    /// define_class("Point", Default::default()).unwrap();
+    /// let val = RTypedData::from_value(Value::from(Point { x: 1, y: 2 })).unwrap();
+    ///
+    /// let mut point = val.get_mut::<Point>().unwrap();
+    /// point.x = 3;
+    /// assert!(val.get::<Point>().is_err());
+    /// drop(point);
+    ///
+    /// assert_eq!(val.get::<Point>().unwrap().x, 3);
+    /// ```
+    pub fn get_mut<T>(&self) -> Result<RefMut<'_, T>, Error>
+    where
+        T: TypedData,
+    {
+        let ptr = typed_data_ptr::<T>(self)?.as_ptr();
+        let borrows = unsafe { &(*ptr).borrows };
+        acquire(borrows, Borrow::Mut).map_err(|msg| borrow_error(self, msg))?;
+        Ok(RefMut {
+            borrows,
+            data: unsafe { &mut (*ptr).data },
+        })
+    }
 }
 
 impl Deref for RTypedData {
@@ -89,6 +174,232 @@ impl From<RTypedData> for Value {
 
 impl Object for RTypedData {}
 
+impl TryConvert for RTypedData {
+    #[inline]
+    fn try_convert(val: &Value) -> Result<Self, Error> {
+        match Self::from_value(*val) {
+            Some(v) => Ok(v),
+            None => Err(Error::new(
+                exception::type_error(),
+                format!("no implicit conversion of {} into Data", unsafe {
+                    val.classname()
+                },),
+            )),
+        }
+    }
+}
+
+/// A shared borrow of a Rust value wrapped in a Ruby object.
+///
+/// See [`RTypedData::get`].
+pub struct Ref<'a, T> {
+    borrows: &'a AtomicIsize,
+    data: &'a T,
+}
+
+impl<'a, T> Deref for Ref<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.data
+    }
+}
+
+impl<'a, T> Drop for Ref<'a, T> {
+    fn drop(&mut self) {
+        release(self.borrows, Borrow::Shared);
+    }
+}
+
+/// A mutable borrow of a Rust value wrapped in a Ruby object.
+///
+/// See [`RTypedData::get_mut`].
+pub struct RefMut<'a, T> {
+    borrows: &'a AtomicIsize,
+    data: &'a mut T,
+}
+
+impl<'a, T> Deref for RefMut<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.data
+    }
+}
+
+impl<'a, T> DerefMut for RefMut<'a, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.data
+    }
+}
+
+impl<'a, T> Drop for RefMut<'a, T> {
+    fn drop(&mut self) {
+        release(self.borrows, Borrow::Mut);
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Borrow {
+    Shared,
+    Mut,
+}
+
+// Rust data wrapped in a Ruby object, along with its borrow state. Positive
+// for the number of shared borrows, -1 when mutably borrowed.
+//
+// The borrow state is kept with the data, rather than in a thread local, as a
+// Ruby thread can be switched out while data is borrowed.
+struct Wrapped<T> {
+    borrows: AtomicIsize,
+    data: T,
+}
+
+// Wrap `data` in a new Ruby object of class `class` (0 for a hidden object),
+// described by `data_type`.
+pub(crate) unsafe fn wrap<T>(class: VALUE, data: T, data_type: &'static DataType) -> VALUE {
+    let wrapped = Box::new(Wrapped {
+        borrows: AtomicIsize::new(0),
+        data,
+    });
+    rb_data_typed_object_wrap(
+        class,
+        Box::into_raw(wrapped) as *mut _,
+        data_type as *const _,
+    )
+}
+
+// Get a pointer to the data wrapped by `val`, without any checks.
+//
+// `val` must have been returned from `wrap::<T>`. The pointer is only valid
+// while `val` is alive.
+pub(crate) unsafe fn get_unchecked<T>(val: Value) -> *mut T {
+    let typed_data = val.as_rb_value() as *mut ruby_sys::RTypedData;
+    &mut (*((*typed_data).data as *mut Wrapped<T>)).data
+}
+
+fn acquire(borrows: &AtomicIsize, borrow: Borrow) -> Result<(), &'static str> {
+    borrows
+        .fetch_update(Ordering::Acquire, Ordering::Relaxed, |count| match borrow {
+            Borrow::Shared if count >= 0 => Some(count + 1),
+            Borrow::Mut if count == 0 => Some(-1),
+            _ => None,
+        })
+        .map(|_| ())
+        .map_err(|count| {
+            if count < 0 {
+                "already mutably borrowed"
+            } else {
+                "already borrowed"
+            }
+        })
+}
+
+fn release(borrows: &AtomicIsize, borrow: Borrow) {
+    match borrow {
+        Borrow::Shared => borrows.fetch_sub(1, Ordering::Release),
+        Borrow::Mut => borrows.swap(0, Ordering::Release),
+    };
+}
+
+// A scope's id, and the borrows it holds. The borrow states are valid for the
+// life of the scope, as the objects are referenced from the stack.
+type Scope = (usize, Vec<(*const AtomicIsize, Borrow)>);
+
+thread_local! {
+    static BORROW_SCOPES: RefCell<Vec<Scope>> = Default::default();
+    static NEXT_SCOPE_ID: Cell<usize> = Default::default();
+}
+
+/// Holds borrows of wrapped data taken while converting the arguments of a
+/// method call or block, releasing them when dropped.
+///
+/// References converted with [`TryConvert`] have no guard to release the
+/// borrow, so without a scope shared borrows are checked but not tracked, and
+/// mutable borrows are refused.
+pub(crate) struct BorrowScope(usize);
+
+impl BorrowScope {
+    pub(crate) fn new() -> Self {
+        let id = NEXT_SCOPE_ID.with(|next| {
+            let id = next.get();
+            next.set(id.wrapping_add(1));
+            id
+        });
+        BORROW_SCOPES.with(|scopes| scopes.borrow_mut().push((id, Vec::new())));
+        Self(id)
+    }
+}
+
+impl Drop for BorrowScope {
+    fn drop(&mut self) {
+        // with Fibers scopes may not be dropped in the order they were
+        // created, so search for ours rather than popping the last.
+        let held = BORROW_SCOPES.with(|scopes| {
+            let mut scopes = scopes.borrow_mut();
+            scopes
+                .iter()
+                .rposition(|(id, _)| *id == self.0)
+                .map(|i| scopes.remove(i).1)
+        });
+        for (borrows, borrow) in held.into_iter().flatten() {
+            release(unsafe { &*borrows }, borrow);
+        }
+    }
+}
+
+// Borrow for the lifetime of the current scope. Outside of a scope the
+// reference's lifetime is unbounded, so there would be nothing to release a
+// borrow. Shared borrows are only checked, and mutable borrows refused.
+fn scoped_borrow(borrows: &AtomicIsize, borrow: Borrow) -> Result<(), &'static str> {
+    BORROW_SCOPES.with(|scopes| match scopes.borrow_mut().last_mut() {
+        Some((_, held)) => {
+            acquire(borrows, borrow)?;
+            held.push((borrows, borrow));
+            Ok(())
+        }
+        None => match borrow {
+            Borrow::Shared if borrows.load(Ordering::Acquire) >= 0 => Ok(()),
+            Borrow::Shared => Err("already mutably borrowed"),
+            Borrow::Mut => Err("can only be mutably borrowed in a method or block"),
+        },
+    })
+}
+
+fn borrow_error(val: &Value, msg: &str) -> Error {
+    Error::new(
+        exception::runtime_error(),
+        format!("{} {}", unsafe { val.classname() }, msg),
+    )
+}
+
+fn typed_data_ptr<T>(val: &Value) -> Result<NonNull<Wrapped<T>>, Error>
+where
+    T: TypedData,
+{
+    debug_assert_value!(val);
+    unsafe {
+        let mut res = None;
+        let _ = protect(|| {
+            res = NonNull::new(
+                rb_check_typeddata(val.as_rb_value(), T::data_type() as *const _)
+                    as *mut Wrapped<T>,
+            );
+            *QNIL
+        });
+        res.ok_or_else(|| {
+            Error::new(
+                exception::type_error(),
+                format!(
+                    "no implicit conversion of {} into {}",
+                    val.classname(),
+                    T::class()
+                ),
+            )
+        })
+    }
+}
+
 /// A C struct containing metadata on a Rust type, for use with the
 /// `rb_data_typed_object_wrap` API.
 pub type DataType = rb_data_type_t;
@@ -156,41 +467,45 @@ where
     ///
     /// # Safety
     ///
-    /// `ptr` must be a vaild pointer to a `Box<Self>`, and must not be aliased
-    /// This function will free the memory pointed to by `ptr`.
+    /// `ptr` must be a vaild pointer to a boxed `Self` as wrapped by Magnus,
+    /// and must not be aliased. This function will free the memory pointed to
+    /// by `ptr`.
     #[doc(hidden)]
     unsafe extern "C" fn extern_free(ptr: *mut c_void) {
-        Self::free(Box::from_raw(ptr as *mut _))
+        let wrapped = Box::from_raw(ptr as *mut Wrapped<Self>);
+        Self::free(Box::new(wrapped.data))
     }
 
     /// Extern wrapper for `mark`. Don't define or call.
     ///
     /// # Safety
     ///
-    /// `ptr` must be a vaild pointer to a `Self`, and must not be aliased.
+    /// `ptr` must be a vaild pointer to a `Self` as wrapped by Magnus, and
+    /// must not be aliased.
     #[doc(hidden)]
     unsafe extern "C" fn extern_mark(ptr: *mut c_void) {
-        Self::mark(&mut *(ptr as *mut Self));
+        Self::mark(&mut (*(ptr as *mut Wrapped<Self>)).data);
     }
 
     /// Extern wrapper for `size`. Don't define or call.
     ///
     /// # Safety
     ///
-    /// `ptr` must be a vaild pointer to a `Self`.
+    /// `ptr` must be a vaild pointer to a `Self` as wrapped by Magnus.
     #[doc(hidden)]
     unsafe extern "C" fn extern_size(ptr: *const c_void) -> size_t {
-        Self::size(&*(ptr as *const Self)) as size_t
+        Self::size(&(*(ptr as *const Wrapped<Self>)).data) as size_t
     }
 
     /// Extern wrapper for `compact`. Don't define or call.
     ///
     /// # Safety
     ///
-    /// `ptr` must be a vaild pointer to a `Self`, and must not be aliased.
+    /// `ptr` must be a vaild pointer to a `Self` as wrapped by Magnus, and
+    /// must not be aliased.
     #[doc(hidden)]
     unsafe extern "C" fn extern_compact(ptr: *mut c_void) {
-        Self::compact(&mut *(ptr as *mut Self));
+        Self::compact(&mut (*(ptr as *mut Wrapped<Self>)).data);
     }
 }
 
@@ -360,26 +675,23 @@ where
 {
     #[inline]
     fn try_convert(val: &Value) -> Result<Self, Error> {
-        debug_assert_value!(val);
-        unsafe {
-            let mut res = None;
-            let _ = protect(|| {
-                res = (rb_check_typeddata(val.as_rb_value(), T::data_type() as *const _)
-                    as *const T)
-                    .as_ref();
-                *QNIL
-            });
-            res.ok_or_else(|| {
-                Error::new(
-                    exception::type_error(),
-                    format!(
-                        "no implicit conversion of {} into {}",
-                        val.classname(),
-                        T::class()
-                    ),
-                )
-            })
-        }
+        let ptr = typed_data_ptr::<T>(val)?.as_ptr();
+        scoped_borrow(unsafe { &(*ptr).borrows }, Borrow::Shared)
+            .map_err(|msg| borrow_error(val, msg))?;
+        unsafe { Ok(&(*ptr).data) }
+    }
+}
+
+impl<T> TryConvert for &mut T
+where
+    T: TypedData,
+{
+    #[inline]
+    fn try_convert(val: &Value) -> Result<Self, Error> {
+        let ptr = typed_data_ptr::<T>(val)?.as_ptr();
+        scoped_borrow(unsafe { &(*ptr).borrows }, Borrow::Mut)
+            .map_err(|msg| borrow_error(val, msg))?;
+        unsafe { Ok(&mut (*ptr).data) }
     }
 }
 
@@ -388,14 +700,6 @@ where
     T: TypedData,
 {
     fn from(data: T) -> Self {
-        let boxed = Box::new(data);
-        let value_ptr = unsafe {
-            rb_data_typed_object_wrap(
-                T::class().as_rb_value(),
-                Box::into_raw(boxed) as *mut _,
-                T::data_type() as *const _,
-            )
-        };
-        Value::new(value_ptr)
+        unsafe { Value::new(wrap(T::class().as_rb_value(), data, T::data_type())) }
     }
 }
@@ -9,3 +9,26 @@
 
 // see build.rs
 include!(concat!(env!("OUT_DIR"), "/ruby_sys.rs"));
+
+// ruby/thread.h isn't included in the generated bindings, so the functions we
+// need from it are declared by hand.
+extern "C" {
+    pub fn rb_thread_call_without_gvl(
+        func: Option<
+            unsafe extern "C" fn(*mut ::std::os::raw::c_void) -> *mut ::std::os::raw::c_void,
+        >,
+        data1: *mut ::std::os::raw::c_void,
+        ubf: Option<unsafe extern "C" fn(*mut ::std::os::raw::c_void)>,
+        data2: *mut ::std::os::raw::c_void,
+    ) -> *mut ::std::os::raw::c_void;
+}
+
+// ruby/re.h isn't included in the generated bindings either.
+extern "C" {
+    pub fn rb_reg_search(
+        re: VALUE,
+        str_: VALUE,
+        pos: ::std::os::raw::c_long,
+        reverse: ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_long;
+}
@@ -39,7 +39,8 @@ use crate::{
     value::{Id, Value, QNIL},
 };
 
-struct ArgSpec {
+#[derive(Clone, Copy)]
+pub(crate) struct ArgSpec {
     required: usize,
     optional: usize,
     splat: bool,
@@ -76,7 +77,7 @@ impl ArgSpec {
         }
     }
 
-    fn len(&self) -> usize {
+    pub(crate) fn len(&self) -> usize {
         self.required
             + self.optional
             + self.splat as usize
@@ -84,6 +85,48 @@ impl ArgSpec {
             + self.keywords as usize
             + self.block as usize
     }
+
+    // Parses a Ruby-style parameter list, e.g. `a, b = nil, *rest, c, **kw, &blk`.
+    pub(crate) fn parse(params: &str) -> Self {
+        let mut required = 0;
+        let mut optional = 0;
+        let mut splat = false;
+        let mut trailing = 0;
+        let mut keywords = false;
+        let mut block = false;
+        let unexpected = |param: &str| panic!("unexpected parameter `{}` in `{}`", param, params);
+        for param in params.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            if block {
+                unexpected(param);
+            } else if param.starts_with('&') {
+                block = true;
+            } else if keywords {
+                unexpected(param);
+            } else if param.starts_with("**") {
+                keywords = true;
+            } else if param.contains(':') {
+                panic!(
+                    "unsupported parameter `{}` in `{}`, use `**kwargs` for keywords",
+                    param, params
+                );
+            } else if param.starts_with('*') {
+                if splat || trailing > 0 {
+                    unexpected(param);
+                }
+                splat = true;
+            } else if param.contains('=') {
+                if splat || trailing > 0 {
+                    unexpected(param);
+                }
+                optional += 1;
+            } else if optional > 0 || splat {
+                trailing += 1;
+            } else {
+                required += 1;
+            }
+        }
+        Self::new(required, optional, splat, trailing, keywords, block)
+    }
 }
 
 impl fmt::Display for ArgSpec {
@@ -845,6 +888,17 @@ mod private {
         }
     }
 
+    impl<T> ScanArgsKw for T
+    where
+        T: FromKwArgs,
+    {
+        const REQ: bool = true;
+
+        fn from_opt(val: Option<Value>) -> Result<Self, Error> {
+            T::from_kwargs(<RHash as ScanArgsKw>::from_opt(val)?)
+        }
+    }
+
     pub trait ScanArgsBlock: Sized {
         const REQ: bool;
 
@@ -921,13 +975,53 @@ impl<T> ScanArgsSplat for T where T: private::ScanArgsSplat {}
 /// Trait implemented for types that can be retrieved as keyword arguments by
 /// [`scan_args`].
 ///
-/// This trait is implemented for [`RHash`].
+/// This trait is implemented for [`RHash`], and types implementing
+/// [`FromKwArgs`].
 ///
 /// `()` also impliments this trait as a placeholder indicating no keyword
 /// arguments are required.
 pub trait ScanArgsKw: private::ScanArgsKw {}
 impl<T> ScanArgsKw for T where T: private::ScanArgsKw {}
 
+/// Trait for types that can be extracted from a Hash of keyword arguments.
+///
+/// This is usually implemented with the [`KwArgs`](magnus_macros::KwArgs)
+/// derive macro. Types implementing this trait can be used as the keywords
+/// type with [`scan_args`].
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{class, error::Error, method, scan_args::scan_args, KwArgs, Module, RHash, Value};
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// #[derive(KwArgs)]
+/// struct Options {
+///     name: String,
+///     retries: Option<usize>,
+///     #[magnus(rest)]
+///     extra: RHash,
+/// }
+///
+/// fn example(rb_self: Value, args: &[Value]) -> Result<String, Error> {
+///     let args = scan_args::<(), (), (), (), Options, ()>(args)?;
+///     let Options { name, retries, extra } = args.keywords;
+///     Ok(format!("{} {} {}", name, retries.unwrap_or(3), extra.len()))
+/// }
+///
+/// class::object().define_method("example", method!(example, -1));
+/// # let res = magnus::eval::<bool>(r#"Object.new.example(name: "a") == "a 3 0""#).unwrap();
+/// # assert!(res);
+/// # let res = magnus::eval::<bool>(r#"Object.new.example(name: "a", retries: 1, b: 2) == "a 1 1""#).unwrap();
+/// # assert!(res);
+/// ```
+pub trait FromKwArgs: Sized {
+    /// Extract `Self` from the keyword arguments `kw`.
+    ///
+    /// `kw` is not modified.
+    fn from_kwargs(kw: RHash) -> Result<Self, Error>;
+}
+
 /// Trait implemented for types that can be retrieved as a block argument by
 /// [`scan_args`].
 ///
@@ -1089,6 +1183,26 @@ fn scan_args_untyped(args: &[Value], arg_spec: ArgSpec) -> Result<ScannedArgs, E
     })
 }
 
+// Scans `args` according to `arg_spec`, parsed from a Ruby-style parameter
+// list (see `method!`), writing one value per parameter to `out`. Missing
+// optional arguments are `nil`, missing keywords are an empty hash.
+pub(crate) fn scan_params(
+    args: &[Value],
+    arg_spec: ArgSpec,
+    out: &mut [Value],
+) -> Result<(), Error> {
+    let len = arg_spec.len();
+    let keywords = arg_spec.keywords.then(|| len - 1 - arg_spec.block as usize);
+    let scanned_args = scan_args_untyped(args, arg_spec)?;
+    out[..len].copy_from_slice(&scanned_args.args[..len]);
+    if let Some(i) = keywords {
+        if out[i].is_nil() {
+            out[i] = *RHash::new();
+        }
+    }
+    Ok(())
+}
+
 // Fairly close to rb_scan_args, but Rust types and works around variadic args.
 // Size of `out` must be >= number of arguments specified in `fmt`.
 unsafe fn scan_args_impl(args: &[Value], fmt: &str, out: &mut [Value]) -> Result<usize, Error> {
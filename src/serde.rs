@@ -0,0 +1,773 @@
+//! Conversion between Rust types implementing [serde]'s `Serialize` and
+//! `Deserialize` traits and Ruby objects.
+//!
+//! Values are converted directly to and from Ruby objects, without an
+//! intermediate format such as JSON.
+//!
+//! | Rust                           | Ruby                          |
+//! |--------------------------------|-------------------------------|
+//! | `bool`                         | `true`/`false`                |
+//! | integers                       | `Integer`                     |
+//! | floats                         | `Float`                       |
+//! | `char`, `str`, `String`        | `String`                      |
+//! | bytes                          | `String` (binary)             |
+//! | `None`, `()`, unit structs     | `nil`                         |
+//! | sequences, tuples              | `Array`                       |
+//! | maps                           | `Hash`                        |
+//! | structs                        | `Hash` with `Symbol` keys     |
+//! | unit enum variants             | `Symbol`                      |
+//! | other enum variants            | `Hash` of `{variant => data}` |
+//!
+//! When converting from Ruby, `Symbol`s may be used anywhere a string is
+//! expected, and `String`s may be used for enum variants.
+//!
+//! This module is only available when the `serde` feature is enabled.
+//!
+//! [serde]: https://serde.rs
+
+use std::{borrow::Cow, fmt};
+
+use ::serde::{
+    de::{self, DeserializeOwned, DeserializeSeed, IntoDeserializer, Visitor},
+    forward_to_deserialize_any,
+    ser::{self, Serialize},
+};
+
+use crate::{
+    error::Error,
+    exception,
+    float::Float,
+    integer::Integer,
+    r_array::RArray,
+    r_hash::{ForEach, RHash},
+    r_string::RString,
+    ruby_sys::ruby_value_type,
+    symbol::Symbol,
+    value::{Value, QNIL},
+};
+
+/// Convert `value` to a Ruby object.
+///
+/// See the [module level documentation](self) for how types are mapped.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+/// use magnus::{eval, serde::to_value};
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// let mut map = HashMap::new();
+/// map.insert("primes", vec![2, 3, 5, 7]);
+/// let value = to_value(&map).unwrap();
+/// let res: bool = eval!(r#"value == {"primes" => [2, 3, 5, 7]}"#, value).unwrap();
+/// assert!(res);
+/// ```
+pub fn to_value<T>(value: &T) -> Result<Value, Error>
+where
+    T: Serialize + ?Sized,
+{
+    value.serialize(Serializer).map_err(|e| e.0)
+}
+
+/// Convert the Ruby object `val` to a `T`.
+///
+/// See the [module level documentation](self) for how types are mapped.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+/// use magnus::{eval, serde::from_value, Value};
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// let value = eval::<Value>(r#"{primes: [2, 3, 5, 7]}"#).unwrap();
+/// let map: HashMap<String, Vec<u8>> = from_value(value).unwrap();
+/// assert_eq!(map["primes"], vec![2, 3, 5, 7]);
+/// ```
+pub fn from_value<T>(val: Value) -> Result<T, Error>
+where
+    T: DeserializeOwned,
+{
+    T::deserialize(Deserializer(val)).map_err(|e| e.0)
+}
+
+// serde requires its error types implement `std::error::Error`.
+#[derive(Debug)]
+struct SerdeError(Error);
+
+impl From<Error> for SerdeError {
+    fn from(e: Error) -> Self {
+        Self(e)
+    }
+}
+
+impl fmt::Display for SerdeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl std::error::Error for SerdeError {}
+
+impl ser::Error for SerdeError {
+    fn custom<T>(msg: T) -> Self
+    where
+        T: fmt::Display,
+    {
+        Self(Error::new(exception::arg_error(), msg.to_string()))
+    }
+}
+
+impl de::Error for SerdeError {
+    fn custom<T>(msg: T) -> Self
+    where
+        T: fmt::Display,
+    {
+        Self(Error::new(exception::arg_error(), msg.to_string()))
+    }
+
+    fn invalid_type(unexp: de::Unexpected, exp: &dyn de::Expected) -> Self {
+        Self(Error::new(
+            exception::type_error(),
+            format!("invalid type: {}, expected {}", unexp, exp),
+        ))
+    }
+}
+
+// Wraps `val` in a single entry Hash of `{variant => val}`.
+fn variant_hash(variant: &'static str, val: Value) -> Result<Value, SerdeError> {
+    let hash = RHash::new();
+    hash.aset(Symbol::new(variant), val)?;
+    Ok(*hash)
+}
+
+struct Serializer;
+
+impl ser::Serializer for Serializer {
+    type Ok = Value;
+    type Error = SerdeError;
+
+    type SerializeSeq = SerializeArray;
+    type SerializeTuple = SerializeArray;
+    type SerializeTupleStruct = SerializeArray;
+    type SerializeTupleVariant = SerializeArray;
+    type SerializeMap = SerializeHash;
+    type SerializeStruct = SerializeHash;
+    type SerializeStructVariant = SerializeHash;
+
+    fn serialize_bool(self, v: bool) -> Result<Value, SerdeError> {
+        Ok(v.into())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Value, SerdeError> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Value, SerdeError> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Value, SerdeError> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Value, SerdeError> {
+        Ok(*Integer::from_i64(v))
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Value, SerdeError> {
+        self.serialize_u64(v as u64)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Value, SerdeError> {
+        self.serialize_u64(v as u64)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Value, SerdeError> {
+        self.serialize_u64(v as u64)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Value, SerdeError> {
+        Ok(*Integer::from_u64(v))
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Value, SerdeError> {
+        self.serialize_f64(v as f64)
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Value, SerdeError> {
+        Ok(*Float::from_f64(v))
+    }
+
+    fn serialize_char(self, v: char) -> Result<Value, SerdeError> {
+        Ok(*RString::from_char(v))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Value, SerdeError> {
+        Ok(*RString::new(v))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Value, SerdeError> {
+        Ok(*RString::from_slice(v))
+    }
+
+    fn serialize_none(self) -> Result<Value, SerdeError> {
+        Ok(QNIL.into())
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<Value, SerdeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Value, SerdeError> {
+        Ok(QNIL.into())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Value, SerdeError> {
+        Ok(QNIL.into())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Value, SerdeError> {
+        Ok(*Symbol::new(variant))
+    }
+
+    fn serialize_newtype_struct<T>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Value, SerdeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Value, SerdeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        variant_hash(variant, value.serialize(self)?)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SerializeArray, SerdeError> {
+        Ok(SerializeArray::new(len.unwrap_or(0), None))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SerializeArray, SerdeError> {
+        Ok(SerializeArray::new(len, None))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SerializeArray, SerdeError> {
+        Ok(SerializeArray::new(len, None))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeArray, SerdeError> {
+        Ok(SerializeArray::new(len, Some(variant)))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<SerializeHash, SerdeError> {
+        Ok(SerializeHash::new(None))
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<SerializeHash, SerdeError> {
+        Ok(SerializeHash::new(None))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<SerializeHash, SerdeError> {
+        Ok(SerializeHash::new(Some(variant)))
+    }
+}
+
+struct SerializeArray {
+    ary: RArray,
+    variant: Option<&'static str>,
+}
+
+impl SerializeArray {
+    fn new(capacity: usize, variant: Option<&'static str>) -> Self {
+        Self {
+            ary: RArray::with_capacity(capacity),
+            variant,
+        }
+    }
+
+    fn push<T>(&mut self, value: &T) -> Result<(), SerdeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        Ok(self.ary.push(value.serialize(Serializer)?)?)
+    }
+
+    fn finish(self) -> Result<Value, SerdeError> {
+        match self.variant {
+            Some(variant) => variant_hash(variant, *self.ary),
+            None => Ok(*self.ary),
+        }
+    }
+}
+
+impl ser::SerializeSeq for SerializeArray {
+    type Ok = Value;
+    type Error = SerdeError;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), SerdeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value, SerdeError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTuple for SerializeArray {
+    type Ok = Value;
+    type Error = SerdeError;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), SerdeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value, SerdeError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleStruct for SerializeArray {
+    type Ok = Value;
+    type Error = SerdeError;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), SerdeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value, SerdeError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleVariant for SerializeArray {
+    type Ok = Value;
+    type Error = SerdeError;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), SerdeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value, SerdeError> {
+        self.finish()
+    }
+}
+
+struct SerializeHash {
+    hash: RHash,
+    key: Value,
+    variant: Option<&'static str>,
+}
+
+impl SerializeHash {
+    fn new(variant: Option<&'static str>) -> Self {
+        Self {
+            hash: RHash::new(),
+            key: QNIL.into(),
+            variant,
+        }
+    }
+
+    fn finish(self) -> Result<Value, SerdeError> {
+        match self.variant {
+            Some(variant) => variant_hash(variant, *self.hash),
+            None => Ok(*self.hash),
+        }
+    }
+}
+
+impl ser::SerializeMap for SerializeHash {
+    type Ok = Value;
+    type Error = SerdeError;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<(), SerdeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.key = key.serialize(Serializer)?;
+        Ok(())
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<(), SerdeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        Ok(self.hash.aset(self.key, value.serialize(Serializer)?)?)
+    }
+
+    fn end(self) -> Result<Value, SerdeError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStruct for SerializeHash {
+    type Ok = Value;
+    type Error = SerdeError;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), SerdeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        Ok(self
+            .hash
+            .aset(Symbol::new(key), value.serialize(Serializer)?)?)
+    }
+
+    fn end(self) -> Result<Value, SerdeError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStructVariant for SerializeHash {
+    type Ok = Value;
+    type Error = SerdeError;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), SerdeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        Ok(self
+            .hash
+            .aset(Symbol::new(key), value.serialize(Serializer)?)?)
+    }
+
+    fn end(self) -> Result<Value, SerdeError> {
+        self.finish()
+    }
+}
+
+fn unexpected(val: Value) -> String {
+    format!("instance of {}", unsafe { val.classname() })
+}
+
+fn str_name(val: Value) -> Result<Option<Cow<'static, str>>, SerdeError> {
+    if let Some(sym) = Symbol::from_value(val) {
+        return Ok(Some(sym.name()?));
+    }
+    if let Some(s) = RString::from_value(val) {
+        return Ok(Some(s.to_string()?.into()));
+    }
+    Ok(None)
+}
+
+struct Deserializer(Value);
+
+impl<'de> de::Deserializer<'de> for Deserializer {
+    type Error = SerdeError;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, SerdeError>
+    where
+        V: Visitor<'de>,
+    {
+        let val = self.0;
+        match val.rb_type() {
+            ruby_value_type::RUBY_T_NIL => visitor.visit_unit(),
+            ruby_value_type::RUBY_T_TRUE => visitor.visit_bool(true),
+            ruby_value_type::RUBY_T_FALSE => visitor.visit_bool(false),
+            ruby_value_type::RUBY_T_FIXNUM | ruby_value_type::RUBY_T_BIGNUM => {
+                let i = Integer::from_value(val).unwrap();
+                match i.to_i64() {
+                    Ok(n) => visitor.visit_i64(n),
+                    Err(_) => visitor.visit_u64(i.to_u64()?),
+                }
+            }
+            ruby_value_type::RUBY_T_FLOAT => {
+                visitor.visit_f64(Float::from_value(val).unwrap().to_f64())
+            }
+            ruby_value_type::RUBY_T_STRING => {
+                visitor.visit_string(RString::from_value(val).unwrap().to_string()?)
+            }
+            ruby_value_type::RUBY_T_SYMBOL => match Symbol::from_value(val).unwrap().name()? {
+                Cow::Borrowed(s) => visitor.visit_str(s),
+                Cow::Owned(s) => visitor.visit_string(s),
+            },
+            ruby_value_type::RUBY_T_ARRAY => visitor.visit_seq(ArrayAccess {
+                ary: RArray::from_value(val).unwrap(),
+                index: 0,
+            }),
+            ruby_value_type::RUBY_T_HASH => {
+                visitor.visit_map(HashAccess::new(RHash::from_value(val).unwrap())?)
+            }
+            _ => Err(de::Error::invalid_type(
+                de::Unexpected::Other(&unexpected(val)),
+                &visitor,
+            )),
+        }
+    }
+
+    fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value, SerdeError>
+    where
+        V: Visitor<'de>,
+    {
+        match RString::from_value(self.0) {
+            Some(s) => visitor.visit_byte_buf(unsafe { s.as_slice().to_vec() }),
+            None => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value, SerdeError>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, SerdeError>
+    where
+        V: Visitor<'de>,
+    {
+        if self.0.is_nil() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, SerdeError>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, SerdeError>
+    where
+        V: Visitor<'de>,
+    {
+        let val = self.0;
+        if let Some(name) = str_name(val)? {
+            return visitor.visit_enum(name.into_owned().into_deserializer());
+        }
+        if let Some(hash) = RHash::from_value(val) {
+            let access = HashAccess::new(hash)?;
+            if access.len() == 1 {
+                return visitor.visit_enum(VariantAccess {
+                    variant: access.pairs.entry(0)?,
+                    value: access.pairs.entry(1)?,
+                });
+            }
+        }
+        Err(de::Error::invalid_type(
+            de::Unexpected::Other(&unexpected(val)),
+            &"Symbol, String, or Hash with a single key",
+        ))
+    }
+
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, SerdeError>
+    where
+        V: Visitor<'de>,
+    {
+        if let Some(ary) = RArray::from_value(self.0) {
+            if ary.len() != len {
+                return Err(de::Error::invalid_length(ary.len(), &visitor));
+            }
+        }
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, SerdeError>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        unit unit_struct seq map struct identifier ignored_any
+    }
+}
+
+struct ArrayAccess {
+    ary: RArray,
+    index: usize,
+}
+
+impl<'de> de::SeqAccess<'de> for ArrayAccess {
+    type Error = SerdeError;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, SerdeError>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        if self.index >= self.ary.len() {
+            return Ok(None);
+        }
+        let val = self.ary.entry(self.index as isize)?;
+        self.index += 1;
+        seed.deserialize(Deserializer(val)).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.ary.len().saturating_sub(self.index))
+    }
+}
+
+struct HashAccess {
+    // keys and values, flattened as `[k, v, k, v, ...]`, so they remain
+    // visible to the GC while we iterate
+    pairs: RArray,
+    index: usize,
+}
+
+impl HashAccess {
+    fn new(hash: RHash) -> Result<Self, SerdeError> {
+        let pairs = RArray::with_capacity(hash.len() * 2);
+        hash.foreach(|key, value| {
+            pairs.push(key)?;
+            pairs.push(value)?;
+            Ok(ForEach::Continue)
+        })?;
+        Ok(Self { pairs, index: 0 })
+    }
+
+    fn len(&self) -> usize {
+        self.pairs.len() / 2
+    }
+}
+
+impl<'de> de::MapAccess<'de> for HashAccess {
+    type Error = SerdeError;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, SerdeError>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        if self.index >= self.pairs.len() {
+            return Ok(None);
+        }
+        let key = self.pairs.entry(self.index as isize)?;
+        self.index += 1;
+        seed.deserialize(Deserializer(key)).map(Some)
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, SerdeError>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let value = self.pairs.entry(self.index as isize)?;
+        self.index += 1;
+        seed.deserialize(Deserializer(value))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.len().saturating_sub(self.index / 2))
+    }
+}
+
+struct VariantAccess {
+    variant: Value,
+    value: Value,
+}
+
+impl<'de> de::EnumAccess<'de> for VariantAccess {
+    type Error = SerdeError;
+    type Variant = Deserializer;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Deserializer), SerdeError>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let variant = seed.deserialize(Deserializer(self.variant))?;
+        Ok((variant, Deserializer(self.value)))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for Deserializer {
+    type Error = SerdeError;
+
+    fn unit_variant(self) -> Result<(), SerdeError> {
+        de::Deserialize::deserialize(self)
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, SerdeError>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value, SerdeError>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_seq(self, visitor)
+    }
+
+    fn struct_variant<V>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, SerdeError>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_map(self, visitor)
+    }
+}
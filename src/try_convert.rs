@@ -27,6 +27,17 @@ pub trait TryConvertOwned: TryConvert {
     fn try_convert_owned(val: Value) -> Result<Self, Error> {
         Self::try_convert(&val)
     }
+
+    /// Check `val` can be converted into `Self`, returning the same error
+    /// as [`try_convert_owned`](TryConvertOwned::try_convert_owned) if not.
+    ///
+    /// The default implementation performs the conversion and discards the
+    /// result. Types where conversion allocates should implement a cheaper
+    /// check.
+    #[inline]
+    fn check_convert(val: Value) -> Result<(), Error> {
+        Self::try_convert_owned(val).map(|_| ())
+    }
 }
 
 impl<T> TryConvert for Option<T>
@@ -49,6 +60,15 @@ where
             .then(|| T::try_convert_owned(val))
             .transpose()
     }
+
+    #[inline]
+    fn check_convert(val: Value) -> Result<(), Error> {
+        if val.is_nil() {
+            Ok(())
+        } else {
+            T::check_convert(val)
+        }
+    }
 }
 
 impl TryConvert for bool {
@@ -91,6 +111,14 @@ impl TryConvert for i64 {
 }
 impl TryConvertOwned for i64 {}
 
+impl TryConvert for i128 {
+    #[inline]
+    fn try_convert(val: &Value) -> Result<Self, Error> {
+        Integer::try_convert(val)?.to_i128()
+    }
+}
+impl TryConvertOwned for i128 {}
+
 impl TryConvert for isize {
     #[inline]
     fn try_convert(val: &Value) -> Result<Self, Error> {
@@ -131,6 +159,14 @@ impl TryConvert for u64 {
 }
 impl TryConvertOwned for u64 {}
 
+impl TryConvert for u128 {
+    #[inline]
+    fn try_convert(val: &Value) -> Result<Self, Error> {
+        Integer::try_convert(val)?.to_u128()
+    }
+}
+impl TryConvertOwned for u128 {}
+
 impl TryConvert for usize {
     #[inline]
     fn try_convert(val: &Value) -> Result<Self, Error> {
@@ -168,7 +204,12 @@ impl TryConvert for String {
         RString::try_convert(val)?.to_string()
     }
 }
-impl TryConvertOwned for String {}
+impl TryConvertOwned for String {
+    fn check_convert(val: Value) -> Result<(), Error> {
+        debug_assert_value!(val);
+        RString::try_convert(&val)?.check_utf8()
+    }
+}
 
 impl TryConvert for char {
     #[inline]
@@ -189,540 +230,95 @@ where
         RArray::try_convert(val)?.to_vec()
     }
 }
-impl<T> TryConvertOwned for Vec<T> where T: TryConvertOwned {}
-
-impl<T, const N: usize> TryConvert for [T; N]
-where
-    T: TryConvert,
-{
-    #[inline]
-    fn try_convert(val: &Value) -> Result<Self, Error> {
-        debug_assert_value!(val);
-        RArray::try_convert(val)?.to_array()
-    }
-}
-impl<T, const N: usize> TryConvertOwned for [T; N] where T: TryConvert {}
-
-impl<T0> TryConvert for (T0,)
-where
-    T0: TryConvert,
-{
-    #[inline]
-    fn try_convert(val: &Value) -> Result<Self, Error> {
-        debug_assert_value!(val);
-        let array = RArray::try_convert(val)?;
-        let slice = unsafe { array.as_slice() };
-        if slice.len() != 1 {
-            return Err(Error::new(
-                exception::type_error(),
-                "expected Array of length 1",
-            ));
-        }
-        Ok((slice[0].try_convert()?,))
-    }
-}
-impl<T0> TryConvertOwned for (T0,) where T0: TryConvert {}
-
-impl<T0, T1> TryConvert for (T0, T1)
-where
-    T0: TryConvert,
-    T1: TryConvert,
-{
-    #[inline]
-    fn try_convert(val: &Value) -> Result<Self, Error> {
-        debug_assert_value!(val);
-        let array = RArray::try_convert(val)?;
-        let slice = unsafe { array.as_slice() };
-        if slice.len() != 2 {
-            return Err(Error::new(
-                exception::type_error(),
-                "expected Array of length 2",
-            ));
-        }
-        Ok((slice[0].try_convert()?, slice[1].try_convert()?))
-    }
-}
-impl<T0, T1> TryConvertOwned for (T0, T1)
-where
-    T0: TryConvert,
-    T1: TryConvert,
-{
-}
-
-impl<T0, T1, T2> TryConvert for (T0, T1, T2)
-where
-    T0: TryConvert,
-    T1: TryConvert,
-    T2: TryConvert,
-{
-    #[inline]
-    fn try_convert(val: &Value) -> Result<Self, Error> {
-        debug_assert_value!(val);
-        let array = RArray::try_convert(val)?;
-        let slice = unsafe { array.as_slice() };
-        if slice.len() != 3 {
-            return Err(Error::new(
-                exception::type_error(),
-                "expected Array of length 3",
-            ));
-        }
-        Ok((
-            slice[0].try_convert()?,
-            slice[1].try_convert()?,
-            slice[2].try_convert()?,
-        ))
-    }
-}
-impl<T0, T1, T2> TryConvertOwned for (T0, T1, T2)
+impl<T> TryConvertOwned for Vec<T>
 where
-    T0: TryConvert,
-    T1: TryConvert,
-    T2: TryConvert,
-{
-}
-
-impl<T0, T1, T2, T3> TryConvert for (T0, T1, T2, T3)
-where
-    T0: TryConvert,
-    T1: TryConvert,
-    T2: TryConvert,
-    T3: TryConvert,
-{
-    #[inline]
-    fn try_convert(val: &Value) -> Result<Self, Error> {
-        debug_assert_value!(val);
-        let array = RArray::try_convert(val)?;
-        let slice = unsafe { array.as_slice() };
-        if slice.len() != 4 {
-            return Err(Error::new(
-                exception::type_error(),
-                "expected Array of length 4",
-            ));
-        }
-        Ok((
-            slice[0].try_convert()?,
-            slice[1].try_convert()?,
-            slice[2].try_convert()?,
-            slice[3].try_convert()?,
-        ))
-    }
-}
-impl<T0, T1, T2, T3> TryConvertOwned for (T0, T1, T2, T3)
-where
-    T0: TryConvert,
-    T1: TryConvert,
-    T2: TryConvert,
-    T3: TryConvert,
-{
-}
-
-impl<T0, T1, T2, T3, T4> TryConvert for (T0, T1, T2, T3, T4)
-where
-    T0: TryConvert,
-    T1: TryConvert,
-    T2: TryConvert,
-    T3: TryConvert,
-    T4: TryConvert,
-{
-    #[inline]
-    fn try_convert(val: &Value) -> Result<Self, Error> {
-        debug_assert_value!(val);
-        let array = RArray::try_convert(val)?;
-        let slice = unsafe { array.as_slice() };
-        if slice.len() != 5 {
-            return Err(Error::new(
-                exception::type_error(),
-                "expected Array of length 5",
-            ));
-        }
-        Ok((
-            slice[0].try_convert()?,
-            slice[1].try_convert()?,
-            slice[2].try_convert()?,
-            slice[3].try_convert()?,
-            slice[4].try_convert()?,
-        ))
-    }
-}
-impl<T0, T1, T2, T3, T4> TryConvertOwned for (T0, T1, T2, T3, T4)
-where
-    T0: TryConvert,
-    T1: TryConvert,
-    T2: TryConvert,
-    T3: TryConvert,
-    T4: TryConvert,
-{
-}
-
-impl<T0, T1, T2, T3, T4, T5> TryConvert for (T0, T1, T2, T3, T4, T5)
-where
-    T0: TryConvert,
-    T1: TryConvert,
-    T2: TryConvert,
-    T3: TryConvert,
-    T4: TryConvert,
-    T5: TryConvert,
-{
-    #[inline]
-    fn try_convert(val: &Value) -> Result<Self, Error> {
-        debug_assert_value!(val);
-        let array = RArray::try_convert(val)?;
-        let slice = unsafe { array.as_slice() };
-        if slice.len() != 6 {
-            return Err(Error::new(
-                exception::type_error(),
-                "expected Array of length 6",
-            ));
-        }
-        Ok((
-            slice[0].try_convert()?,
-            slice[1].try_convert()?,
-            slice[2].try_convert()?,
-            slice[3].try_convert()?,
-            slice[4].try_convert()?,
-            slice[5].try_convert()?,
-        ))
-    }
-}
-impl<T0, T1, T2, T3, T4, T5> TryConvertOwned for (T0, T1, T2, T3, T4, T5)
-where
-    T0: TryConvert,
-    T1: TryConvert,
-    T2: TryConvert,
-    T3: TryConvert,
-    T4: TryConvert,
-    T5: TryConvert,
-{
-}
-
-impl<T0, T1, T2, T3, T4, T5, T6> TryConvert for (T0, T1, T2, T3, T4, T5, T6)
-where
-    T0: TryConvert,
-    T1: TryConvert,
-    T2: TryConvert,
-    T3: TryConvert,
-    T4: TryConvert,
-    T5: TryConvert,
-    T6: TryConvert,
+    T: TryConvertOwned,
 {
-    #[inline]
-    fn try_convert(val: &Value) -> Result<Self, Error> {
+    fn check_convert(val: Value) -> Result<(), Error> {
         debug_assert_value!(val);
-        let array = RArray::try_convert(val)?;
-        let slice = unsafe { array.as_slice() };
-        if slice.len() != 7 {
-            return Err(Error::new(
-                exception::type_error(),
-                "expected Array of length 7",
-            ));
+        let ary = RArray::try_convert(&val)?;
+        let mut i = 0;
+        // length is re-checked each time as checks may call Ruby
+        while i < ary.len() {
+            T::check_convert(ary.entry(i as isize)?)?;
+            i += 1;
         }
-        Ok((
-            slice[0].try_convert()?,
-            slice[1].try_convert()?,
-            slice[2].try_convert()?,
-            slice[3].try_convert()?,
-            slice[4].try_convert()?,
-            slice[5].try_convert()?,
-            slice[6].try_convert()?,
-        ))
+        Ok(())
     }
 }
-impl<T0, T1, T2, T3, T4, T5, T6> TryConvertOwned for (T0, T1, T2, T3, T4, T5, T6)
-where
-    T0: TryConvert,
-    T1: TryConvert,
-    T2: TryConvert,
-    T3: TryConvert,
-    T4: TryConvert,
-    T5: TryConvert,
-    T6: TryConvert,
-{
-}
 
-impl<T0, T1, T2, T3, T4, T5, T6, T7> TryConvert for (T0, T1, T2, T3, T4, T5, T6, T7)
+impl<T, const N: usize> TryConvert for [T; N]
 where
-    T0: TryConvert,
-    T1: TryConvert,
-    T2: TryConvert,
-    T3: TryConvert,
-    T4: TryConvert,
-    T5: TryConvert,
-    T6: TryConvert,
-    T7: TryConvert,
+    T: TryConvert,
 {
     #[inline]
     fn try_convert(val: &Value) -> Result<Self, Error> {
         debug_assert_value!(val);
-        let array = RArray::try_convert(val)?;
-        let slice = unsafe { array.as_slice() };
-        if slice.len() != 8 {
-            return Err(Error::new(
-                exception::type_error(),
-                "expected Array of length 8",
-            ));
-        }
-        Ok((
-            slice[0].try_convert()?,
-            slice[1].try_convert()?,
-            slice[2].try_convert()?,
-            slice[3].try_convert()?,
-            slice[4].try_convert()?,
-            slice[5].try_convert()?,
-            slice[6].try_convert()?,
-            slice[7].try_convert()?,
-        ))
+        RArray::try_convert(val)?.to_array()
     }
 }
-impl<T0, T1, T2, T3, T4, T5, T6, T7> TryConvertOwned for (T0, T1, T2, T3, T4, T5, T6, T7)
-where
-    T0: TryConvert,
-    T1: TryConvert,
-    T2: TryConvert,
-    T3: TryConvert,
-    T4: TryConvert,
-    T5: TryConvert,
-    T6: TryConvert,
-    T7: TryConvert,
-{
-}
+impl<T, const N: usize> TryConvertOwned for [T; N] where T: TryConvert {}
 
-impl<T0, T1, T2, T3, T4, T5, T6, T7, T8> TryConvert for (T0, T1, T2, T3, T4, T5, T6, T7, T8)
-where
-    T0: TryConvert,
-    T1: TryConvert,
-    T2: TryConvert,
-    T3: TryConvert,
-    T4: TryConvert,
-    T5: TryConvert,
-    T6: TryConvert,
-    T7: TryConvert,
-    T8: TryConvert,
-{
-    #[inline]
-    fn try_convert(val: &Value) -> Result<Self, Error> {
-        debug_assert_value!(val);
-        let array = RArray::try_convert(val)?;
-        let slice = unsafe { array.as_slice() };
-        if slice.len() != 9 {
-            return Err(Error::new(
-                exception::type_error(),
-                "expected Array of length 9",
-            ));
+macro_rules! impl_try_convert_tuple {
+    () => {};
+    ($($t:ident $i:tt)+) => {
+        impl<$($t),+> TryConvert for ($($t,)+)
+        where
+            $($t: TryConvert,)+
+        {
+            #[inline]
+            fn try_convert(val: &Value) -> Result<Self, Error> {
+                debug_assert_value!(val);
+                let array = RArray::try_convert(val)?;
+                let slice = unsafe { array.as_slice() };
+                if slice.len() != count!($($t)+) {
+                    return Err(Error::new(
+                        exception::type_error(),
+                        format!("expected Array of length {}", count!($($t)+)),
+                    ));
+                }
+                Ok(($(slice[$i].try_convert()?,)+))
+            }
         }
-        Ok((
-            slice[0].try_convert()?,
-            slice[1].try_convert()?,
-            slice[2].try_convert()?,
-            slice[3].try_convert()?,
-            slice[4].try_convert()?,
-            slice[5].try_convert()?,
-            slice[6].try_convert()?,
-            slice[7].try_convert()?,
-            slice[8].try_convert()?,
-        ))
-    }
-}
-impl<T0, T1, T2, T3, T4, T5, T6, T7, T8> TryConvertOwned for (T0, T1, T2, T3, T4, T5, T6, T7, T8)
-where
-    T0: TryConvert,
-    T1: TryConvert,
-    T2: TryConvert,
-    T3: TryConvert,
-    T4: TryConvert,
-    T5: TryConvert,
-    T6: TryConvert,
-    T7: TryConvert,
-    T8: TryConvert,
-{
-}
 
-impl<T0, T1, T2, T3, T4, T5, T6, T7, T8, T9> TryConvert for (T0, T1, T2, T3, T4, T5, T6, T7, T8, T9)
-where
-    T0: TryConvert,
-    T1: TryConvert,
-    T2: TryConvert,
-    T3: TryConvert,
-    T4: TryConvert,
-    T5: TryConvert,
-    T6: TryConvert,
-    T7: TryConvert,
-    T8: TryConvert,
-    T9: TryConvert,
-{
-    #[inline]
-    fn try_convert(val: &Value) -> Result<Self, Error> {
-        debug_assert_value!(val);
-        let array = RArray::try_convert(val)?;
-        let slice = unsafe { array.as_slice() };
-        if slice.len() != 10 {
-            return Err(Error::new(
-                exception::type_error(),
-                "expected Array of length 10",
-            ));
-        }
-        Ok((
-            slice[0].try_convert()?,
-            slice[1].try_convert()?,
-            slice[2].try_convert()?,
-            slice[3].try_convert()?,
-            slice[4].try_convert()?,
-            slice[5].try_convert()?,
-            slice[6].try_convert()?,
-            slice[7].try_convert()?,
-            slice[8].try_convert()?,
-            slice[9].try_convert()?,
-        ))
-    }
-}
-impl<T0, T1, T2, T3, T4, T5, T6, T7, T8, T9> TryConvertOwned
-    for (T0, T1, T2, T3, T4, T5, T6, T7, T8, T9)
-where
-    T0: TryConvert,
-    T1: TryConvert,
-    T2: TryConvert,
-    T3: TryConvert,
-    T4: TryConvert,
-    T5: TryConvert,
-    T6: TryConvert,
-    T7: TryConvert,
-    T8: TryConvert,
-    T9: TryConvert,
-{
+        impl<$($t),+> TryConvertOwned for ($($t,)+) where $($t: TryConvert,)+ {}
+    };
 }
 
-impl<T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10> TryConvert
-    for (T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10)
-where
-    T0: TryConvert,
-    T1: TryConvert,
-    T2: TryConvert,
-    T3: TryConvert,
-    T4: TryConvert,
-    T5: TryConvert,
-    T6: TryConvert,
-    T7: TryConvert,
-    T8: TryConvert,
-    T9: TryConvert,
-    T10: TryConvert,
-{
-    #[inline]
-    fn try_convert(val: &Value) -> Result<Self, Error> {
-        debug_assert_value!(val);
-        let array = RArray::try_convert(val)?;
-        let slice = unsafe { array.as_slice() };
-        if slice.len() != 11 {
-            return Err(Error::new(
-                exception::type_error(),
-                "expected Array of length 11",
-            ));
-        }
-        Ok((
-            slice[0].try_convert()?,
-            slice[1].try_convert()?,
-            slice[2].try_convert()?,
-            slice[3].try_convert()?,
-            slice[4].try_convert()?,
-            slice[5].try_convert()?,
-            slice[6].try_convert()?,
-            slice[7].try_convert()?,
-            slice[8].try_convert()?,
-            slice[9].try_convert()?,
-            slice[10].try_convert()?,
-        ))
-    }
-}
-impl<T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10> TryConvertOwned
-    for (T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10)
-where
-    T0: TryConvert,
-    T1: TryConvert,
-    T2: TryConvert,
-    T3: TryConvert,
-    T4: TryConvert,
-    T5: TryConvert,
-    T6: TryConvert,
-    T7: TryConvert,
-    T8: TryConvert,
-    T9: TryConvert,
-    T10: TryConvert,
-{
-}
+for_each_tuple!(impl_try_convert_tuple);
 
-impl<T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11> TryConvert
-    for (T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11)
+impl<K, V> TryConvert for std::collections::HashMap<K, V>
 where
-    T0: TryConvert,
-    T1: TryConvert,
-    T2: TryConvert,
-    T3: TryConvert,
-    T4: TryConvert,
-    T5: TryConvert,
-    T6: TryConvert,
-    T7: TryConvert,
-    T8: TryConvert,
-    T9: TryConvert,
-    T10: TryConvert,
-    T11: TryConvert,
+    K: TryConvertOwned + Eq + std::hash::Hash,
+    V: TryConvertOwned,
 {
     #[inline]
     fn try_convert(val: &Value) -> Result<Self, Error> {
         debug_assert_value!(val);
-        let array = RArray::try_convert(val)?;
-        let slice = unsafe { array.as_slice() };
-        if slice.len() != 12 {
-            return Err(Error::new(
-                exception::type_error(),
-                "expected Array of length 12",
-            ));
-        }
-        Ok((
-            slice[0].try_convert()?,
-            slice[1].try_convert()?,
-            slice[2].try_convert()?,
-            slice[3].try_convert()?,
-            slice[4].try_convert()?,
-            slice[5].try_convert()?,
-            slice[6].try_convert()?,
-            slice[7].try_convert()?,
-            slice[8].try_convert()?,
-            slice[9].try_convert()?,
-            slice[10].try_convert()?,
-            slice[11].try_convert()?,
-        ))
+        RHash::try_convert(val)?.to_hash_map()
     }
 }
-impl<T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11> TryConvertOwned
-    for (T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11)
+impl<K, V> TryConvertOwned for std::collections::HashMap<K, V>
 where
-    T0: TryConvert,
-    T1: TryConvert,
-    T2: TryConvert,
-    T3: TryConvert,
-    T4: TryConvert,
-    T5: TryConvert,
-    T6: TryConvert,
-    T7: TryConvert,
-    T8: TryConvert,
-    T9: TryConvert,
-    T10: TryConvert,
-    T11: TryConvert,
+    K: TryConvertOwned + Eq + std::hash::Hash,
+    V: TryConvertOwned,
 {
 }
 
-impl<K, V> TryConvert for std::collections::HashMap<K, V>
+impl<K, V> TryConvert for std::collections::BTreeMap<K, V>
 where
-    K: TryConvertOwned + Eq + std::hash::Hash,
+    K: TryConvertOwned + Ord,
     V: TryConvertOwned,
 {
     #[inline]
     fn try_convert(val: &Value) -> Result<Self, Error> {
         debug_assert_value!(val);
-        RHash::try_convert(val)?.to_hash_map()
+        RHash::try_convert(val)?.iter().collect()
     }
 }
-impl<K, V> TryConvertOwned for std::collections::HashMap<K, V>
+impl<K, V> TryConvertOwned for std::collections::BTreeMap<K, V>
 where
-    K: TryConvertOwned + Eq + std::hash::Hash,
+    K: TryConvertOwned + Ord,
     V: TryConvertOwned,
 {
 }
@@ -776,287 +372,22 @@ impl<'a> ArgList for &'a [Value] {
     }
 }
 
-impl ArgList for () {
-    type Output = [Value; 0];
-
-    fn into_arg_list(self) -> Self::Output {
-        []
-    }
-}
-
-impl<A> ArgList for (A,)
-where
-    A: Into<Value>,
-{
-    type Output = [Value; 1];
-
-    fn into_arg_list(self) -> Self::Output {
-        [self.0.into()]
-    }
-}
-
-impl<A, B> ArgList for (A, B)
-where
-    A: Into<Value>,
-    B: Into<Value>,
-{
-    type Output = [Value; 2];
-
-    fn into_arg_list(self) -> Self::Output {
-        [self.0.into(), self.1.into()]
-    }
-}
-
-impl<A, B, C> ArgList for (A, B, C)
-where
-    A: Into<Value>,
-    B: Into<Value>,
-    C: Into<Value>,
-{
-    type Output = [Value; 3];
-
-    fn into_arg_list(self) -> Self::Output {
-        [self.0.into(), self.1.into(), self.2.into()]
-    }
-}
+macro_rules! impl_arg_list_tuple {
+    ($($t:ident $i:tt)*) => {
+        impl<$($t),*> ArgList for ($($t,)*)
+        where
+            $($t: Into<Value>,)*
+        {
+            type Output = [Value; count!($($t)*)];
 
-impl<A, B, C, D> ArgList for (A, B, C, D)
-where
-    A: Into<Value>,
-    B: Into<Value>,
-    C: Into<Value>,
-    D: Into<Value>,
-{
-    type Output = [Value; 4];
-
-    fn into_arg_list(self) -> Self::Output {
-        [self.0.into(), self.1.into(), self.2.into(), self.3.into()]
-    }
-}
-
-impl<A, B, C, D, E> ArgList for (A, B, C, D, E)
-where
-    A: Into<Value>,
-    B: Into<Value>,
-    C: Into<Value>,
-    D: Into<Value>,
-    E: Into<Value>,
-{
-    type Output = [Value; 5];
-
-    fn into_arg_list(self) -> Self::Output {
-        [
-            self.0.into(),
-            self.1.into(),
-            self.2.into(),
-            self.3.into(),
-            self.4.into(),
-        ]
-    }
-}
-
-impl<A, B, C, D, E, F> ArgList for (A, B, C, D, E, F)
-where
-    A: Into<Value>,
-    B: Into<Value>,
-    C: Into<Value>,
-    D: Into<Value>,
-    E: Into<Value>,
-    F: Into<Value>,
-{
-    type Output = [Value; 6];
-
-    fn into_arg_list(self) -> Self::Output {
-        [
-            self.0.into(),
-            self.1.into(),
-            self.2.into(),
-            self.3.into(),
-            self.4.into(),
-            self.5.into(),
-        ]
-    }
-}
-
-impl<A, B, C, D, E, F, G> ArgList for (A, B, C, D, E, F, G)
-where
-    A: Into<Value>,
-    B: Into<Value>,
-    C: Into<Value>,
-    D: Into<Value>,
-    E: Into<Value>,
-    F: Into<Value>,
-    G: Into<Value>,
-{
-    type Output = [Value; 7];
-
-    fn into_arg_list(self) -> Self::Output {
-        [
-            self.0.into(),
-            self.1.into(),
-            self.2.into(),
-            self.3.into(),
-            self.4.into(),
-            self.5.into(),
-            self.6.into(),
-        ]
-    }
-}
-
-impl<A, B, C, D, E, F, G, H> ArgList for (A, B, C, D, E, F, G, H)
-where
-    A: Into<Value>,
-    B: Into<Value>,
-    C: Into<Value>,
-    D: Into<Value>,
-    E: Into<Value>,
-    F: Into<Value>,
-    G: Into<Value>,
-    H: Into<Value>,
-{
-    type Output = [Value; 8];
-
-    fn into_arg_list(self) -> Self::Output {
-        [
-            self.0.into(),
-            self.1.into(),
-            self.2.into(),
-            self.3.into(),
-            self.4.into(),
-            self.5.into(),
-            self.6.into(),
-            self.7.into(),
-        ]
-    }
-}
-
-impl<A, B, C, D, E, F, G, H, I> ArgList for (A, B, C, D, E, F, G, H, I)
-where
-    A: Into<Value>,
-    B: Into<Value>,
-    C: Into<Value>,
-    D: Into<Value>,
-    E: Into<Value>,
-    F: Into<Value>,
-    G: Into<Value>,
-    H: Into<Value>,
-    I: Into<Value>,
-{
-    type Output = [Value; 9];
-
-    fn into_arg_list(self) -> Self::Output {
-        [
-            self.0.into(),
-            self.1.into(),
-            self.2.into(),
-            self.3.into(),
-            self.4.into(),
-            self.5.into(),
-            self.6.into(),
-            self.7.into(),
-            self.8.into(),
-        ]
-    }
-}
-
-impl<A, B, C, D, E, F, G, H, I, J> ArgList for (A, B, C, D, E, F, G, H, I, J)
-where
-    A: Into<Value>,
-    B: Into<Value>,
-    C: Into<Value>,
-    D: Into<Value>,
-    E: Into<Value>,
-    F: Into<Value>,
-    G: Into<Value>,
-    H: Into<Value>,
-    I: Into<Value>,
-    J: Into<Value>,
-{
-    type Output = [Value; 10];
-
-    fn into_arg_list(self) -> Self::Output {
-        [
-            self.0.into(),
-            self.1.into(),
-            self.2.into(),
-            self.3.into(),
-            self.4.into(),
-            self.5.into(),
-            self.6.into(),
-            self.7.into(),
-            self.8.into(),
-            self.9.into(),
-        ]
-    }
-}
-
-impl<A, B, C, D, E, F, G, H, I, J, K> ArgList for (A, B, C, D, E, F, G, H, I, J, K)
-where
-    A: Into<Value>,
-    B: Into<Value>,
-    C: Into<Value>,
-    D: Into<Value>,
-    E: Into<Value>,
-    F: Into<Value>,
-    G: Into<Value>,
-    H: Into<Value>,
-    I: Into<Value>,
-    J: Into<Value>,
-    K: Into<Value>,
-{
-    type Output = [Value; 11];
-
-    fn into_arg_list(self) -> Self::Output {
-        [
-            self.0.into(),
-            self.1.into(),
-            self.2.into(),
-            self.3.into(),
-            self.4.into(),
-            self.5.into(),
-            self.6.into(),
-            self.7.into(),
-            self.8.into(),
-            self.9.into(),
-            self.10.into(),
-        ]
-    }
+            fn into_arg_list(self) -> Self::Output {
+                [$(self.$i.into()),*]
+            }
+        }
+    };
 }
 
-impl<A, B, C, D, E, F, G, H, I, J, K, L> ArgList for (A, B, C, D, E, F, G, H, I, J, K, L)
-where
-    A: Into<Value>,
-    B: Into<Value>,
-    C: Into<Value>,
-    D: Into<Value>,
-    E: Into<Value>,
-    F: Into<Value>,
-    G: Into<Value>,
-    H: Into<Value>,
-    I: Into<Value>,
-    J: Into<Value>,
-    K: Into<Value>,
-    L: Into<Value>,
-{
-    type Output = [Value; 12];
-
-    fn into_arg_list(self) -> Self::Output {
-        [
-            self.0.into(),
-            self.1.into(),
-            self.2.into(),
-            self.3.into(),
-            self.4.into(),
-            self.5.into(),
-            self.6.into(),
-            self.7.into(),
-            self.8.into(),
-            self.9.into(),
-            self.10.into(),
-            self.11.into(),
-        ]
-    }
-}
+for_each_tuple!(impl_arg_list_tuple);
 
 impl<const N: usize> ArgList for [Value; N] {
     type Output = [Value; N];
@@ -4,6 +4,7 @@ use std::{
     borrow::Cow,
     ffi::CStr,
     fmt,
+    marker::PhantomData,
     mem::transmute,
     num::NonZeroUsize,
     ops::{Deref, DerefMut},
@@ -68,14 +69,17 @@ macro_rules! debug_assert_value {
 }
 
 /// Ruby's `VALUE` type, which can represent any Ruby object.
+///
+/// `Value` is neither [`Send`] nor [`Sync`], Ruby objects must only be
+/// accessed from a Ruby thread while holding the GVL.
 #[derive(Clone, Copy)]
 #[repr(transparent)]
-pub struct Value(VALUE);
+pub struct Value(VALUE, PhantomData<*mut RBasic>);
 
 impl Value {
     #[inline]
     pub(crate) const fn new(val: VALUE) -> Self {
-        Self(val)
+        Self(val, PhantomData)
     }
 
     #[inline]
@@ -889,6 +893,12 @@ impl From<i64> for Value {
     }
 }
 
+impl From<i128> for Value {
+    fn from(value: i128) -> Self {
+        Integer::from_i128(value).into()
+    }
+}
+
 impl From<isize> for Value {
     fn from(value: isize) -> Self {
         Integer::from_i64(value as i64).into()
@@ -919,6 +929,12 @@ impl From<u64> for Value {
     }
 }
 
+impl From<u128> for Value {
+    fn from(value: u128) -> Self {
+        Integer::from_u128(value).into()
+    }
+}
+
 impl From<usize> for Value {
     fn from(value: usize) -> Self {
         Integer::from_u64(value as u64).into()
@@ -946,12 +962,15 @@ impl TryConvert for Value {
 
 #[derive(Clone, Copy)]
 #[repr(transparent)]
-pub(crate) struct NonZeroValue(NonZeroUsize);
+pub(crate) struct NonZeroValue(NonZeroUsize, PhantomData<*mut RBasic>);
 
 impl NonZeroValue {
     #[inline]
     pub(crate) const unsafe fn new_unchecked(val: Value) -> Self {
-        Self(NonZeroUsize::new_unchecked(val.as_rb_value() as usize))
+        Self(
+            NonZeroUsize::new_unchecked(val.as_rb_value() as usize),
+            PhantomData,
+        )
     }
 
     pub(crate) const fn get(self) -> Value {
@@ -0,0 +1,99 @@
+use magnus::{eval, RArray, Value};
+
+macro_rules! rb_assert {
+    ($s:literal) => {
+        assert!(magnus::eval::<bool>($s).unwrap())
+    };
+    ($s:literal, $($rest:tt)*) => {
+        let result: bool = magnus::eval!($s, $($rest)*).unwrap();
+        assert!(result)
+    };
+}
+
+#[test]
+fn it_modifies_arrays() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let ary: RArray = eval("[1, 2, 3]").unwrap();
+    ary.insert(0, 0).unwrap();
+    ary.insert(-1, 4).unwrap();
+    rb_assert!("ary == [0, 1, 2, 3, 4]", ary);
+
+    ary.push(1).unwrap();
+    ary.delete(1).unwrap();
+    rb_assert!("ary == [0, 2, 3, 4]", ary);
+    assert_eq!(ary.delete_at::<i64>(-1).unwrap(), 4);
+    assert_eq!(ary.delete_at::<Option<i64>>(10).unwrap(), None);
+    rb_assert!("ary == [0, 2, 3]", ary);
+
+    ary.concat(eval("[5, 6]").unwrap()).unwrap();
+    rb_assert!("ary == [0, 2, 3, 5, 6]", ary);
+    ary.reverse().unwrap();
+    rb_assert!("ary == [6, 5, 3, 2, 0]", ary);
+    ary.rotate(2).unwrap();
+    rb_assert!("ary == [3, 2, 0, 6, 5]", ary);
+    ary.sort().unwrap();
+    rb_assert!("ary == [0, 2, 3, 5, 6]", ary);
+    ary.sort_by(|a, b| {
+        let a = a.try_convert::<i64>().unwrap();
+        let b = b.try_convert::<i64>().unwrap();
+        b.cmp(&a)
+    })
+    .unwrap();
+    rb_assert!("ary == [6, 5, 3, 2, 0]", ary);
+
+    let dup = ary.dup();
+    ary.resize(2).unwrap();
+    rb_assert!("ary == [6, 5]", ary);
+    rb_assert!("dup == [6, 5, 3, 2, 0]", dup);
+    ary.resize(3).unwrap();
+    rb_assert!("ary == [6, 5, nil]", ary);
+    ary.clear().unwrap();
+    rb_assert!("ary.empty?", ary);
+
+    let alist: RArray = eval("[[:a, 1], [:b, 2]]").unwrap();
+    let pair: RArray = alist.assoc(magnus::Symbol::new("b")).unwrap();
+    rb_assert!("pair == [:b, 2]", pair);
+    assert!(alist
+        .assoc::<_, Option<RArray>>(magnus::Symbol::new("c"))
+        .unwrap()
+        .is_none());
+
+    let val: Value = eval("Struct.new(:to_ary).new([1, 2])").unwrap();
+    let converted = RArray::to_ary(val).unwrap().unwrap();
+    rb_assert!("converted == [1, 2]", converted);
+    assert!(RArray::to_ary(eval::<Value>("1").unwrap())
+        .unwrap()
+        .is_none());
+    assert!(RArray::to_ary(eval::<Value>("Struct.new(:to_ary).new(1)").unwrap()).is_err());
+
+    let mut ary: RArray = (1..=3).collect();
+    ary.extend(vec![4, 5]);
+    rb_assert!("ary == [1, 2, 3, 4, 5]", ary);
+    let doubled = ary
+        .into_iter()
+        .map(|v| v.try_convert::<i64>().unwrap() * 2)
+        .collect::<Vec<_>>();
+    assert_eq!(doubled, vec![2, 4, 6, 8, 10]);
+
+    let frozen: RArray = eval("[3, 1, 2].freeze").unwrap();
+    assert!(frozen.insert(0, 1).is_err());
+    assert!(frozen.delete(1).is_err());
+    assert!(frozen.delete(99).is_err());
+    assert!(frozen.delete_at::<Value>(0).is_err());
+    assert!(frozen.delete_at::<Option<Value>>(10).is_err());
+    assert!(frozen.concat(RArray::new()).is_err());
+    assert!(frozen.reverse().is_err());
+    assert!(frozen.rotate(1).is_err());
+    assert!(frozen.sort().is_err());
+    assert!(frozen.sort_by(|_, _| std::cmp::Ordering::Equal).is_err());
+    assert!(frozen.clear().is_err());
+    assert!(frozen.resize(1).is_err());
+    assert!(frozen.extend_from(vec![4]).is_err());
+    assert!(std::panic::catch_unwind(|| {
+        let mut frozen = frozen;
+        frozen.extend(vec![4]);
+    })
+    .is_err());
+    rb_assert!("frozen == [3, 1, 2]", frozen);
+}
@@ -0,0 +1,65 @@
+use magnus::{class, define_module, eval, prelude::*, RClass, Value};
+
+macro_rules! rb_assert {
+    ($s:literal) => {
+        assert!(magnus::eval::<bool>($s).unwrap())
+    };
+    ($s:literal, $($rest:tt)*) => {
+        let result: bool = magnus::eval!($s, $($rest)*).unwrap();
+        assert!(result)
+    };
+}
+
+#[test]
+fn it_introspects_classes() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    eval::<Value>(
+        r#"
+        class Base
+          def self.inherited(subclass)
+            (@subclasses ||= []) << subclass.name
+          end
+
+          def base_method
+          end
+        end
+        "#,
+    )
+    .unwrap();
+    let base: RClass = eval("Base").unwrap();
+
+    let outer = define_module("Outer").unwrap();
+    let class = RClass::new_named(outer, "Derived", base).unwrap();
+    assert_eq!(class.name().unwrap(), "Outer::Derived");
+    rb_assert!(r#"Base.instance_variable_get(:@subclasses) == ["Outer::Derived"]"#);
+    assert!(class.superclass().unwrap().unwrap().equal(base).unwrap());
+    assert!(class::basic_object().superclass().unwrap().is_none());
+
+    let anonymous = RClass::new(base).unwrap();
+    assert!(anonymous.name().is_none());
+
+    let ancestors = class.ancestors();
+    rb_assert!(
+        "ancestors.first(3) == [Outer::Derived, Base, Object]",
+        ancestors
+    );
+
+    eval::<Value>("class Outer::Derived; def derived_method; end; end").unwrap();
+    rb_assert!(
+        "methods == [:derived_method]",
+        methods = class.instance_methods(false)
+    );
+    rb_assert!(
+        "methods.include?(:base_method) && methods.include?(:derived_method)",
+        methods = class.instance_methods(true)
+    );
+
+    assert!(!class.is_singleton());
+    let obj: Value = eval("Object.new").unwrap();
+    let singleton = RClass::singleton_class_of(obj).unwrap();
+    assert!(singleton.is_singleton());
+    assert!(singleton.attached_object().unwrap().equal(&obj).unwrap());
+    assert!(class.attached_object().is_err());
+    assert!(RClass::singleton_class_of(eval::<Value>(":sym").unwrap()).is_err());
+}
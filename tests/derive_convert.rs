@@ -0,0 +1,121 @@
+use magnus::{embed::init, eval, Error, IntoValue, TryConvert, Value};
+
+macro_rules! rb_assert {
+    ($s:literal) => {
+        assert!(magnus::eval::<bool>($s).unwrap())
+    };
+    ($s:literal, $($rest:tt)*) => {
+        let result: bool = magnus::eval!($s, $($rest)*).unwrap();
+        assert!(result)
+    };
+}
+
+#[derive(Debug, PartialEq, TryConvert, IntoValue)]
+enum Mode {
+    Fast,
+    #[magnus(rename = "careful")]
+    Safe,
+}
+
+#[derive(Debug, PartialEq, TryConvert, IntoValue)]
+#[magnus(tag = "kind")]
+enum Shape {
+    Circle { radius: f64 },
+    Square { side: f64 },
+}
+
+#[derive(Debug, PartialEq, TryConvert, IntoValue)]
+struct Point(i64, i64);
+
+#[derive(Debug, PartialEq, TryConvert, IntoValue)]
+struct Database {
+    host: String,
+    port: u16,
+}
+
+#[derive(Debug, PartialEq, TryConvert, IntoValue)]
+struct Config {
+    #[magnus(rename = "max_retries")]
+    retries: u32,
+    #[magnus(default)]
+    timeout: f64,
+    #[magnus(optional)]
+    name: Option<String>,
+    mode: Mode,
+    database: Database,
+    origin: Point,
+    shape: Shape,
+}
+
+fn error_message(e: Error) -> String {
+    match e {
+        Error::Error(_, msg) => msg.into_owned(),
+        e => e.to_string(),
+    }
+}
+
+#[test]
+fn it_derives_conversions() {
+    let _cleanup = unsafe { init() };
+
+    let config: Config = eval(
+        r#"{
+          max_retries: 3,
+          "mode" => :careful,
+          database: {host: "localhost", "port" => 5432},
+          origin: [1, 2],
+          shape: {kind: :circle, radius: 1.5},
+        }"#,
+    )
+    .unwrap();
+    assert_eq!(
+        config,
+        Config {
+            retries: 3,
+            timeout: 0.0,
+            name: None,
+            mode: Mode::Safe,
+            database: Database {
+                host: "localhost".to_owned(),
+                port: 5432,
+            },
+            origin: Point(1, 2),
+            shape: Shape::Circle { radius: 1.5 },
+        }
+    );
+
+    let val = Value::from(config);
+    rb_assert!(
+        r#"val == {
+          max_retries: 3,
+          timeout: 0.0,
+          mode: :careful,
+          database: {host: "localhost", port: 5432},
+          origin: [1, 2],
+          shape: {kind: :circle, radius: 1.5},
+        }"#,
+        val
+    );
+
+    let err = eval::<Config>(r#"{max_retries: "three"}"#).unwrap_err();
+    assert_eq!(
+        error_message(err),
+        "config[:max_retries]: no implicit conversion of String into Integer"
+    );
+
+    let err = eval::<Config>(r#"{max_retries: 1, mode: :fast, database: {host: "localhost"}}"#)
+        .unwrap_err();
+    assert_eq!(error_message(err), "config[:database][:port]: missing key");
+
+    let err = eval::<Config>(r#"{max_retries: 1, mode: :slow}"#).unwrap_err();
+    assert_eq!(
+        error_message(err),
+        "config[:mode]: unknown variant :slow, expected one of :fast, :careful"
+    );
+
+    let err = eval::<Point>("[1, nil]").unwrap_err();
+    assert_eq!(
+        error_message(err),
+        "point[1]: no implicit conversion from nil to integer"
+    );
+}
@@ -0,0 +1,41 @@
+use magnus::{Enumerator, RString};
+
+macro_rules! rb_assert {
+    ($s:literal) => {
+        assert!(magnus::eval::<bool>($s).unwrap())
+    };
+    ($s:literal, $($rest:tt)*) => {
+        let result: bool = magnus::eval!($s, $($rest)*).unwrap();
+        assert!(result)
+    };
+}
+
+#[test]
+fn it_makes_an_enumerator_from_an_iterator() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let s = RString::new("captured");
+    let e = Enumerator::from_iterator((0..3).map(move |i| format!("{} {}", s, i)));
+    rb_assert!("GC.start; e.size == 3", e);
+    rb_assert!(r#"e.peek == "captured 0""#, e);
+    rb_assert!(r#"e.next == "captured 0""#, e);
+    rb_assert!(r#"e.next_values == ["captured 1"]"#, e);
+    rb_assert!("e.size == 1", e);
+    rb_assert!(r#"e.map(&:upcase) == ["CAPTURED 2"]"#, e);
+    rb_assert!("begin; e.next; rescue StopIteration; true; end", e);
+
+    let e = Enumerator::from_iterator((1..).filter(|i| i % 3 == 0));
+    rb_assert!("e.size.nil? && e.first(3) == [3, 6, 9] && e.next == 12", e);
+
+    let e2 = Enumerator::from_iterator(0..1);
+    rb_assert!(
+        "e.is_a?(Enumerator) && e.class == e2.class && e.singleton_methods.empty?",
+        e,
+        e2
+    );
+    rb_assert!(
+        r#"begin; e.rewind; false; rescue TypeError => err; err.message == "can't rewind"; end"#,
+        e
+    );
+    rb_assert!("[1, 2, 3].zip(e2) == [[1, 0], [2, nil], [3, nil]]", e2);
+}
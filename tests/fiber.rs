@@ -0,0 +1,37 @@
+use magnus::{eval, exception, Error, Fiber, RArray, Value};
+
+#[test]
+fn it_resumes_fibers() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let ary = RArray::new();
+    let fib = Fiber::new(move |args| {
+        let mut n: i64 = args[0].try_convert()?;
+        while n < 100 {
+            ary.push(n)?;
+            n += Fiber::yield_values::<_, i64>((n,))?;
+        }
+        Ok::<_, Error>(ary)
+    })
+    .unwrap();
+    eval::<Value>("GC.start").unwrap();
+
+    assert_eq!(fib.resume::<_, i64>((1,)).unwrap(), 1);
+    assert_eq!(fib.resume::<_, i64>((10,)).unwrap(), 11);
+    assert!(fib.is_alive());
+    let res: RArray = fib.resume((100,)).unwrap();
+    assert_eq!(res.to_vec::<i64>().unwrap(), vec![1, 11]);
+    assert!(!fib.is_alive());
+    assert!(fib.resume::<_, Value>(()).is_err());
+
+    let fib = Fiber::new(|_| -> Result<(), Error> {
+        Err(Error::new(exception::arg_error(), "bad input"))
+    })
+    .unwrap();
+    let res: bool = eval!("Fiber === fib && fib.alive?", fib).unwrap();
+    assert!(res);
+    assert!(fib
+        .resume::<_, Value>(())
+        .unwrap_err()
+        .is_kind_of(exception::arg_error()));
+}
@@ -0,0 +1,94 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    convert::TryFrom,
+};
+
+use indexmap::IndexMap;
+use magnus::{block::Proc, eval, RHash, Value};
+
+macro_rules! rb_assert {
+    ($s:literal) => {
+        assert!(magnus::eval::<bool>($s).unwrap())
+    };
+    ($s:literal, $($rest:tt)*) => {
+        let result: bool = magnus::eval!($s, $($rest)*).unwrap();
+        assert!(result)
+    };
+}
+
+#[test]
+fn it_manipulates_hashes() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let hash: RHash = eval(r#"{"c" => 3, "a" => 1, "b" => 2}"#).unwrap();
+    let pairs = hash
+        .iter::<String, i64>()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(
+        pairs,
+        vec![
+            (String::from("c"), 3),
+            (String::from("a"), 1),
+            (String::from("b"), 2)
+        ]
+    );
+    assert!(hash.iter::<i64, i64>().next().unwrap().is_err());
+    rb_assert!(r#"keys == ["c", "a", "b"]"#, keys = hash.keys());
+    rb_assert!("values == [3, 1, 2]", values = hash.values());
+
+    let index_map: IndexMap<String, i64> = eval(r#"{"c" => 3, "a" => 1}"#).unwrap();
+    assert_eq!(index_map.keys().collect::<Vec<_>>(), vec!["c", "a"]);
+    rb_assert!(r#"h.to_a == [["c", 3], ["a", 1]]"#, h = index_map);
+    let btree: BTreeMap<String, i64> = eval(r#"{"c" => 3, "a" => 1}"#).unwrap();
+    assert_eq!(btree.keys().collect::<Vec<_>>(), vec!["a", "c"]);
+    rb_assert!(r#"h.to_a == [["a", 1], ["c", 3]]"#, h = btree);
+
+    assert_eq!(hash.delete::<_, Option<i64>>("a").unwrap(), Some(1));
+    assert_eq!(hash.delete::<_, Option<i64>>("a").unwrap(), None);
+    let other: RHash = eval(r#"{"b" => 20, "d" => 4}"#).unwrap();
+    let merged = hash.merge(other).unwrap();
+    rb_assert!(r#"merged == {"c" => 3, "b" => 20, "d" => 4}"#, merged);
+    rb_assert!(r#"hash == {"c" => 3, "b" => 2}"#, hash);
+    hash.update(other).unwrap();
+    rb_assert!("hash == merged", hash, merged);
+    hash.clear().unwrap();
+    assert!(hash.is_empty());
+    let frozen: RHash = eval("{a: 1}.freeze").unwrap();
+    assert!(frozen.clear().is_err());
+    assert!(frozen.delete::<_, Value>(1).is_err());
+    assert!(frozen.extend_from(vec![("b", 2)]).is_err());
+
+    let mut extended = vec![("x", 1)].into_iter().collect::<RHash>();
+    extended.extend(vec![("y", 2), ("x", 3)]);
+    rb_assert!(r#"h == {"x" => 3, "y" => 2}"#, h = extended);
+    let with_capacity = RHash::with_capacity(100);
+    assert!(with_capacity.is_empty());
+
+    let ident = RHash::new();
+    ident.compare_by_identity().unwrap();
+    assert!(ident.is_compare_by_identity());
+    assert!(!hash.is_compare_by_identity());
+    ident.aset("key", 1).unwrap();
+    ident.aset("key", 2).unwrap();
+    assert_eq!(ident.len(), 2);
+
+    let defaults = RHash::new();
+    assert_eq!(defaults.default_value::<Option<i64>>().unwrap(), None);
+    defaults.set_default_value(7).unwrap();
+    assert_eq!(defaults.aref::<_, i64>("missing").unwrap(), 7);
+    assert_eq!(defaults.default_value::<i64>().unwrap(), 7);
+    let proc: Proc = eval("proc { |h, k| k.to_s * 2 }").unwrap();
+    defaults.set_default_proc(proc).unwrap();
+    assert_eq!(defaults.aref::<_, String>(4).unwrap(), "44");
+    assert_eq!(defaults.default_value::<Option<i64>>().unwrap(), None);
+
+    let set: HashSet<i64> = vec![1, 2, 3].into_iter().collect();
+    rb_assert!(
+        "s == Set[1, 2, 3]",
+        s = Value::try_from(set.clone()).unwrap()
+    );
+    assert_eq!(eval::<HashSet<i64>>("Set[3, 2, 1]").unwrap(), set);
+    assert_eq!(eval::<HashSet<i64>>("[1, 2, 3, 3]").unwrap(), set);
+    assert!(eval::<HashSet<i64>>("1").is_err());
+}
@@ -0,0 +1,76 @@
+use std::io::{ErrorKind, Read, Seek, SeekFrom, Write};
+
+use magnus::{eval, RFile};
+
+macro_rules! rb_assert {
+    ($s:literal) => {
+        assert!(magnus::eval::<bool>($s).unwrap())
+    };
+    ($s:literal, $($rest:tt)*) => {
+        let result: bool = magnus::eval!($s, $($rest)*).unwrap();
+        assert!(result)
+    };
+}
+
+#[test]
+fn it_reads_and_writes_ruby_io() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let mut file: RFile = eval(r#"require "tempfile"; $file = Tempfile.create("magnus")"#).unwrap();
+    eval::<magnus::Value>(r#"$file.write("from ruby, ")"#).unwrap();
+    write!(file, "from rust").unwrap();
+    file.flush().unwrap();
+    rb_assert!(r#"File.read($file.path) == "from ruby, from rust""#);
+
+    assert_eq!(file.seek(SeekFrom::Start(5)).unwrap(), 5);
+    let mut buf = [0; 4];
+    file.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"ruby");
+    rb_assert!(r#"$file.read(2) == ", ""#);
+    assert_eq!(file.seek(SeekFrom::Current(-2)).unwrap(), 9);
+    assert_eq!(file.seek(SeekFrom::End(-4)).unwrap(), 16);
+    let mut rest = String::new();
+    file.read_to_string(&mut rest).unwrap();
+    assert_eq!(rest, "rust");
+    assert_eq!(file.read(&mut buf).unwrap(), 0);
+
+    let err = file.seek(SeekFrom::Current(-100)).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+
+    let fileno: i32 = eval("$file.fileno").unwrap();
+    assert_eq!(file.fileno().unwrap(), fileno);
+
+    eval::<magnus::Value>("$file.close; File.unlink($file.path)").unwrap();
+    assert!(file.fileno().is_err());
+    assert!(write!(file, "closed").is_err());
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::{
+            io::{AsRawFd, IntoRawFd},
+            net::UnixStream,
+        };
+
+        let (a, mut b) = UnixStream::pair().unwrap();
+        let a_fd = a.into_raw_fd();
+        let mut a = unsafe { RFile::from_raw_fd(a_fd, "r+").unwrap() };
+        assert_eq!(a.as_raw_fd(), a_fd);
+        rb_assert!(r#"a.sync = false; a.write("buffered") == 8"#, a);
+        // fileno flushes Ruby's buffer, so the data is visible to b
+        a.fileno().unwrap();
+        let mut buf = [0; 8];
+        b.read_exact(&mut buf).unwrap();
+        assert_eq!(&buf, b"buffered");
+        b.write_all(b"reply").unwrap();
+        // returns what is available rather than waiting to fill the buffer
+        let mut partial = [0; 16];
+        assert_eq!(a.read(&mut partial).unwrap(), 5);
+        assert_eq!(&partial[..5], b"reply");
+        b.write_all(b"again").unwrap();
+        drop(b);
+        let mut reply = String::new();
+        a.read_to_string(&mut reply).unwrap();
+        assert_eq!(reply, "again");
+        assert!(unsafe { RFile::from_raw_fd(-1, "r") }.is_err());
+    }
+}
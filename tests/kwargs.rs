@@ -0,0 +1,76 @@
+use magnus::{
+    define_global_function, function, scan_args::scan_args, Error, KwArgs, RArray, RHash, Value,
+};
+
+macro_rules! rb_assert {
+    ($s:literal) => {
+        assert!(magnus::eval::<bool>($s).unwrap())
+    };
+    ($s:literal, $($rest:tt)*) => {
+        let result: bool = magnus::eval!($s, $($rest)*).unwrap();
+        assert!(result)
+    };
+}
+
+#[derive(KwArgs)]
+struct Search {
+    query: String,
+    limit: Option<usize>,
+    #[magnus(rename = "type")]
+    kind: Option<String>,
+}
+
+#[derive(KwArgs)]
+struct Render {
+    template: String,
+    #[magnus(rest)]
+    locals: RHash,
+}
+
+fn search(args: &[Value]) -> Result<RArray, Error> {
+    let args = scan_args::<(), (), (), (), Search, ()>(args)?;
+    let Search { query, limit, kind } = args.keywords;
+    let res = RArray::new();
+    res.push(query)?;
+    res.push(limit)?;
+    res.push(kind)?;
+    Ok(res)
+}
+
+fn render(args: &[Value]) -> Result<RArray, Error> {
+    let args = scan_args::<(), (), (), (), Render, ()>(args)?;
+    let Render { template, locals } = args.keywords;
+    let res = RArray::new();
+    res.push(template)?;
+    res.push(locals)?;
+    Ok(res)
+}
+
+#[test]
+fn it_derives_kwargs() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    define_global_function("search", function!(search, -1));
+    define_global_function("render", function!(render, -1));
+
+    rb_assert!(r#"search(query: "rust") == ["rust", nil, nil]"#);
+    rb_assert!(r#"search(query: "rust", limit: 5, type: "repo") == ["rust", 5, "repo"]"#);
+    rb_assert!(
+        r#"begin; search(query: "rust", limt: 5); rescue ArgumentError => e; e.message.include?("limt"); end"#
+    );
+    rb_assert!(
+        r#"begin; search(limit: 5); rescue ArgumentError => e; e.message.include?("query"); end"#
+    );
+    rb_assert!(
+        r#"begin; search(query: 1); rescue TypeError => e; e.message.start_with?("query: "); end"#
+    );
+
+    rb_assert!(r#"render(template: "index") == ["index", {}]"#);
+    rb_assert!(r#"render(template: "index", title: "Home") == ["index", {title: "Home"}]"#);
+
+    let kw: RHash = magnus::eval(r#"{template: "show", id: 1}"#).unwrap();
+    let Render { template, locals } = magnus::scan_args::FromKwArgs::from_kwargs(kw).unwrap();
+    assert_eq!(template, "show");
+    assert_eq!(locals.len(), 1);
+    assert_eq!(kw.len(), 2);
+}
@@ -0,0 +1,107 @@
+#![allow(clippy::too_many_arguments, clippy::type_complexity)]
+
+use magnus::{class, define_global_function, function, method, prelude::*, Value};
+
+macro_rules! rb_assert {
+    ($s:literal) => {
+        assert!(magnus::eval::<bool>($s).unwrap())
+    };
+    ($s:literal, $($rest:tt)*) => {
+        let result: bool = magnus::eval!($s, $($rest)*).unwrap();
+        assert!(result)
+    };
+}
+
+fn sum15(
+    rb_self: i64,
+    a: i64,
+    b: i64,
+    c: i64,
+    d: i64,
+    e: i64,
+    f: i64,
+    g: i64,
+    h: i64,
+    i: i64,
+    j: i64,
+    k: i64,
+    l: i64,
+    m: i64,
+    n: i64,
+    o: i64,
+) -> i64 {
+    rb_self + a + b + c + d + e + f + g + h + i + j + k + l + m + n + o
+}
+
+fn sum20(
+    a: i64,
+    b: i64,
+    c: i64,
+    d: i64,
+    e: i64,
+    f: i64,
+    g: i64,
+    h: i64,
+    i: i64,
+    j: i64,
+    k: i64,
+    l: i64,
+    m: i64,
+    n: i64,
+    o: i64,
+    p: i64,
+    q: i64,
+    r: i64,
+    s: i64,
+    t: i64,
+) -> i64 {
+    a + b + c + d + e + f + g + h + i + j + k + l + m + n + o + p + q + r + s + t
+}
+
+#[test]
+fn it_defines_methods_with_many_arguments() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    class::integer().define_method("sum15", method!(sum15, 15));
+    define_global_function("sum20", function!(sum20, 20));
+
+    rb_assert!("1.sum15(*[1] * 15) == 16");
+    rb_assert!("1.method(:sum15).arity == 15");
+    rb_assert!("sum20(*(1..20)) == 210");
+    rb_assert!("method(:sum20).arity == -1");
+    rb_assert!(
+        r#"begin; sum20(1, 2); rescue ArgumentError => e; e.message == "wrong number of arguments (given 2, expected 20)"; end"#
+    );
+    // an arity not matching the function is caught when defined
+    assert!(std::panic::catch_unwind(|| function!(sum20, 19)).is_err());
+    assert!(std::panic::catch_unwind(|| method!(sum15, 16)).is_err());
+
+    let tuple: (
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+    ) = magnus::eval("(1..14).to_a").unwrap();
+    assert_eq!(tuple.13, 14);
+    let res: Value = magnus::eval!("val == (1..14).to_a", val = tuple).unwrap();
+    assert!(res.to_bool());
+    let res: i64 = class::object()
+        .funcall(
+            "sum20",
+            (
+                1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20,
+            ),
+        )
+        .unwrap();
+    assert_eq!(res, 210);
+}
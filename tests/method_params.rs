@@ -0,0 +1,71 @@
+use magnus::{
+    block::Proc, class, define_global_function, function, method, prelude::*, Error, RArray, RHash,
+    Value,
+};
+
+macro_rules! rb_assert {
+    ($s:literal) => {
+        assert!(magnus::eval::<bool>($s).unwrap())
+    };
+    ($s:literal, $($rest:tt)*) => {
+        let result: bool = magnus::eval!($s, $($rest)*).unwrap();
+        assert!(result)
+    };
+}
+
+fn everything(
+    a: i64,
+    b: Option<i64>,
+    rest: RArray,
+    c: i64,
+    kw: RHash,
+    block: Option<Proc>,
+) -> Result<RArray, Error> {
+    let res = RArray::new();
+    res.push(a)?;
+    res.push(b)?;
+    res.push(rest)?;
+    res.push(c)?;
+    res.push(kw)?;
+    res.push(match block {
+        Some(block) => block.call::<_, Value>(())?,
+        None => magnus::QNIL.into(),
+    })?;
+    Ok(res)
+}
+
+fn repeat(rb_self: String, times: Option<usize>) -> String {
+    rb_self.repeat(times.unwrap_or(2))
+}
+
+#[test]
+fn it_scans_declared_params() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    define_global_function(
+        "everything",
+        function!(everything, "a, b = nil, *rest, c, **kw, &block"),
+    );
+    class::string().define_method("repeat", method!(repeat, "times = nil"));
+
+    rb_assert!("everything(1, 2) == [1, nil, [], 2, {}, nil]");
+    rb_assert!("everything(1, 2, 3) == [1, 2, [], 3, {}, nil]");
+    rb_assert!("everything(1, 2, 3, 4, 5) == [1, 2, [3, 4], 5, {}, nil]");
+    rb_assert!("everything(1, 2, x: 3) { :blk } == [1, nil, [], 2, {x: 3}, :blk]");
+    rb_assert!(
+        r#"begin; everything(1); rescue ArgumentError => e; e.message == "wrong number of arguments (given 1, expected 2+)"; end"#
+    );
+    rb_assert!(r#"begin; everything("1", 2); rescue TypeError; true; end"#);
+
+    rb_assert!(r#""ab".repeat == "abab""#);
+    rb_assert!(r#""ab".repeat(3) == "ababab""#);
+    rb_assert!(
+        r#"begin; "ab".repeat(1, 2); rescue ArgumentError => e; e.message == "wrong number of arguments (given 2, expected 0..1)"; end"#
+    );
+
+    // a parameter list not matching the function is caught when defined
+    let res = std::panic::catch_unwind(|| function!(everything, "a, *rest, **kw, &block"));
+    assert!(res.is_err());
+    let res = std::panic::catch_unwind(|| method!(repeat, "a, *rest"));
+    assert!(res.is_err());
+}
@@ -0,0 +1,73 @@
+use magnus::{define_class, embed::init, method::DefineMethods, Value};
+
+macro_rules! rb_assert {
+    ($s:literal) => {
+        assert!(magnus::eval::<bool>($s).unwrap())
+    };
+    ($s:literal, $($rest:tt)*) => {
+        let result: bool = magnus::eval!($s, $($rest)*).unwrap();
+        assert!(result)
+    };
+}
+
+#[magnus::wrap(class = "Point", free_immediatly)]
+struct Point {
+    x: isize,
+    y: isize,
+}
+
+#[magnus::methods]
+impl Point {
+    pub fn new(x: isize, y: isize) -> Self {
+        Self { x, y }
+    }
+
+    #[magnus(singleton, name = "origin")]
+    pub fn zero() -> Self {
+        Self::new(0, 0)
+    }
+
+    pub fn x(&self) -> isize {
+        self.x
+    }
+
+    #[magnus(name = "x=")]
+    pub fn set_x(&mut self, val: isize) {
+        self.x = val;
+    }
+
+    pub fn y(&self) -> isize {
+        self.y
+    }
+
+    pub fn sum(&self, args: &[Value]) -> usize {
+        args.len()
+    }
+
+    #[magnus(private)]
+    pub fn secret(&self) -> isize {
+        self.x * self.y
+    }
+
+    #[allow(dead_code)]
+    fn not_bound(&self) -> isize {
+        self.x
+    }
+}
+
+#[test]
+fn it_defines_methods_from_impl() {
+    let _cleanup = unsafe { init() };
+
+    define_class("Point", Default::default()).unwrap();
+    Point::define_methods();
+
+    rb_assert!("Point.new(1, 2).x == 1");
+    rb_assert!("Point.new(1, 2).y == 2");
+    rb_assert!("Point.origin.x == 0");
+    rb_assert!("p = Point.new(1, 2); p.x = 5; p.x == 5");
+    rb_assert!("Point.new(1, 2).sum(1, 2, 3) == 3");
+    rb_assert!("Point.new(3, 4).send(:secret) == 12");
+    rb_assert!("Point.private_method_defined?(:secret)");
+    rb_assert!("!Point.method_defined?(:not_bound)");
+}
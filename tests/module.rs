@@ -0,0 +1,76 @@
+use magnus::{
+    define_module, eval, exception, module::Attr, prelude::*, RClass, RModule, RObject, Value,
+};
+
+macro_rules! rb_assert {
+    ($s:literal) => {
+        assert!(magnus::eval::<bool>($s).unwrap())
+    };
+    ($s:literal, $($rest:tt)*) => {
+        let result: bool = magnus::eval!($s, $($rest)*).unwrap();
+        assert!(result)
+    };
+}
+
+#[test]
+fn it_manipulates_modules() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let class = eval::<RClass>(
+        r#"
+        class Widget
+          def name
+            "widget"
+          end
+
+          def secret
+            "hidden"
+          end
+
+          self
+        end
+        "#,
+    )
+    .unwrap();
+
+    class.const_set("SIZE", 3).unwrap();
+    assert!(class.const_defined("SIZE"));
+    rb_assert!("Widget::SIZE == 3");
+    class.private_constant("SIZE").unwrap();
+    let err = eval::<Value>("Widget::SIZE").unwrap_err();
+    assert!(err.is_kind_of(exception::name_error()));
+
+    class.cvar_set("@@count", 1).unwrap();
+    assert!(class.cvar_defined("@@count"));
+    assert_eq!(class.cvar_get::<_, i64>("@@count").unwrap(), 1);
+
+    class.define_alias("title", "name").unwrap();
+    rb_assert!(r#"Widget.new.title == "widget""#);
+    assert!(class.define_alias("other", "missing").is_err());
+
+    class.define_attr("color", Attr::ReadWrite).unwrap();
+    class.define_attr("size", Attr::Read).unwrap();
+    rb_assert!(r#"w = Widget.new; w.color = "red"; w.color == "red""#);
+    rb_assert!("Widget.method_defined?(:size) && !Widget.method_defined?(:size=)");
+
+    class.undef_method("secret").unwrap();
+    rb_assert!("!Widget.new.respond_to?(:secret)");
+    class.remove_method("title").unwrap();
+    rb_assert!("!Widget.method_defined?(:title)");
+    assert!(class.remove_method("title").is_err());
+
+    let loud = eval::<RModule>("module Loud; def name; super.upcase; end; self; end").unwrap();
+    class.prepend_module(loud).unwrap();
+    rb_assert!(r#"Widget.new.name == "WIDGET""#);
+
+    let helpers = define_module("Helpers").unwrap();
+    eval::<Value>("module Helpers; def helper; :help; end; end").unwrap();
+    helpers.module_function("helper").unwrap();
+    rb_assert!("Helpers.helper == :help");
+    class.include_module(helpers).unwrap();
+    rb_assert!("Widget.include?(Helpers)");
+
+    let obj = eval::<RObject>("Object.new").unwrap();
+    obj.extend_object(helpers).unwrap();
+    rb_assert!("obj.singleton_class.include?(Helpers)", obj);
+}
@@ -0,0 +1,96 @@
+use magnus::{eval, Float, Integer, RBignum};
+
+macro_rules! rb_assert {
+    ($s:literal) => {
+        assert!(magnus::eval::<bool>($s).unwrap())
+    };
+    ($s:literal, $($rest:tt)*) => {
+        let result: bool = magnus::eval!($s, $($rest)*).unwrap();
+        assert!(result)
+    };
+}
+
+#[test]
+fn it_does_arithmetic_and_comparisons() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let a = Integer::from_i64(7);
+    let b = Integer::from_i64(-2);
+    assert_eq!(a + b, 5);
+    assert_eq!(a - b, 9);
+    assert_eq!(a * b, -14);
+    assert_eq!(a / b, -4);
+    assert_eq!(a % b, -1);
+    assert_eq!(-a, -7);
+    assert_eq!(!a, -8);
+    assert_eq!(a & 3, 3);
+    assert_eq!(a | 8, 15);
+    assert_eq!(a ^ 1, 6);
+    assert_eq!(a << 2, 28);
+    assert_eq!(a >> 1, 3);
+    assert_eq!(a.pow(3).unwrap(), 343);
+    assert!(a.checked_div(Integer::from_i64(0)).is_err());
+    assert!(a.checked_rem(Integer::from_i64(0)).is_err());
+
+    let max = Integer::from_i64(4611686018427387903);
+    let big = max + 1;
+    assert!(RBignum::from_value(*big).is_some());
+    rb_assert!("big == 4611686018427387904", big);
+    assert_eq!(big - 1, max);
+    rb_assert!(
+        "res == 4611686018427387903 * 4611686018427387903",
+        res = max * max
+    );
+    rb_assert!(
+        "res == -(2 ** 64) / 3",
+        res = -(Integer::from_i64(1) << 64) / 3
+    );
+    rb_assert!(
+        "res == (2 ** 64) % -3",
+        res = (Integer::from_i64(1) << 64) % -3
+    );
+    rb_assert!("res == ~(2 ** 64)", res = !(Integer::from_i64(1) << 64));
+    rb_assert!(
+        "res == 2 ** 100 >> 90",
+        res = Integer::from_i64(2).pow(100).unwrap() >> 90
+    );
+    let bignum: RBignum = eval("2 ** 70").unwrap();
+    rb_assert!("res == 2 ** 70 + 1", res = bignum + 1);
+    rb_assert!(
+        "res == 2 ** 70 & 2 ** 70",
+        res = bignum & Integer::from(bignum)
+    );
+
+    let x = Float::from_f64(7.5);
+    assert_eq!(x + 0.5, 8.0);
+    assert_eq!(x - Float::from_f64(0.5), 7.0);
+    assert_eq!(x * 2.0, 15.0);
+    assert_eq!(x / 2.0, 3.75);
+    assert_eq!(Float::from_f64(-7.0) % 2.0, 1.0);
+    assert_eq!(-x, -7.5);
+
+    let small = Integer::from_i64(-3);
+    let big: Integer = eval("2 ** 70").unwrap();
+    let neg_big: Integer = eval("-(2 ** 70)").unwrap();
+    assert!(small < big);
+    assert!(neg_big < small);
+    assert!(neg_big < big);
+    assert_eq!(big, eval::<Integer>("2 ** 70").unwrap());
+    assert!(small == -3_i32);
+    assert!(small < 0_u8);
+    assert!(big > u64::MAX);
+    assert_eq!(small.cmp(&big), std::cmp::Ordering::Less);
+
+    let half = Float::from_f64(2.5);
+    assert!(Integer::from_i64(2) < half);
+    assert!(Integer::from_i64(3) > half);
+    assert!(Integer::from_i64(2) == Float::from_f64(2.0));
+    assert!(half > Integer::from_i64(2));
+    assert!(big > 1e20);
+    assert!(big < 1e22);
+    assert!(Integer::from_i64(4611686018427387903) < 4611686018427387904.0);
+    assert!(Integer::from_i64(1).partial_cmp(&f64::NAN).is_none());
+    assert!(big.partial_cmp(&f64::NAN).is_none());
+    assert!(half < 3.0);
+    assert!(half == Float::from_f64(2.5));
+}
@@ -0,0 +1,59 @@
+use magnus::{block::Proc, exception, Error, RString, Value};
+
+macro_rules! rb_assert {
+    ($s:literal) => {
+        assert!(magnus::eval::<bool>($s).unwrap())
+    };
+    ($s:literal, $($rest:tt)*) => {
+        let result: bool = magnus::eval!($s, $($rest)*).unwrap();
+        assert!(result)
+    };
+}
+
+#[test]
+fn it_makes_procs_from_rust() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let double = Proc::new(|args, _block| {
+        let i: i64 = args.first().copied().unwrap_or_default().try_convert()?;
+        Ok::<_, Error>(i * 2)
+    });
+    rb_assert!("[1, 2, 3].map(&double) == [2, 4, 6]", double);
+    rb_assert!(
+        "begin; double.call(nil); rescue TypeError; true; end",
+        double
+    );
+
+    let prefix = RString::new("hello");
+    let greet = Proc::from_fn(move |args, block| {
+        let name: RString = args[0].try_convert()?;
+        let res = RString::new(&format!("{}, {}", prefix, name));
+        match block {
+            Some(block) => block.call((res,)),
+            None => Ok(Value::from(res)),
+        }
+    });
+    rb_assert!(r#"GC.start; greet.call("world") == "hello, world""#, greet);
+    rb_assert!(r#"greet.call("world", &:upcase) == "HELLO, WORLD""#, greet);
+    // calling back in to the same proc is refused rather than aliasing the
+    // closure, and the proc is usable again afterwards
+    let mut calls = 0;
+    let reenter = Proc::from_fn(move |args, _block| {
+        calls += 1;
+        match args.first().copied().and_then(Proc::from_value) {
+            Some(p) => p.call::<_, Value>((p,)).map(|_| calls),
+            None => Ok(calls),
+        }
+    });
+    rb_assert!(
+        r#"begin; reenter.call(reenter); false; rescue RuntimeError => e; e.message == "proc called recursively"; end"#,
+        reenter
+    );
+    rb_assert!("reenter.call == 2", reenter);
+
+    let fail = Proc::new(|_, _| -> Result<(), Error> {
+        Err(Error::new(exception::arg_error(), "bad input"))
+    });
+    let err = fail.call::<_, Value>(()).unwrap_err();
+    assert!(err.is_kind_of(exception::arg_error()));
+}
@@ -0,0 +1,72 @@
+use std::convert::TryFrom;
+
+use magnus::{eval, Integer, RComplex, RRational, Value};
+use num_bigint::BigInt;
+use num_complex::Complex;
+use num_rational::{BigRational, Ratio, Rational64};
+
+macro_rules! rb_assert {
+    ($s:literal) => {
+        assert!(magnus::eval::<bool>($s).unwrap())
+    };
+    ($s:literal, $($rest:tt)*) => {
+        let result: bool = magnus::eval!($s, $($rest)*).unwrap();
+        assert!(result)
+    };
+}
+
+#[test]
+fn it_converts_rationals_and_complexes() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let rational = RRational::new(Integer::from_i64(10), Integer::from_i64(-4)).unwrap();
+    rb_assert!("rational == -5/2r", rational);
+    assert_eq!(rational.numerator().to_i64().unwrap(), -5);
+    assert_eq!(rational.denominator().to_i64().unwrap(), 2);
+    assert_eq!(rational.to_f64(), -2.5);
+    assert!(RRational::new(Integer::from_i64(1), Integer::from_i64(0)).is_err());
+    assert_eq!(<(i64, i64)>::try_from(rational).unwrap(), (-5, 2));
+    rb_assert!("r == 1/3r", r = RRational::try_from((2, 6)).unwrap());
+
+    let ratio: Rational64 = eval("3/9r").unwrap();
+    assert_eq!(ratio, Rational64::new(1, 3));
+    rb_assert!(
+        "r == 7/4r",
+        r = RRational::try_from(Rational64::new(14, 8)).unwrap()
+    );
+    assert!(RRational::try_from(Rational64::new_raw(1, 0)).is_err());
+    assert!(RRational::try_from(Ratio::new_raw(2.5, 1.0)).is_err());
+    let big: BigRational = eval("(2 ** 100) / 3r").unwrap();
+    assert_eq!(
+        big,
+        BigRational::new(BigInt::from(2).pow(100), BigInt::from(3))
+    );
+    rb_assert!(
+        "r == (2 ** 100) / 3r",
+        r = RRational::try_from(big).unwrap()
+    );
+    assert!(eval::<Rational64>("1.5").is_err());
+    let ratios: Vec<Rational64> = eval("[1/2r, 3r]").unwrap();
+    assert_eq!(ratios, vec![Rational64::new(1, 2), Rational64::from(3)]);
+
+    let complex = RComplex::new(3, -4).unwrap();
+    rb_assert!("complex == Complex(3, -4)", complex);
+    assert_eq!(complex.real::<i64>().unwrap(), 3);
+    assert_eq!(complex.imag::<i64>().unwrap(), -4);
+    assert_eq!(complex.rect::<f64>().unwrap(), (3.0, -4.0));
+    assert_eq!(complex.abs(), 5.0);
+    assert_eq!(complex.arg(), (-4.0_f64).atan2(3.0));
+    assert_eq!(complex.polar(), (5.0, (-4.0_f64).atan2(3.0)));
+    assert!(RComplex::new(1, eval::<Value>("nil").unwrap()).is_err());
+
+    let c: Complex<f64> = eval("Complex(1.5, 2.5)").unwrap();
+    assert_eq!(c, Complex::new(1.5, 2.5));
+    rb_assert!(
+        "c == Complex(1, 2)",
+        c = RComplex::try_from(Complex::new(1, 2)).unwrap()
+    );
+    assert!(RComplex::try_from(Complex::new("1", "2")).is_err());
+    assert!(eval::<Complex<i64>>("1").is_err());
+    let complexes: Vec<Complex<i64>> = eval("[Complex(1, 2), Complex(3, 4)]").unwrap();
+    assert_eq!(complexes, vec![Complex::new(1, 2), Complex::new(3, 4)]);
+}
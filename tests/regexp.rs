@@ -0,0 +1,62 @@
+use magnus::{backref_get, define_global_function, eval, function, RMatch, RRegexp, RString};
+
+macro_rules! rb_assert {
+    ($s:literal) => {
+        assert!(magnus::eval::<bool>($s).unwrap())
+    };
+    ($s:literal, $($rest:tt)*) => {
+        let result: bool = magnus::eval!($s, $($rest)*).unwrap();
+        assert!(result)
+    };
+}
+
+fn last_match_start() -> Option<usize> {
+    backref_get().and_then(|m| m.begin(0).unwrap())
+}
+
+#[test]
+fn it_matches_regexps() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let regexp = RRegexp::new(r"(?<word>[a-z]+) (?<num>\d+)?", RRegexp::IGNORECASE).unwrap();
+    rb_assert!(r#"regexp == /(?<word>[a-z]+) (?<num>\d+)?/i"#, regexp);
+    assert_eq!(
+        regexp.source().unwrap().to_string().unwrap(),
+        r"(?<word>[a-z]+) (?<num>\d+)?"
+    );
+    assert_eq!(regexp.options().unwrap(), RRegexp::IGNORECASE);
+    assert!(RRegexp::new("[", 0).is_err());
+
+    let s = RString::new("é Cafe bar");
+    let m = regexp.match_at(s, 0).unwrap().unwrap();
+    assert_eq!(m.nth(0).unwrap().to_string().unwrap(), "Cafe ");
+    assert_eq!(m.begin(0).unwrap(), Some(3));
+    assert_eq!(m.end(0).unwrap(), Some(8));
+    assert_eq!(m.nth(1).unwrap().to_string().unwrap(), "Cafe");
+    assert!(m.nth(2).is_none());
+    assert_eq!(
+        m.named("word").unwrap().unwrap().to_string().unwrap(),
+        "Cafe"
+    );
+    assert!(m.named("num").unwrap().is_none());
+    assert!(m.named("missing").is_err());
+    assert_eq!(m.pre_match().to_string().unwrap(), "é ");
+    assert_eq!(m.post_match().to_string().unwrap(), "bar");
+    assert!(regexp.match_at(s, 8).unwrap().is_none());
+
+    let m: RMatch = eval(r#"/(\w)(x)?(\d)/.match("--a1--")"#).unwrap();
+    let captures = m
+        .captures()
+        .map(|c| c.map(|s| s.to_string().unwrap()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        captures,
+        vec![Some("a".to_owned()), None, Some("1".to_owned())]
+    );
+    assert_eq!(m.begin(2).unwrap(), None);
+    assert!(m.end(4).is_err());
+
+    define_global_function("last_match_start", function!(last_match_start, 0));
+    rb_assert!(r#"("xyz" =~ /z/) && last_match_start == 2"#);
+    rb_assert!(r#"("xyz" =~ /q/).nil? && last_match_start.nil?"#);
+}
@@ -0,0 +1,95 @@
+use std::collections::HashMap;
+
+use magnus::{
+    embed::init,
+    eval, exception,
+    serde::{from_value, to_value},
+    Value,
+};
+use serde::{Deserialize, Serialize};
+
+macro_rules! rb_assert {
+    ($s:literal) => {
+        assert!(magnus::eval::<bool>($s).unwrap())
+    };
+    ($s:literal, $($rest:tt)*) => {
+        let result: bool = magnus::eval!($s, $($rest)*).unwrap();
+        assert!(result)
+    };
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum Shape {
+    Point,
+    Circle(f64),
+    Rect { width: f64, height: f64 },
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Drawing {
+    name: String,
+    layers: Vec<Vec<Shape>>,
+    origin: (i64, i64),
+    tags: HashMap<String, u32>,
+    notes: Option<String>,
+}
+
+#[test]
+fn it_converts_with_serde() {
+    let _cleanup = unsafe { init() };
+
+    let mut tags = HashMap::new();
+    tags.insert("draft".to_owned(), 1);
+    let drawing = Drawing {
+        name: "sketch".to_owned(),
+        layers: vec![
+            vec![Shape::Point, Shape::Circle(1.5)],
+            vec![Shape::Rect {
+                width: 2.0,
+                height: 3.0,
+            }],
+        ],
+        origin: (-1, 1),
+        tags,
+        notes: None,
+    };
+
+    let val = to_value(&drawing).unwrap();
+    rb_assert!(
+        r#"val == {
+            name: "sketch",
+            layers: [[:Point, {Circle: 1.5}], [{Rect: {width: 2.0, height: 3.0}}]],
+            origin: [-1, 1],
+            tags: {"draft" => 1},
+            notes: nil,
+        }"#,
+        val
+    );
+
+    assert_eq!(from_value::<Drawing>(val).unwrap(), drawing);
+
+    let val: Value = eval(
+        r#"{
+            "name" => :sketch,
+            "layers" => [["Point"], [{"Circle" => 2.5}]],
+            "origin" => [0, 0],
+            "tags" => {},
+        }"#,
+    )
+    .unwrap();
+    let res = from_value::<Drawing>(val).unwrap();
+    assert_eq!(res.name, "sketch");
+    assert_eq!(
+        res.layers,
+        vec![vec![Shape::Point], vec![Shape::Circle(2.5)]]
+    );
+    assert_eq!(res.notes, None);
+
+    let val: Value = eval(r#"{name: 1}"#).unwrap();
+    let err = from_value::<Drawing>(val).unwrap_err();
+    assert!(err.is_kind_of(exception::type_error()));
+
+    let val: Value = eval(r#"[1, 2, 3]"#).unwrap();
+    let err = from_value::<(u8, u8)>(val).unwrap_err();
+    assert!(err.is_kind_of(exception::arg_error()));
+}
@@ -0,0 +1,24 @@
+use magnus::{eval, gc, RString};
+
+#[test]
+fn it_borrows_string_bytes() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let s = RString::new("hello");
+    let len = s
+        .with_bytes(|b| {
+            let res: Result<RString, _> = eval!(r#"s << " world""#, s);
+            assert!(res.is_err());
+            gc::start();
+            b.len()
+        })
+        .unwrap();
+    assert_eq!(len, 5);
+
+    // unlocked again once the closure returns
+    let _: RString = eval!(r#"s << " world""#, s).unwrap();
+    assert_eq!(s.with_str(|s| s.to_owned()).unwrap(), "hello world");
+
+    let nested = s.with_bytes(|_| s.with_bytes(|_| ())).unwrap();
+    assert!(nested.is_err());
+}
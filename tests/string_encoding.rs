@@ -0,0 +1,28 @@
+use magnus::{
+    encoding::{Encoding, Index, RbEncoding},
+    eval, RString, Value,
+};
+
+#[test]
+fn it_handles_string_encodings() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let binary = RString::enc_new([0xca, 0xfe], RbEncoding::ascii8bit());
+    assert_eq!(binary.encoding(), RbEncoding::ascii8bit());
+    assert!(binary.is_valid_encoding());
+
+    binary.force_encoding(Index::utf8()).unwrap();
+    assert_eq!(binary.encoding().name(), "UTF-8");
+    assert!(!binary.is_valid_encoding());
+    assert!(binary.conv(RbEncoding::utf8()).is_err());
+
+    let latin1: Value = eval(r#"Encoding::ISO_8859_1"#).unwrap();
+    let latin1 = latin1.try_convert::<Encoding>().unwrap();
+    let s = RString::new("café").conv(latin1).unwrap();
+    assert_eq!(s.encoding(), RbEncoding::from(latin1));
+    let res: bool = eval!(r#"s == "caf\xE9".force_encoding("ISO-8859-1")"#, s).unwrap();
+    assert!(res);
+
+    let frozen: RString = eval(r#""example".freeze"#).unwrap();
+    assert!(frozen.force_encoding(RbEncoding::ascii8bit()).is_err());
+}
@@ -0,0 +1,27 @@
+use magnus::{eval, exception, Error, RThread, Value};
+
+#[test]
+fn it_spawns_ruby_threads() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let t = RThread::spawn(|| {
+        eval::<Value>("Thread.stop")?;
+        let n: i64 = RThread::current().local_aref("n")?;
+        Ok::<_, Error>(n * 2)
+    })
+    .unwrap();
+    let _: Value = eval!("sleep 0.01 until t.stop?", t).unwrap();
+    assert!(t.is_alive());
+    t.local_aset("n", 21).unwrap();
+    t.run().unwrap();
+    assert_eq!(t.value::<i64>().unwrap(), 42);
+    assert!(!t.is_alive());
+    assert!(t.wakeup().is_err());
+
+    let t = RThread::spawn(|| -> Result<(), Error> {
+        Err(Error::new(exception::arg_error(), "bad input"))
+    })
+    .unwrap();
+    let _: Value = t.funcall("report_on_exception=", (false,)).unwrap();
+    assert!(t.join().unwrap_err().is_kind_of(exception::arg_error()));
+}
@@ -0,0 +1,86 @@
+use std::{
+    convert::TryFrom,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use chrono::{DateTime, FixedOffset, TimeZone, Utc};
+use magnus::{eval, RTime};
+use time::{OffsetDateTime, UtcOffset};
+
+macro_rules! rb_assert {
+    ($s:literal) => {
+        assert!(magnus::eval::<bool>($s).unwrap())
+    };
+    ($s:literal, $($rest:tt)*) => {
+        let result: bool = magnus::eval!($s, $($rest)*).unwrap();
+        assert!(result)
+    };
+}
+
+#[test]
+fn it_converts_times() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let t = RTime::new(1_234_567_890, 123_456_789);
+    rb_assert!("t == Time.at(1_234_567_890, 123_456_789, :nsec)", t);
+    assert!(!t.is_utc());
+    assert_eq!(t.to_timespec().unwrap(), (1_234_567_890, 123_456_789));
+
+    let utc = RTime::new_utc(-1, 5);
+    assert!(utc.is_utc());
+    assert_eq!(utc.utc_offset().unwrap(), 0);
+    assert_eq!(utc.to_timespec().unwrap(), (-1, 5));
+
+    let fixed = RTime::new_with_offset(0, 0, -9000).unwrap();
+    rb_assert!(r#"fixed.utc_offset == -9000 && fixed.hour == 21"#, fixed);
+    assert!(RTime::new_with_offset(0, 0, -86400).is_err());
+    assert!(eval::<RTime>("1").is_err());
+
+    let before_epoch = UNIX_EPOCH - Duration::new(1, 250_000_000);
+    rb_assert!("t == Time.at(-2, 750_000_000, :nsec)", t = before_epoch);
+    assert_eq!(
+        eval::<SystemTime>("Time.at(-2, 750_000_000, :nsec)").unwrap(),
+        before_epoch
+    );
+    let now = SystemTime::now();
+    rb_assert!("t.is_a?(Time)", t = now);
+
+    rb_assert!("d == 5 && d.is_a?(Integer)", d = Duration::from_secs(5));
+    rb_assert!("d == 3/2r", d = Duration::from_millis(1500));
+    rb_assert!("d == 1/1_000_000_000r", d = Duration::from_nanos(1));
+    assert_eq!(eval::<Duration>("3").unwrap(), Duration::from_secs(3));
+    assert_eq!(eval::<Duration>("0.3").unwrap(), Duration::from_millis(300));
+    assert_eq!(
+        eval::<Duration>("7/4r").unwrap(),
+        Duration::from_millis(1750)
+    );
+    assert!(eval::<Duration>("-1").is_err());
+    assert!(eval::<Duration>("nil").is_err());
+
+    let dt = FixedOffset::east_opt(19800)
+        .unwrap()
+        .timestamp_opt(1_000_000_000, 42)
+        .unwrap();
+    rb_assert!(
+        r#"t == Time.at(1_000_000_000, 42, :nsec) && t.utc_offset == 19800"#,
+        t = dt
+    );
+    let parsed: DateTime<FixedOffset> = eval(r#"Time.new(2001, 2, 3, 4, 5, 6, "+05:30")"#).unwrap();
+    assert_eq!(parsed.to_rfc3339(), "2001-02-03T04:05:06+05:30");
+    let utc: DateTime<Utc> = eval("Time.at(0, 1, :nsec)").unwrap();
+    assert_eq!(utc, Utc.timestamp_opt(0, 1).unwrap());
+
+    let odt = OffsetDateTime::from_unix_timestamp_nanos(1_500_000_000_000_000_007)
+        .unwrap()
+        .to_offset(UtcOffset::from_whole_seconds(-3600).unwrap());
+    rb_assert!(
+        r#"t == Time.at(1_500_000_000, 7, :nsec) && t.utc_offset == -3600"#,
+        t = RTime::try_from(odt).unwrap()
+    );
+    assert_eq!(
+        eval::<OffsetDateTime>(r#"Time.at(1_500_000_000, 7, :nsec).localtime("-01:00")"#).unwrap(),
+        odt
+    );
+    let far = odt.to_offset(UtcOffset::from_hms(25, 0, 0).unwrap());
+    assert!(RTime::try_from(far).is_err());
+}
@@ -0,0 +1,116 @@
+use magnus::{define_global_function, eval, function, Error, RArray, TypedArray, Value};
+
+macro_rules! rb_assert {
+    ($s:literal) => {
+        assert!(magnus::eval::<bool>($s).unwrap())
+    };
+    ($s:literal, $($rest:tt)*) => {
+        let result: bool = magnus::eval!($s, $($rest)*).unwrap();
+        assert!(result)
+    };
+}
+
+fn sum(nums: TypedArray<f64>) -> Result<f64, Error> {
+    nums.iter().sum()
+}
+
+#[test]
+fn it_checks_and_iterates_typed_arrays() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    define_global_function("sum", function!(sum, 1));
+    rb_assert!("sum([1, 2.5, 3]) == 6.5");
+    rb_assert!("sum([]) == 0");
+    rb_assert!(
+        r#"begin
+          sum([1, 2, "three"])
+          false
+        rescue TypeError => e
+          e.message.include?("at index 2")
+        end"#
+    );
+    rb_assert!(
+        r#"begin
+          sum(nil)
+          false
+        rescue TypeError
+          true
+        end"#
+    );
+
+    let ary: RArray = eval(r#"["a", "b", "c"]"#).unwrap();
+    let strings = ary.typed::<String>().unwrap();
+    assert_eq!(strings.len(), 3);
+    assert!(!strings.is_empty());
+    assert_eq!(strings.get(2).unwrap(), "c");
+    assert!(strings.get(3).is_err());
+    strings.push(String::from("d")).unwrap();
+    rb_assert!(r#"ary == ["a", "b", "c", "d"]"#, ary);
+
+    let ints: TypedArray<i64> = eval("[1, 2, 3]").unwrap();
+    let mut seen = Vec::new();
+    for (i, n) in ints.iter().enumerate() {
+        if i == 0 {
+            // changes during iteration aren't seen by the iterator
+            let _: Value = eval!("ints.clear", ints).unwrap();
+        }
+        seen.push(n.unwrap());
+    }
+    assert_eq!(seen, vec![1, 2, 3]);
+    assert!(ints.is_empty());
+
+    let _: Value = eval!(r#"ints.push(1, "two")"#, ints).unwrap();
+    assert_eq!(ints.get(0).unwrap(), 1);
+    assert!(ints.get(1).is_err());
+    let mut iter = ints.iter();
+    assert_eq!(iter.next().unwrap().unwrap(), 1);
+    assert!(iter.next().unwrap().is_err());
+    assert!(iter.next().is_none());
+    assert!(eval::<RArray>("[1, 2, :three]")
+        .unwrap()
+        .typed::<i64>()
+        .is_err());
+    assert!(eval::<RArray>("[1, 2, 2 ** 70]")
+        .unwrap()
+        .typed::<i64>()
+        .is_err());
+
+    let err = eval::<RArray>(r#"["a", "\xff".force_encoding("UTF-8")]"#)
+        .unwrap()
+        .typed::<String>()
+        .unwrap_err();
+    assert!(err.is_kind_of(magnus::exception::encoding_error()));
+    assert!(err.to_string().contains("at index 1"));
+
+    let err = eval::<RArray>(
+        r#"
+        o = Object.new
+        def o.to_str; raise ArgumentError, "bad"; end
+        [o]
+        "#,
+    )
+    .unwrap()
+    .typed::<String>()
+    .unwrap_err();
+    assert!(err.is_kind_of(magnus::exception::arg_error()));
+    match err {
+        Error::Exception(ex) => {
+            assert_eq!(ex.to_string(), "bad at index 0");
+            assert!(!ex.funcall::<_, _, Value>("backtrace", ()).unwrap().is_nil());
+        }
+        _ => panic!("expected a Ruby exception"),
+    }
+
+    let nested: TypedArray<Vec<String>> = eval(r#"[["a"], ["b", "c"]]"#).unwrap();
+    assert_eq!(nested.get(1).unwrap(), vec!["b", "c"]);
+    assert!(eval::<RArray>(r#"[["a"], ["b", 1]]"#)
+        .unwrap()
+        .typed::<Vec<String>>()
+        .is_err());
+
+    let frozen = eval::<RArray>("[1].freeze")
+        .unwrap()
+        .typed::<i64>()
+        .unwrap();
+    assert!(frozen.push(2).is_err());
+}
@@ -0,0 +1,77 @@
+use std::cell::RefCell;
+
+use magnus::{define_class, embed::init, method, prelude::*};
+
+macro_rules! rb_assert {
+    ($s:literal) => {
+        assert!(magnus::eval::<bool>($s).unwrap())
+    };
+    ($s:literal, $($rest:tt)*) => {
+        let result: bool = magnus::eval!($s, $($rest)*).unwrap();
+        assert!(result)
+    };
+}
+
+#[derive(Clone, Default)]
+#[magnus::wrap(class = "Counter", free_immediatly)]
+struct Counter(RefCell<usize>);
+
+impl Counter {
+    fn initialize(&self, start: usize) {
+        *self.0.borrow_mut() = start;
+    }
+
+    fn incr(&self) -> usize {
+        let mut count = self.0.borrow_mut();
+        *count += 1;
+        *count
+    }
+
+    fn count(&self) -> usize {
+        *self.0.borrow()
+    }
+}
+
+#[test]
+fn it_allocates_and_copies_wrapped_struct() {
+    let _cleanup = unsafe { init() };
+
+    let class = define_class("Counter", Default::default()).unwrap();
+    class.define_alloc_func::<Counter>();
+    class.define_initialize_copy::<Counter>();
+    class.define_method("initialize", method!(Counter::initialize, 1));
+    class.define_method("incr", method!(Counter::incr, 0));
+    class.define_method("count", method!(Counter::count, 0));
+
+    rb_assert!("Counter.allocate.count == 0");
+    rb_assert!("Counter.new(5).incr == 6");
+
+    rb_assert!(
+        r#"
+        class SubCounter < Counter
+          def incr
+            super * 10
+          end
+        end
+        c = SubCounter.new(1)
+        c.incr == 20 && c.class == SubCounter
+        "#
+    );
+
+    rb_assert!(
+        r#"
+        a = Counter.new(1)
+        b = a.dup
+        b.incr
+        a.count == 1 && b.count == 2
+        "#
+    );
+
+    rb_assert!(
+        r#"
+        a = Counter.new(1).freeze
+        b = a.clone
+        b.frozen? && b.count == 1
+        "#
+    );
+}
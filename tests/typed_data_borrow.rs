@@ -0,0 +1,88 @@
+use magnus::{
+    block::Proc, define_class, embed::init, eval, method, prelude::*, Error, RTypedData, Value,
+};
+
+macro_rules! rb_assert {
+    ($s:literal) => {
+        assert!(magnus::eval::<bool>($s).unwrap())
+    };
+    ($s:literal, $($rest:tt)*) => {
+        let result: bool = magnus::eval!($s, $($rest)*).unwrap();
+        assert!(result)
+    };
+}
+
+#[magnus::wrap(class = "Counter", free_immediatly)]
+struct Counter {
+    count: usize,
+}
+
+impl Counter {
+    fn count(&self) -> usize {
+        self.count
+    }
+
+    fn incr(&mut self) -> usize {
+        self.count += 1;
+        self.count
+    }
+
+    fn incr_with(&mut self, block: Value) -> Result<usize, Error> {
+        let n: usize = block.funcall("call", ())?;
+        self.count += n;
+        Ok(self.count)
+    }
+}
+
+#[test]
+fn it_tracks_borrows_of_wrapped_struct() {
+    let _cleanup = unsafe { init() };
+
+    let class = define_class("Counter", Default::default()).unwrap();
+    class.define_method("count", method!(Counter::count, 0));
+    class.define_method("incr", method!(Counter::incr, 0));
+    class.define_method("incr_with", method!(Counter::incr_with, 1));
+
+    let val = Value::from(Counter { count: 0 });
+    rb_assert!("val.incr == 1", val);
+    rb_assert!("val.incr == 2", val);
+    rb_assert!("val.incr_with(-> { 3 }) == 5", val);
+
+    rb_assert!(
+        r#"
+        begin
+          val.incr_with(-> { val.count })
+          false
+        rescue RuntimeError => e
+          e.message == "Counter already mutably borrowed"
+        end
+        "#,
+        val
+    );
+    rb_assert!("val.incr == 6", val);
+
+    // a reference from outside a method call or block is checked, but not
+    // tracked, as there is nothing to release it
+    let counter: &Counter = eval!("val", val).unwrap();
+    assert_eq!(counter.count, 6);
+    rb_assert!("val.incr == 7", val);
+    let data = RTypedData::from_value(val).unwrap();
+    let guard = data.get_mut::<Counter>().unwrap();
+    assert!(val.try_convert::<&Counter>().is_err());
+    assert!(val.try_convert::<&mut Counter>().is_err());
+    drop(guard);
+
+    // borrows in a block are released when it returns
+    let proc = Proc::from_fn(|args, _| {
+        let counter: &mut Counter = args[0].try_convert()?;
+        counter.count += 1;
+        Ok::<_, Error>(counter.count)
+    });
+    rb_assert!("proc.call(val) == 8 && val.incr == 9", proc, val);
+    let _: Value = val
+        .block_call("tap", (), |args, _| {
+            args[0].try_convert::<&Counter>().map(|c| c.count)
+        })
+        .unwrap();
+    rb_assert!("val.incr == 10", val);
+}
@@ -0,0 +1,63 @@
+use magnus::{eval, Integer, RBignum, Value};
+use num_bigint::{BigInt, BigUint};
+
+macro_rules! rb_assert {
+    ($s:literal) => {
+        assert!(magnus::eval::<bool>($s).unwrap())
+    };
+    ($s:literal, $($rest:tt)*) => {
+        let result: bool = magnus::eval!($s, $($rest)*).unwrap();
+        assert!(result)
+    };
+}
+
+#[test]
+fn it_converts_wide_integers() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    assert_eq!(eval::<i128>("2 ** 127 - 1").unwrap(), i128::MAX);
+    assert_eq!(eval::<i128>("-(2 ** 127)").unwrap(), i128::MIN);
+    assert_eq!(eval::<i128>("-5").unwrap(), -5);
+    assert!(eval::<i128>("2 ** 127").is_err());
+    assert!(eval::<i128>("-(2 ** 127) - 1").is_err());
+    assert_eq!(eval::<u128>("2 ** 128 - 1").unwrap(), u128::MAX);
+    assert_eq!(eval::<u128>("2 ** 64").unwrap(), 1 << 64);
+    assert!(eval::<u128>("2 ** 128").is_err());
+    assert!(eval::<u128>("-1").is_err());
+
+    rb_assert!("n == 2 ** 127 - 1", n = i128::MAX);
+    rb_assert!("n == -(2 ** 127)", n = i128::MIN);
+    rb_assert!("n == -5", n = -5_i128);
+    rb_assert!("n == 2 ** 128 - 1", n = u128::MAX);
+    rb_assert!("n == 7", n = 7_u128);
+    assert!(Integer::from_i128(i128::MIN) == i128::MIN);
+
+    let uuid: u128 = 0x1234_5678_9abc_def0_0fed_cba9_8765_4321;
+    let val = Value::from(uuid);
+    rb_assert!("val == 0x123456789abcdef00fedcba987654321", val);
+    assert_eq!(
+        eval::<u128>("0x123456789abcdef00fedcba987654321").unwrap(),
+        uuid
+    );
+
+    let big = RBignum::from_bytes_le(&uuid.to_le_bytes()).unwrap();
+    assert_eq!(big.to_u128().unwrap(), uuid);
+    assert_eq!(big.to_bytes_le(), uuid.to_le_bytes().to_vec());
+    let neg: RBignum = eval("-(2 ** 80)").unwrap();
+    assert_eq!(
+        neg.to_bytes_le(),
+        (1_u128 << 80).to_le_bytes()[..11].to_vec()
+    );
+    assert!(RBignum::from_bytes_le(&[42]).is_err());
+
+    let bigint: BigInt = eval("-(3 ** 100)").unwrap();
+    assert_eq!(bigint, -BigInt::from(3).pow(100));
+    rb_assert!("n == -(3 ** 100)", n = bigint);
+    assert_eq!(eval::<BigInt>("-12").unwrap(), BigInt::from(-12));
+    rb_assert!("n == 12", n = BigInt::from(12));
+    let biguint: BigUint = eval("5 ** 80").unwrap();
+    assert_eq!(biguint, BigUint::from(5_u32).pow(80));
+    rb_assert!("n == 5 ** 80", n = biguint);
+    assert!(eval::<BigUint>("-(5 ** 80)").is_err());
+    assert!(eval::<BigUint>("-1").is_err());
+}